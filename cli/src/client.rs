@@ -0,0 +1,325 @@
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::{anyhow, bail, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use dex::states::DexState;
+use solana_client::{nonce_utils, rpc_client::RpcClient, rpc_config::RpcSimulateTransactionConfig};
+use solana_sdk::{
+    commitment_config::CommitmentConfig, hash::Hash, instruction::Instruction, message::Message,
+    pubkey::Pubkey, signature::Keypair, transaction::Transaction,
+};
+use std::io::{self, BufRead, Write};
+
+/// What is done with the transactions of the admin instructions
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    /// Sign with the keypair and send
+    Send,
+    /// Simulate without signature and print the logs
+    DryRun,
+    /// Print the unsigned transaction to sign it offline
+    Unsigned,
+}
+
+/// Durable nonce of the unsigned transactions, which then don't expire with a recent blockhash
+#[derive(Clone, Copy, Debug)]
+pub struct DurableNonce {
+    pub account: Pubkey,
+    pub authority: Pubkey,
+}
+
+pub struct Client {
+    rpc: RpcClient,
+    url: String,
+    signer: Option<Keypair>,
+    authority: Pubkey,
+    mode: Mode,
+    nonce: Option<DurableNonce>,
+    skip_confirmation: bool,
+}
+
+impl Client {
+    pub fn new(
+        url: String,
+        signer: Option<Keypair>,
+        authority: Pubkey,
+        mode: Mode,
+        nonce: Option<DurableNonce>,
+        skip_confirmation: bool,
+    ) -> Self {
+        Self {
+            rpc: RpcClient::new_with_commitment(url.clone(), CommitmentConfig::confirmed()),
+            url,
+            signer,
+            authority,
+            mode,
+            nonce,
+            skip_confirmation,
+        }
+    }
+    /// Admin and fee payer of the transactions
+    pub fn authority(&self) -> Pubkey {
+        self.authority
+    }
+    pub fn account_data(&self, address: &Pubkey) -> Result<Vec<u8>> {
+        self.rpc
+            .get_account_data(address)
+            .map_err(|error| anyhow!("failed to fetch account {address}: {error}"))
+    }
+    pub fn account_owner(&self, address: &Pubkey) -> Result<Pubkey> {
+        self.rpc
+            .get_account(address)
+            .map(|account| account.owner)
+            .map_err(|error| anyhow!("failed to fetch account {address}: {error}"))
+    }
+    pub fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<T> {
+        decode_account(&self.account_data(address)?)
+    }
+    /// Account of a program, `None` when it doesn't exist
+    pub fn optional_account<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<Option<T>> {
+        self.rpc
+            .get_account_with_commitment(address, self.rpc.commitment())
+            .map_err(|error| anyhow!("failed to fetch account {address}: {error}"))?
+            .value
+            .map(|account| decode_account(&account.data))
+            .transpose()
+    }
+    pub fn dex_state(&self, address: &Pubkey) -> Result<DexState> {
+        decode_dex_state(&self.account_data(address)?)
+    }
+    pub fn process(&self, instructions: &[Instruction]) -> Result<()> {
+        let mut transaction = Transaction::new_unsigned(build_message(
+            instructions,
+            &self.authority,
+            self.nonce.as_ref(),
+        ));
+
+        match self.mode {
+            Mode::DryRun => {
+                let result = self
+                    .rpc
+                    .simulate_transaction_with_config(
+                        &transaction,
+                        RpcSimulateTransactionConfig {
+                            sig_verify: false,
+                            replace_recent_blockhash: true,
+                            commitment: Some(self.rpc.commitment()),
+                            ..Default::default()
+                        },
+                    )?
+                    .value;
+
+                for log in result.logs.unwrap_or_default() {
+                    println!("{log}");
+                }
+                if let Some(units) = result.units_consumed {
+                    println!("Compute units: {units}");
+                }
+                match result.err {
+                    Some(error) => bail!("simulation failed: {error}"),
+                    None => println!("Simulation succeeded"),
+                }
+            }
+            Mode::Unsigned => {
+                transaction.message.recent_blockhash = match &self.nonce {
+                    Some(nonce) => self.nonce_blockhash(nonce)?,
+                    None => self.rpc.get_latest_blockhash()?,
+                };
+                println!("{}", encode_transaction(&transaction)?);
+            }
+            Mode::Send => {
+                let signer = self
+                    .signer
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("a keypair is required to send transactions"))?;
+                if !self.confirm(&transaction)? {
+                    bail!("transaction not sent");
+                }
+
+                transaction.try_sign(&[signer], self.rpc.get_latest_blockhash()?)?;
+                self.send(&transaction)?;
+            }
+        }
+
+        Ok(())
+    }
+    /// Send a transaction signed offline
+    pub fn send(&self, transaction: &Transaction) -> Result<()> {
+        if !transaction.is_signed() {
+            bail!("transaction is not fully signed");
+        }
+
+        let signature = self
+            .rpc
+            .send_and_confirm_transaction_with_spinner(transaction)?;
+        println!("Signature: {signature}");
+
+        Ok(())
+    }
+    /// Blockhash stored in the durable nonce account, checking its authority
+    fn nonce_blockhash(&self, nonce: &DurableNonce) -> Result<Hash> {
+        let account = nonce_utils::get_account_with_commitment(
+            &self.rpc,
+            &nonce.account,
+            self.rpc.commitment(),
+        )?;
+        let data = nonce_utils::data_from_account(&account)?;
+        if data.authority != nonce.authority {
+            bail!(
+                "the authority of the nonce account {} is {}, not {}",
+                nonce.account,
+                data.authority,
+                nonce.authority
+            );
+        }
+
+        Ok(data.blockhash())
+    }
+    fn confirm(&self, transaction: &Transaction) -> Result<bool> {
+        eprintln!("Cluster: {}", self.url);
+        print_transaction(transaction);
+
+        if self.skip_confirmation {
+            return Ok(true);
+        }
+
+        eprint!("Send the transaction? [y/N] ");
+        io::stderr().flush()?;
+        let mut answer = String::new();
+        io::stdin().lock().read_line(&mut answer)?;
+
+        Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+    }
+}
+
+/// Message paid by the payer, advancing the durable nonce first when there is one
+pub fn build_message(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    nonce: Option<&DurableNonce>,
+) -> Message {
+    match nonce {
+        Some(nonce) => Message::new_with_nonce(
+            instructions.to_vec(),
+            Some(payer),
+            &nonce.account,
+            &nonce.authority,
+        ),
+        None => Message::new(instructions, Some(payer)),
+    }
+}
+
+/// Sign the fee payer or another signature of a transaction printed by the unsigned mode
+pub fn sign_transaction(encoded: &str, signer: &Keypair) -> Result<String> {
+    let mut transaction = decode_transaction(encoded)?;
+    print_transaction(&transaction);
+
+    let recent_blockhash = transaction.message.recent_blockhash;
+    transaction.try_partial_sign(&[signer], recent_blockhash)?;
+    encode_transaction(&transaction)
+}
+
+/// Summary of a transaction printed to stderr before it is signed
+pub fn print_transaction(transaction: &Transaction) {
+    let message = &transaction.message;
+    eprintln!("Fee payer: {}", message.account_keys[0]);
+    for instruction in &message.instructions {
+        eprintln!(
+            "Instruction: program {}, {} accounts, {} bytes of data",
+            message.account_keys[usize::from(instruction.program_id_index)],
+            instruction.accounts.len(),
+            instruction.data.len()
+        );
+    }
+}
+
+pub fn encode_transaction(transaction: &Transaction) -> Result<String> {
+    Ok(STANDARD.encode(bincode::serialize(transaction)?))
+}
+
+pub fn decode_transaction(encoded: &str) -> Result<Transaction> {
+    Ok(bincode::deserialize(&STANDARD.decode(encoded.trim())?)?)
+}
+
+pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..]).map_err(|error| anyhow!("{error}"))
+}
+
+/// The dex state is a zero copy account without borsh deserialization
+pub fn decode_dex_state(data: &[u8]) -> Result<DexState> {
+    if data.len() < DexState::LEN || data[..8] != DexState::DISCRIMINATOR {
+        bail!("account is not a dex state");
+    }
+    Ok(bytemuck::pod_read_unaligned(&data[8..DexState::LEN]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_sdk::{hash::Hash, signature::Signer, system_instruction};
+
+    #[test]
+    fn sign_transaction_test() {
+        let payer = Keypair::new();
+        let mut transaction = Transaction::new_with_payer(
+            &[system_instruction::transfer(
+                &payer.pubkey(),
+                &Pubkey::new_unique(),
+                1,
+            )],
+            Some(&payer.pubkey()),
+        );
+        transaction.message.recent_blockhash = Hash::new_unique();
+        let unsigned = encode_transaction(&transaction).unwrap();
+
+        let signed = decode_transaction(&sign_transaction(&unsigned, &payer).unwrap()).unwrap();
+        assert!(signed.is_signed());
+        assert_eq!(signed.message, transaction.message);
+        assert!(signed.verify().is_ok());
+    }
+
+    #[test]
+    fn build_message_test() {
+        let payer = Pubkey::new_unique();
+        let instruction = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+
+        let message = build_message(std::slice::from_ref(&instruction), &payer, None);
+        assert_eq!(message.instructions.len(), 1);
+
+        // the nonce authority signs the nonce advance, the first instruction
+        let nonce = DurableNonce {
+            account: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+        };
+        let message = build_message(&[instruction], &payer, Some(&nonce));
+        assert_eq!(message.instructions.len(), 2);
+        assert_eq!(message.program_id(0), Some(&solana_sdk::system_program::ID));
+        assert_eq!(message.account_keys[0], payer);
+        assert!(message.is_signer(
+            message
+                .account_keys
+                .iter()
+                .position(|key| *key == nonce.authority)
+                .unwrap()
+        ));
+        assert!(message.is_writable(
+            message
+                .account_keys
+                .iter()
+                .position(|key| *key == nonce.account)
+                .unwrap()
+        ));
+    }
+
+    #[test]
+    fn decode_dex_state_test() {
+        let mut data = DexState::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&DexState {
+            vault_reserve_bound: 1_000,
+            ..Default::default()
+        }));
+
+        let dex_state = decode_dex_state(&data).unwrap();
+        assert_eq!({ dex_state.vault_reserve_bound }, 1_000);
+        assert!(decode_dex_state(&data[..data.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,369 @@
+use crate::client::Client;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anyhow::{anyhow, bail, Result};
+use clap::{ArgAction, Subcommand};
+use dex::{
+    client::{
+        instructions::{self, LaunchDexKeys},
+        pda,
+    },
+    states::{AuthorityManager, ConfigState, FeeDistributionState, FeeRecipient},
+};
+use solana_sdk::{compute_budget::ComputeBudgetInstruction, pubkey::Pubkey};
+
+/// The whirlpool launch creates the pool, its tick arrays and the position over the default limit
+const LAUNCH_DEX_CLMM_COMPUTE_UNITS: u32 = 400_000;
+
+#[derive(Subcommand)]
+pub enum DexCommand {
+    /// Print the authority manager
+    ShowAuthorityManager,
+    /// Print a config
+    ShowConfig {
+        #[arg(long, default_value_t = 0)]
+        index: u16,
+    },
+    /// Print a dex state
+    ShowDex {
+        dex_state: Pubkey,
+    },
+    /// Print the fee recipients of the withdrawn dex fees
+    ShowFeeRecipients,
+    /// Create the authority manager, the payer becomes the admin
+    InitializeAuthorityManager {
+        cpi_authority: Pubkey,
+    },
+    UpdateAdmin {
+        admin: Pubkey,
+    },
+    /// Update the launcher authority allowed to initialize dexes
+    UpdateCpiAuthority {
+        cpi_authority: Pubkey,
+    },
+    InitializeConfig {
+        index: u16,
+    },
+    /// Update a setting of a config
+    UpdateConfig {
+        #[arg(long, default_value_t = 0)]
+        index: u16,
+        #[command(subcommand)]
+        setting: ConfigSetting,
+    },
+    /// Replace the fee recipients, each recipient is WALLET:WEIGHT_BPS
+    UpdateFeeRecipients {
+        #[arg(value_parser = parse_fee_recipient)]
+        recipients: Vec<FeeRecipient>,
+    },
+    /// Withdraw the dex fees to the associated token accounts of the fee recipients
+    WithdrawDexFee {
+        dex_state: Pubkey,
+        /// Wallet receiving all the fees until the fee recipients are set, defaults to the admin
+        #[arg(long)]
+        recipient: Option<Pubkey>,
+    },
+    /// Launch a dex ready to launch to Raydium CP-Swap
+    LaunchDex {
+        dex_state: Pubkey,
+        #[arg(long)]
+        amm_config: Pubkey,
+        /// Lamports paid by the admin on top of the dex launch lamports
+        #[arg(long, default_value_t = 0)]
+        shared_lamports: u64,
+    },
+    /// Launch a dex ready to launch to an Orca Whirlpool full range position
+    LaunchDexClmm {
+        dex_state: Pubkey,
+        #[arg(long, default_value_t = 0)]
+        shared_lamports: u64,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigSetting {
+    SwapFeeRate {
+        rate: u64,
+    },
+    LaunchFeeRate {
+        rate: u64,
+    },
+    VaultReserveBound {
+        amount: u64,
+    },
+    InitialReserve {
+        amount: u64,
+    },
+    CurveType {
+        curve_type: u8,
+    },
+    VirtualReserve {
+        amount: u64,
+    },
+    VirtualSupply {
+        amount: u64,
+    },
+    CreatorFeeRate {
+        rate: u64,
+    },
+    ReferralFeeRate {
+        rate: u64,
+    },
+    /// Disabled configs can not be used to create new dex
+    Disabled {
+        #[arg(action = ArgAction::Set)]
+        disabled: bool,
+    },
+    /// Anti-sniper window and its buy limits
+    SniperProtection {
+        window: u64,
+        max_swap_input: u64,
+        max_wallet_input: u64,
+    },
+    LaunchWindow {
+        seconds: u64,
+    },
+    /// Lamports collected from the dex creator to fund the launch and the keeper share
+    LaunchLamports {
+        launch_lamports: u64,
+        keeper_reward: u64,
+    },
+    LaunchTarget {
+        launch_target: u8,
+    },
+    /// Share of the LP tokens burned at the Raydium launch and vesting of the locked rest
+    LpPolicy {
+        burn_rate: u64,
+        lock_cliff: u64,
+        vesting_period: u64,
+    },
+    /// Swap fee rate of a new dex decaying to the swap fee rate
+    SniperFee {
+        rate: u64,
+        period: u64,
+        steps: u64,
+    },
+}
+
+impl DexCommand {
+    pub fn run(self, client: &Client) -> Result<()> {
+        let admin = client.authority();
+
+        match self {
+            DexCommand::ShowAuthorityManager => {
+                let address = pda::authority_manager_address().0;
+                let state: AuthorityManager = client.account(&address)?;
+                println!("{address}\n{state:#?}");
+            }
+            DexCommand::ShowConfig { index } => {
+                let address = pda::config_address(index).0;
+                let state: ConfigState = client.account(&address)?;
+                println!("{address}\n{state:#?}");
+            }
+            DexCommand::ShowDex { dex_state } => {
+                let state = client.dex_state(&dex_state)?;
+                println!("{dex_state}\n{state:#?}");
+            }
+            DexCommand::ShowFeeRecipients => {
+                let address = pda::fee_distribution_address().0;
+                let state: FeeDistributionState = client.account(&address)?;
+                println!("{address}");
+                for recipient in state.get_recipients() {
+                    println!("{} {}", recipient.wallet, recipient.weight_bps);
+                }
+            }
+            DexCommand::InitializeAuthorityManager { cpi_authority } => {
+                client.process(&[instructions::initialize_authority_manager(
+                    admin,
+                    cpi_authority,
+                )])?;
+            }
+            DexCommand::UpdateAdmin { admin: new_admin } => {
+                client.process(&[instructions::update_authority_manager_admin(
+                    admin, new_admin,
+                )])?;
+            }
+            DexCommand::UpdateCpiAuthority { cpi_authority } => {
+                client.process(&[instructions::update_authority_manager_cpi_authority(
+                    admin,
+                    cpi_authority,
+                )])?;
+            }
+            DexCommand::InitializeConfig { index } => {
+                client.process(&[instructions::initialize_config(admin, index)])?;
+            }
+            DexCommand::UpdateConfig { index, setting } => {
+                let config = pda::config_address(index).0;
+                client.process(&[setting.instruction(admin, config)])?;
+            }
+            DexCommand::UpdateFeeRecipients { recipients } => {
+                client.process(&[instructions::update_fee_recipients(admin, recipients)])?;
+            }
+            DexCommand::WithdrawDexFee {
+                dex_state,
+                recipient,
+            } => {
+                let state = client.dex_state(&dex_state)?;
+                // without the distribution account the program pays the single recipient passed
+                let fee_distribution: FeeDistributionState =
+                    match client.optional_account(&pda::fee_distribution_address().0)? {
+                        Some(_) if recipient.is_some() => {
+                            bail!("the fee recipients are set, --recipient is only used before")
+                        }
+                        Some(fee_distribution) => fee_distribution,
+                        None => FeeDistributionState::single_recipient(recipient.unwrap_or(admin)),
+                    };
+                let recipient_token_accounts = fee_distribution
+                    .get_recipients()
+                    .iter()
+                    .map(|recipient| {
+                        (
+                            get_associated_token_address_with_program_id(
+                                &recipient.wallet,
+                                &state.token_0_mint,
+                                &state.token_0_program,
+                            ),
+                            get_associated_token_address_with_program_id(
+                                &recipient.wallet,
+                                &state.token_1_mint,
+                                &state.token_1_program,
+                            ),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                client.process(&[instructions::withdraw_dex_fee(
+                    admin,
+                    dex_state,
+                    state.token_0_mint,
+                    state.token_1_mint,
+                    &recipient_token_accounts,
+                    Vec::new(),
+                )])?;
+            }
+            DexCommand::LaunchDex {
+                dex_state,
+                amm_config,
+                shared_lamports,
+            } => {
+                let keys = launch_dex_keys(client, dex_state)?;
+                client.process(&[instructions::launch_dex(&keys, amm_config, shared_lamports)])?;
+            }
+            DexCommand::LaunchDexClmm {
+                dex_state,
+                shared_lamports,
+            } => {
+                let keys = launch_dex_keys(client, dex_state)?;
+                client.process(&[
+                    ComputeBudgetInstruction::set_compute_unit_limit(LAUNCH_DEX_CLMM_COMPUTE_UNITS),
+                    instructions::launch_dex_clmm(&keys, shared_lamports),
+                ])?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ConfigSetting {
+    fn instruction(self, admin: Pubkey, config: Pubkey) -> solana_sdk::instruction::Instruction {
+        match self {
+            ConfigSetting::SwapFeeRate { rate } => {
+                instructions::update_config_swap_fee_rate(admin, config, rate)
+            }
+            ConfigSetting::LaunchFeeRate { rate } => {
+                instructions::update_config_launch_fee_rate(admin, config, rate)
+            }
+            ConfigSetting::VaultReserveBound { amount } => {
+                instructions::update_config_vault_reserve_bound(admin, config, amount)
+            }
+            ConfigSetting::InitialReserve { amount } => {
+                instructions::update_config_initial_reserve(admin, config, amount)
+            }
+            ConfigSetting::CurveType { curve_type } => {
+                instructions::update_config_curve_type(admin, config, curve_type)
+            }
+            ConfigSetting::VirtualReserve { amount } => {
+                instructions::update_config_virtual_reserve(admin, config, amount)
+            }
+            ConfigSetting::VirtualSupply { amount } => {
+                instructions::update_config_virtual_supply(admin, config, amount)
+            }
+            ConfigSetting::CreatorFeeRate { rate } => {
+                instructions::update_config_creator_fee_rate(admin, config, rate)
+            }
+            ConfigSetting::ReferralFeeRate { rate } => {
+                instructions::update_config_referral_fee_rate(admin, config, rate)
+            }
+            ConfigSetting::Disabled { disabled } => {
+                instructions::update_config_disabled(admin, config, disabled)
+            }
+            ConfigSetting::SniperProtection {
+                window,
+                max_swap_input,
+                max_wallet_input,
+            } => instructions::update_config_sniper_protection(
+                admin,
+                config,
+                window,
+                max_swap_input,
+                max_wallet_input,
+            ),
+            ConfigSetting::LaunchWindow { seconds } => {
+                instructions::update_config_launch_window(admin, config, seconds)
+            }
+            ConfigSetting::LaunchLamports {
+                launch_lamports,
+                keeper_reward,
+            } => instructions::update_config_launch_lamports(
+                admin,
+                config,
+                launch_lamports,
+                keeper_reward,
+            ),
+            ConfigSetting::LaunchTarget { launch_target } => {
+                instructions::update_config_launch_target(admin, config, launch_target)
+            }
+            ConfigSetting::LpPolicy {
+                burn_rate,
+                lock_cliff,
+                vesting_period,
+            } => instructions::update_config_lp_policy(
+                admin,
+                config,
+                burn_rate,
+                lock_cliff,
+                vesting_period,
+            ),
+            ConfigSetting::SniperFee {
+                rate,
+                period,
+                steps,
+            } => instructions::update_config_sniper_fee(admin, config, rate, period, steps),
+        }
+    }
+}
+
+fn launch_dex_keys(client: &Client, dex_state: Pubkey) -> Result<LaunchDexKeys> {
+    let state = client.dex_state(&dex_state)?;
+
+    Ok(LaunchDexKeys {
+        payer: client.authority(),
+        dex_config: state.config,
+        dex_state,
+        token_0_mint: state.token_0_mint,
+        token_1_mint: state.token_1_mint,
+        token_0_program: state.token_0_program,
+        token_1_program: state.token_1_program,
+    })
+}
+
+fn parse_fee_recipient(value: &str) -> Result<FeeRecipient> {
+    let (wallet, weight_bps) = value
+        .split_once(':')
+        .ok_or_else(|| anyhow!("expected WALLET:WEIGHT_BPS"))?;
+
+    Ok(FeeRecipient {
+        wallet: wallet.parse()?,
+        weight_bps: weight_bps.parse()?,
+    })
+}
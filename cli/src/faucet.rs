@@ -0,0 +1,149 @@
+use crate::client::Client;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anyhow::{anyhow, Result};
+use clap::Subcommand;
+use faucet::{
+    client::{instructions, pda},
+    states::{AuthorityManager, FaucetClaim},
+};
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Subcommand)]
+pub enum FaucetCommand {
+    /// Print the authority manager
+    ShowAuthorityManager,
+    /// Print the faucet claim of a mint
+    ShowClaim {
+        mint: Pubkey,
+    },
+    /// Create the authority manager, the payer becomes the first admin
+    InitializeAuthorityManager,
+    SetAdmin {
+        index: u64,
+        admin: Pubkey,
+    },
+    RemoveAdmin {
+        index: u64,
+    },
+    InitializeVault {
+        mint: Pubkey,
+    },
+    InitializeClaim {
+        mint: Pubkey,
+    },
+    /// Add the next shard to the faucet claim of a mint
+    InitializeShard {
+        mint: Pubkey,
+        /// Merkle root of the shard as 64 hex characters
+        #[arg(value_parser = parse_merkle_root)]
+        merkle_root: [u8; 32],
+    },
+    DestroyShard {
+        mint: Pubkey,
+        shard_index: u16,
+    },
+    /// Destroy the claim, after every shard of the claim is destroyed
+    DestroyClaim {
+        mint: Pubkey,
+    },
+    /// Withdraw the unclaimed tokens of a finished claim to the payer associated token account
+    WithdrawExpired {
+        mint: Pubkey,
+    },
+}
+
+impl FaucetCommand {
+    pub fn run(self, client: &Client) -> Result<()> {
+        let admin = client.authority();
+
+        match self {
+            FaucetCommand::ShowAuthorityManager => {
+                let address = pda::authority_manager_address().0;
+                let state: AuthorityManager = client.account(&address)?;
+                println!("{address}\n{state:#?}");
+            }
+            FaucetCommand::ShowClaim { mint } => {
+                let address = pda::faucet_claim_address(&mint).0;
+                let state: FaucetClaim = client.account(&address)?;
+                println!("{address}\n{state:#?}");
+            }
+            FaucetCommand::InitializeAuthorityManager => {
+                client.process(&[instructions::initialize_authority_manager(admin)])?;
+            }
+            FaucetCommand::SetAdmin {
+                index,
+                admin: new_admin,
+            } => {
+                client.process(&[instructions::set_admin(admin, index, new_admin)])?;
+            }
+            FaucetCommand::RemoveAdmin { index } => {
+                client.process(&[instructions::remove_admin(admin, index)])?;
+            }
+            FaucetCommand::InitializeVault { mint } => {
+                let token_program = client.account_owner(&mint)?;
+                client.process(&[instructions::initialize_faucet_vault(
+                    admin,
+                    mint,
+                    token_program,
+                )])?;
+            }
+            FaucetCommand::InitializeClaim { mint } => {
+                let token_program = client.account_owner(&mint)?;
+                client.process(&[instructions::initialize_faucet_claim(
+                    admin,
+                    mint,
+                    token_program,
+                )])?;
+            }
+            FaucetCommand::InitializeShard { mint, merkle_root } => {
+                let token_program = client.account_owner(&mint)?;
+                let faucet_claim: FaucetClaim =
+                    client.account(&pda::faucet_claim_address(&mint).0)?;
+                client.process(&[instructions::initialize_faucet_claim_shard(
+                    admin,
+                    mint,
+                    token_program,
+                    faucet_claim.shards,
+                    merkle_root,
+                )])?;
+            }
+            FaucetCommand::DestroyShard { mint, shard_index } => {
+                client.process(&[instructions::destroy_faucet_claim_shard(
+                    admin,
+                    mint,
+                    shard_index,
+                )])?;
+            }
+            FaucetCommand::DestroyClaim { mint } => {
+                client.process(&[instructions::destroy_faucet_claim(admin, mint)])?;
+            }
+            FaucetCommand::WithdrawExpired { mint } => {
+                let token_program = client.account_owner(&mint)?;
+                let payer_vault =
+                    get_associated_token_address_with_program_id(&admin, &mint, &token_program);
+                client.process(&[instructions::withdraw_expired_faucet_claim(
+                    admin,
+                    payer_vault,
+                    mint,
+                    token_program,
+                )])?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_merkle_root(value: &str) -> Result<[u8; 32]> {
+    let value = value.trim_start_matches("0x");
+    if value.len() != 64 || !value.is_ascii() {
+        return Err(anyhow!("expected 32 bytes as 64 hex characters"));
+    }
+
+    let mut merkle_root = [0u8; 32];
+    for (index, byte) in merkle_root.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[index * 2..index * 2 + 2], 16)?;
+    }
+
+    Ok(merkle_root)
+}
@@ -0,0 +1,102 @@
+use crate::client::Client;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anyhow::Result;
+use clap::Subcommand;
+use launcher::{
+    client::{instructions, pda},
+    states::{AuthorityManager, ConfigState},
+};
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Subcommand)]
+pub enum LauncherCommand {
+    /// Print the authority manager
+    ShowAuthorityManager,
+    /// Print the config
+    ShowConfig,
+    /// Create the authority manager, the payer becomes the admin
+    InitializeAuthorityManager {
+        faucet_authority: Pubkey,
+    },
+    UpdateAdmin {
+        admin: Pubkey,
+    },
+    /// Update the authority funding the faucet claims
+    UpdateFaucetAuthority {
+        faucet_authority: Pubkey,
+    },
+    InitializeConfig,
+    /// Tokens of each new mint kept in the team vault
+    UpdateTeamTokens {
+        amount: u64,
+    },
+    /// Tokens of each new mint sent to the faucet vault
+    UpdateFaucetTokens {
+        amount: u64,
+    },
+    /// Withdraw the team vault of a mint, to the admin associated token account by default
+    WithdrawTeamTokens {
+        mint: Pubkey,
+        #[arg(long)]
+        recipient: Option<Pubkey>,
+    },
+}
+
+impl LauncherCommand {
+    pub fn run(self, client: &Client) -> Result<()> {
+        let admin = client.authority();
+
+        match self {
+            LauncherCommand::ShowAuthorityManager => {
+                let address = pda::authority_manager_address().0;
+                let state: AuthorityManager = client.account(&address)?;
+                println!("{address}\n{state:#?}");
+            }
+            LauncherCommand::ShowConfig => {
+                let address = pda::config_address().0;
+                let state: ConfigState = client.account(&address)?;
+                println!("{address}\n{state:#?}");
+            }
+            LauncherCommand::InitializeAuthorityManager { faucet_authority } => {
+                client.process(&[instructions::initialize_authority_manager(
+                    admin,
+                    faucet_authority,
+                )])?;
+            }
+            LauncherCommand::UpdateAdmin { admin: new_admin } => {
+                client.process(&[instructions::update_authority_manager_admin(
+                    admin, new_admin,
+                )])?;
+            }
+            LauncherCommand::UpdateFaucetAuthority { faucet_authority } => {
+                client.process(&[instructions::update_authority_manager_faucet_authority(
+                    admin,
+                    faucet_authority,
+                )])?;
+            }
+            LauncherCommand::InitializeConfig => {
+                client.process(&[instructions::initialize_config(admin)])?;
+            }
+            LauncherCommand::UpdateTeamTokens { amount } => {
+                client.process(&[instructions::update_config_team_tokens(admin, amount)])?;
+            }
+            LauncherCommand::UpdateFaucetTokens { amount } => {
+                client.process(&[instructions::update_config_faucet_tokens(admin, amount)])?;
+            }
+            LauncherCommand::WithdrawTeamTokens { mint, recipient } => {
+                let token_program = client.account_owner(&mint)?;
+                let recipient = recipient.unwrap_or_else(|| {
+                    get_associated_token_address_with_program_id(&admin, &mint, &token_program)
+                });
+                client.process(&[instructions::withdraw_team_tokens(
+                    admin,
+                    mint,
+                    token_program,
+                    recipient,
+                )])?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+mod client;
+mod dex;
+mod faucet;
+mod launcher;
+
+use crate::client::{Client, DurableNonce, Mode};
+use anyhow::{anyhow, bail, Result};
+use clap::{Parser, Subcommand};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+};
+use std::path::PathBuf;
+
+/// Admin tool of the dex, launcher and faucet programs
+#[derive(Parser)]
+#[command(name = "launchpad", version)]
+struct Cli {
+    /// RPC URL or moniker: localhost, devnet, testnet, mainnet-beta
+    #[arg(long, short = 'u', global = true, default_value = "localhost")]
+    url: String,
+    /// Keypair of the admin, defaults to the Solana CLI keypair
+    #[arg(long, short = 'k', global = true)]
+    keypair: Option<PathBuf>,
+    /// Admin and fee payer when it is not the keypair, used to build unsigned transactions
+    #[arg(long, global = true)]
+    authority: Option<Pubkey>,
+    /// Simulate the transaction without signing it
+    #[arg(long, global = true, conflicts_with = "unsigned")]
+    dry_run: bool,
+    /// Print the unsigned transaction as base64 to sign it offline
+    #[arg(long, global = true)]
+    unsigned: bool,
+    /// Durable nonce account of the unsigned transaction, which then doesn't expire
+    #[arg(long, global = true, requires = "unsigned")]
+    nonce: Option<Pubkey>,
+    /// Authority of the durable nonce account, defaults to the authority
+    #[arg(long, global = true, requires = "nonce")]
+    nonce_authority: Option<Pubkey>,
+    /// Send without confirmation
+    #[arg(long, short = 'y', global = true)]
+    yes: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Dex admin instructions and accounts
+    #[command(subcommand)]
+    Dex(dex::DexCommand),
+    /// Launcher admin instructions and accounts
+    #[command(subcommand)]
+    Launcher(launcher::LauncherCommand),
+    /// Faucet admin instructions and accounts
+    #[command(subcommand)]
+    Faucet(faucet::FaucetCommand),
+    /// Sign a base64 transaction with the keypair and print it back
+    Sign { transaction: String },
+    /// Send a base64 transaction signed offline
+    Send { transaction: String },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let keypair = read_keypair(cli.keypair.as_ref());
+
+    if let Command::Sign { transaction } = &cli.command {
+        let keypair = keypair?;
+        println!("{}", client::sign_transaction(transaction, &keypair)?);
+        return Ok(());
+    }
+
+    let mode = if cli.dry_run {
+        Mode::DryRun
+    } else if cli.unsigned {
+        Mode::Unsigned
+    } else {
+        Mode::Send
+    };
+    let (signer, authority) = match (keypair, cli.authority) {
+        (Ok(keypair), None) => {
+            let authority = keypair.pubkey();
+            (Some(keypair), authority)
+        }
+        (Ok(keypair), Some(authority)) if keypair.pubkey() == authority => {
+            (Some(keypair), authority)
+        }
+        (_, Some(authority)) if mode != Mode::Send => (None, authority),
+        (_, Some(authority)) => {
+            bail!("the keypair does not match the authority {authority}, use --unsigned")
+        }
+        (Err(error), None) => return Err(error),
+    };
+    let nonce = cli.nonce.map(|account| DurableNonce {
+        account,
+        authority: cli.nonce_authority.unwrap_or(authority),
+    });
+    let client = Client::new(
+        cluster_url(&cli.url),
+        signer,
+        authority,
+        mode,
+        nonce,
+        cli.yes,
+    );
+
+    match cli.command {
+        Command::Dex(command) => command.run(&client),
+        Command::Launcher(command) => command.run(&client),
+        Command::Faucet(command) => command.run(&client),
+        Command::Send { transaction } => client.send(&client::decode_transaction(&transaction)?),
+        Command::Sign { .. } => unreachable!(),
+    }
+}
+
+fn read_keypair(path: Option<&PathBuf>) -> Result<Keypair> {
+    let path = match path {
+        Some(path) => path.clone(),
+        None => {
+            let home = std::env::var("HOME").map_err(|_| anyhow!("HOME is not set"))?;
+            PathBuf::from(home).join(".config/solana/id.json")
+        }
+    };
+
+    read_keypair_file(&path)
+        .map_err(|error| anyhow!("failed to read keypair {}: {error}", path.display()))
+}
+
+fn cluster_url(url: &str) -> String {
+    match url {
+        "localhost" | "l" => "http://localhost:8899",
+        "devnet" | "d" => "https://api.devnet.solana.com",
+        "testnet" | "t" => "https://api.testnet.solana.com",
+        "mainnet-beta" | "m" => "https://api.mainnet-beta.solana.com",
+        url => url,
+    }
+    .to_string()
+}
@@ -0,0 +1,605 @@
+//! In-process runtime of the launcher, dex and faucet programs with the Raydium CP-Swap and
+//! Metaplex token metadata binaries, for the end-to-end tests of the launch lifecycle.
+//!
+//! The launcher, dex and faucet run natively from this build. With `SBF_OUT_DIR` (or
+//! `BPF_OUT_DIR`) set they are loaded from the built binaries of that directory instead, the
+//! output of `anchor build` is `target/deploy`.
+//!
+//! The Raydium CP-Swap and Metaplex binaries are dumped from mainnet to `e2e/tests/fixtures`.
+//! Without the Metaplex one the token metadata program is a stand-in accepting any instruction,
+//! without the Raydium one `Runtime::has_raydium` is false and the tests skip the launch.
+
+use std::sync::Once;
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        entrypoint::ProgramResult, instruction::Instruction, program_pack::Pack, system_instruction,
+    },
+    AccountDeserialize, AccountSerialize, Discriminator,
+};
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token::spl_token::{self, native_mint},
+    token_interface::TokenAccount,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use dex::{
+    client::{
+        instructions::{self as dex_instructions, SwapKeys, UserToken},
+        pda as dex_pda,
+    },
+    states::DexState,
+};
+use faucet::client::{instructions as faucet_instructions, pda as faucet_pda};
+use launcher::client::{
+    instructions::{self as launcher_instructions, InitializeDexKeys},
+    pda as launcher_pda,
+};
+use raydium_cp_swap::states::AmmConfig;
+use solana_program_test::{
+    find_file, processor, BanksClientError, ProgramTest, ProgramTestContext,
+};
+use solana_sdk::{
+    account::Account,
+    bpf_loader,
+    compute_budget::ComputeBudgetInstruction,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+/// Raydium CP-Swap amm config cloned from mainnet by the local validator
+pub const AMM_CONFIG: Pubkey = solana_sdk::pubkey!("D4FPEruKEHrG5TenZ2mpDGEfu1iUvTiqBxvpU8HLBvC2");
+/// Pool creation fee of the mainnet amm config in lamports
+pub const CREATE_POOL_FEE: u64 = 150_000_000;
+/// Reserve minted to the payer, enough to swap the dex to the default reserve bound
+pub const RESERVE_AMOUNT: u64 = 210_000_000 * 10u64.pow(9);
+/// Every transaction runs with the maximum compute budget
+const COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+/// Log of the event data of a native program, turned back into a `Program data:` log
+const NATIVE_PROGRAM_DATA_LOG: &str = "Program log: native program data: ";
+
+/// Launch of a token by the launcher with the faucet, the dex runs with the config of index 0
+#[derive(Clone, Debug)]
+pub struct Launch {
+    pub dex_config: Pubkey,
+    pub dex_state: Pubkey,
+    /// Token paid into the dex, an SPL mint of the payer
+    pub reserve: UserToken,
+    /// Token minted by the launcher and sold by the dex
+    pub supply: UserToken,
+}
+
+impl Launch {
+    /// Accounts of a payer swap, buying the launched token when `buy` and selling it otherwise
+    pub fn swap_keys(&self, payer: Pubkey, buy: bool) -> SwapKeys {
+        let (input, output) = if buy {
+            (self.reserve, self.supply)
+        } else {
+            (self.supply, self.reserve)
+        };
+
+        SwapKeys {
+            payer,
+            config: self.dex_config,
+            dex_state: self.dex_state,
+            input,
+            output,
+            referrer: None,
+            wallet_limit: buy,
+            trader_stats: false,
+            hook_accounts: Vec::new(),
+        }
+    }
+}
+
+pub struct Runtime {
+    pub context: ProgramTestContext,
+    has_raydium: bool,
+}
+
+impl Runtime {
+    pub async fn start() -> Self {
+        let prefer_bpf =
+            std::env::var_os("SBF_OUT_DIR").is_some() || std::env::var_os("BPF_OUT_DIR").is_some();
+        let mut program_test = ProgramTest::default();
+        program_test.prefer_bpf(prefer_bpf);
+        program_test.add_program("dex", dex::ID, processor!(dex_entry));
+        program_test.add_program("launcher", launcher::ID, processor!(launcher_entry));
+        program_test.add_program("faucet", faucet::ID, processor!(faucet_entry));
+        let has_raydium =
+            add_dumped_program(&mut program_test, "raydium_cp_swap", raydium_cp_swap::ID);
+        if !add_dumped_program(
+            &mut program_test,
+            "mpl_token_metadata",
+            anchor_spl::metadata::ID,
+        ) {
+            // the processor is only taken when the built binaries aren't preferred
+            program_test.prefer_bpf(false);
+            program_test.add_program(
+                "mpl_token_metadata",
+                anchor_spl::metadata::ID,
+                processor!(token_metadata_stand_in),
+            );
+            program_test.prefer_bpf(prefer_bpf);
+        }
+
+        let rent = Rent::default();
+        program_test.add_account(AMM_CONFIG, amm_config_account(&rent));
+        program_test.add_account(
+            raydium_cp_swap::create_pool_fee_reveiver::id(),
+            pack_account(
+                &rent,
+                spl_token::state::Account {
+                    mint: native_mint::ID,
+                    owner: Pubkey::new_unique(),
+                    state: spl_token::state::AccountState::Initialized,
+                    is_native: Some(rent.minimum_balance(spl_token::state::Account::LEN)).into(),
+                    ..Default::default()
+                },
+            ),
+        );
+        program_test.add_account(
+            native_mint::ID,
+            pack_account(
+                &rent,
+                spl_token::state::Mint {
+                    decimals: native_mint::DECIMALS,
+                    is_initialized: true,
+                    ..Default::default()
+                },
+            ),
+        );
+
+        let context = program_test.start_with_context().await;
+        // the stubs of the native programs are set by the first start
+        static PROGRAM_DATA_STUBS: Once = Once::new();
+        PROGRAM_DATA_STUBS.call_once(|| {
+            struct Unset;
+            impl SyscallStubs for Unset {}
+            let inner = set_syscall_stubs(Box::new(Unset));
+            set_syscall_stubs(Box::new(ProgramDataStubs { inner }));
+        });
+
+        Self {
+            context,
+            has_raydium,
+        }
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    /// Whether the Raydium CP-Swap binary is loaded, `launch_dex` needs it
+    pub fn has_raydium(&self) -> bool {
+        self.has_raydium
+    }
+
+    /// Process the instructions in one transaction paid by the payer and return its logs
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> std::result::Result<Vec<String>, BanksClientError> {
+        let mut transaction_instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+            COMPUTE_UNIT_LIMIT,
+        )];
+        transaction_instructions.extend_from_slice(instructions);
+
+        // a new blockhash keeps repeated transactions from being deduplicated
+        let blockhash = self.context.get_new_latest_blockhash().await?;
+        let mut keypairs = vec![&self.context.payer];
+        keypairs.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            &transaction_instructions,
+            Some(&self.context.payer.pubkey()),
+            &keypairs,
+            blockhash,
+        );
+
+        let result = self
+            .context
+            .banks_client
+            .process_transaction_with_metadata(transaction)
+            .await?;
+        result.result.map_err(BanksClientError::TransactionError)?;
+
+        Ok(result
+            .metadata
+            .map(|metadata| {
+                metadata
+                    .log_messages
+                    .into_iter()
+                    .map(|log| match log.strip_prefix(NATIVE_PROGRAM_DATA_LOG) {
+                        Some(data) => format!("Program data: {data}"),
+                        None => log,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    pub async fn account_data(&mut self, address: Pubkey) -> Option<Vec<u8>> {
+        self.context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .map(|account| account.data)
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let data = self
+            .account_data(address)
+            .await
+            .unwrap_or_else(|| panic!("account {address} not found"));
+        T::try_deserialize(&mut data.as_slice()).unwrap()
+    }
+
+    /// The dex state is a zero copy account without borsh deserialization
+    pub async fn dex_state(&mut self, address: Pubkey) -> DexState {
+        let data = self
+            .account_data(address)
+            .await
+            .unwrap_or_else(|| panic!("dex state {address} not found"));
+        assert_eq!(data[..8], DexState::DISCRIMINATOR);
+        bytemuck::pod_read_unaligned(&data[8..DexState::LEN])
+    }
+
+    pub async fn token_balance(&mut self, token_account: Pubkey) -> u64 {
+        self.account::<TokenAccount>(token_account).await.amount
+    }
+
+    pub async fn lamports(&mut self, address: Pubkey) -> u64 {
+        self.context
+            .banks_client
+            .get_balance(address)
+            .await
+            .unwrap()
+    }
+
+    /// Move the clock of the bank forward
+    pub async fn warp_timestamp(&mut self, seconds: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    /// Create an SPL mint of the payer
+    pub async fn create_mint(&mut self, decimals: u8) -> Pubkey {
+        let payer = self.payer();
+        let mint = Keypair::new();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+
+        self.process(
+            &[
+                system_instruction::create_account(
+                    &payer,
+                    &mint.pubkey(),
+                    rent.minimum_balance(spl_token::state::Mint::LEN),
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint2(
+                    &spl_token::ID,
+                    &mint.pubkey(),
+                    &payer,
+                    None,
+                    decimals,
+                )
+                .unwrap(),
+            ],
+            &[&mint],
+        )
+        .await
+        .unwrap();
+
+        mint.pubkey()
+    }
+
+    /// Associated token account of the wallet, created when missing
+    pub async fn create_token_account(
+        &mut self,
+        wallet: Pubkey,
+        mint: Pubkey,
+        token_program: Pubkey,
+    ) -> Pubkey {
+        let payer = self.payer();
+        self.process(
+            &[create_associated_token_account_idempotent(
+                &payer,
+                &wallet,
+                &mint,
+                &token_program,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+        get_associated_token_address_with_program_id(&wallet, &mint, &token_program)
+    }
+
+    /// Mint tokens of an SPL mint of the payer
+    pub async fn mint_to(&mut self, mint: Pubkey, token_account: Pubkey, amount: u64) {
+        let payer = self.payer();
+        self.process(
+            &[spl_token::instruction::mint_to(
+                &spl_token::ID,
+                &mint,
+                &token_account,
+                &payer,
+                &[],
+                amount,
+            )
+            .unwrap()],
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Create the authority managers and configs, the payer is the admin of every program
+    pub async fn initialize_programs(&mut self) {
+        let payer = self.payer();
+        self.process(
+            &[
+                launcher_instructions::initialize_authority_manager(
+                    payer,
+                    faucet_pda::authority_address().0,
+                ),
+                launcher_instructions::initialize_config(payer),
+                faucet_instructions::initialize_authority_manager(payer),
+                dex_instructions::initialize_authority_manager(
+                    payer,
+                    launcher_pda::authority_address().0,
+                ),
+                dex_instructions::initialize_config(payer, 0),
+            ],
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Create a token with the launcher and its dex with the faucet, paid in a new reserve mint
+    pub async fn initialize_launch(&mut self) -> Launch {
+        let payer = self.payer();
+
+        let reserve_mint = self.create_mint(9).await;
+        let reserve_account = self
+            .create_token_account(payer, reserve_mint, spl_token::ID)
+            .await;
+        self.mint_to(reserve_mint, reserve_account, RESERVE_AMOUNT)
+            .await;
+
+        let mint = Keypair::new();
+        self.process(
+            &[launcher_instructions::initialize_mint(
+                payer,
+                mint.pubkey(),
+                "TEST".to_string(),
+                "TST".to_string(),
+                "https://www.google.com".to_string(),
+            )],
+            &[&mint],
+        )
+        .await
+        .unwrap();
+
+        let dex_config = dex_pda::config_address(0).0;
+        self.process(
+            &[
+                faucet_instructions::initialize_faucet_vault(payer, mint.pubkey(), spl_token::ID),
+                launcher_instructions::initialize_dex_with_faucet(&InitializeDexKeys {
+                    payer,
+                    dex_config,
+                    mint: reserve_mint,
+                    token_program_payer: spl_token::ID,
+                    mint_authority: mint.pubkey(),
+                    token_program_authority: spl_token::ID,
+                    hook_accounts: Vec::new(),
+                }),
+            ],
+            &[],
+        )
+        .await
+        .unwrap();
+
+        Launch {
+            dex_config,
+            dex_state: dex_pda::dex_state_address(&reserve_mint, &mint.pubkey()).0,
+            reserve: UserToken {
+                mint: reserve_mint,
+                token_program: spl_token::ID,
+                token_account: reserve_account,
+            },
+            supply: UserToken {
+                mint: mint.pubkey(),
+                token_program: spl_token::ID,
+                token_account: get_associated_token_address_with_program_id(
+                    &payer,
+                    &mint.pubkey(),
+                    &spl_token::ID,
+                ),
+            },
+        }
+    }
+}
+
+/// Entrypoints of the programs run natively, the accounts are leaked for the lifetime the
+/// anchor entrypoint requires
+fn dex_entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    dex::entry(
+        program_id,
+        Box::leak(accounts.to_vec().into_boxed_slice()),
+        data,
+    )
+}
+
+fn launcher_entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    launcher::entry(
+        program_id,
+        Box::leak(accounts.to_vec().into_boxed_slice()),
+        data,
+    )
+}
+
+fn faucet_entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    faucet::entry(
+        program_id,
+        Box::leak(accounts.to_vec().into_boxed_slice()),
+        data,
+    )
+}
+
+/// Syscalls of the native programs, the program-test ones print the event data instead of
+/// logging it
+struct ProgramDataStubs {
+    inner: Box<dyn SyscallStubs>,
+}
+
+impl SyscallStubs for ProgramDataStubs {
+    fn sol_log(&self, message: &str) {
+        self.inner.sol_log(message)
+    }
+    fn sol_log_compute_units(&self) {
+        self.inner.sol_log_compute_units()
+    }
+    fn sol_remaining_compute_units(&self) -> u64 {
+        self.inner.sol_remaining_compute_units()
+    }
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        self.inner
+            .sol_invoke_signed(instruction, account_infos, signers_seeds)
+    }
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_clock_sysvar(var_addr)
+    }
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_epoch_schedule_sysvar(var_addr)
+    }
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_fees_sysvar(var_addr)
+    }
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_rent_sysvar(var_addr)
+    }
+    fn sol_get_epoch_rewards_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_epoch_rewards_sysvar(var_addr)
+    }
+    fn sol_get_last_restart_slot(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_last_restart_slot(var_addr)
+    }
+    unsafe fn sol_memcpy(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.inner.sol_memcpy(dst, src, n)
+    }
+    unsafe fn sol_memmove(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.inner.sol_memmove(dst, src, n)
+    }
+    unsafe fn sol_memcmp(&self, s1: *const u8, s2: *const u8, n: usize, result: *mut i32) {
+        self.inner.sol_memcmp(s1, s2, n, result)
+    }
+    unsafe fn sol_memset(&self, s: *mut u8, c: u8, n: usize) {
+        self.inner.sol_memset(s, c, n)
+    }
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.inner.sol_get_return_data()
+    }
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.inner.sol_set_return_data(data)
+    }
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let data = fields
+            .iter()
+            .map(|field| STANDARD.encode(field))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let prefix = NATIVE_PROGRAM_DATA_LOG
+            .strip_prefix("Program log: ")
+            .unwrap();
+        self.inner.sol_log(&format!("{prefix}{data}"))
+    }
+    fn sol_get_processed_sibling_instruction(&self, index: usize) -> Option<Instruction> {
+        self.inner.sol_get_processed_sibling_instruction(index)
+    }
+    fn sol_get_stack_height(&self) -> u64 {
+        self.inner.sol_get_stack_height()
+    }
+}
+
+/// Token metadata program without the dumped binary, the metadata accounts are never read
+fn token_metadata_stand_in(_: &Pubkey, _: &[AccountInfo], _: &[u8]) -> ProgramResult {
+    Ok(())
+}
+
+/// Load a binary dumped from mainnet, false when it is missing
+fn add_dumped_program(
+    program_test: &mut ProgramTest,
+    program_name: &str,
+    program_id: Pubkey,
+) -> bool {
+    let Some(program_file) = find_file(&format!("{program_name}.so")) else {
+        return false;
+    };
+    let data = std::fs::read(program_file).unwrap();
+    program_test.add_account(
+        program_id,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: bpf_loader::ID,
+            executable: true,
+            rent_epoch: 0,
+        },
+    );
+    true
+}
+
+/// Amm config with the settings of the mainnet one, which doesn't check its address
+fn amm_config_account(rent: &Rent) -> Account {
+    let amm_config = AmmConfig {
+        bump: 0,
+        disable_create_pool: false,
+        index: 0,
+        trade_fee_rate: 2_500,
+        protocol_fee_rate: 120_000,
+        fund_fee_rate: 40_000,
+        create_pool_fee: CREATE_POOL_FEE,
+        protocol_owner: Pubkey::default(),
+        fund_owner: Pubkey::default(),
+        padding: [0; 16],
+    };
+    let mut data = Vec::new();
+    amm_config.try_serialize(&mut data).unwrap();
+
+    Account {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        owner: raydium_cp_swap::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Account of the token program holding a packed mint or token account
+fn pack_account<T: Pack>(rent: &Rent, state: T) -> Account {
+    let mut data = vec![0; T::LEN];
+    T::pack(state, &mut data).unwrap();
+
+    Account {
+        lamports: rent.minimum_balance(T::LEN),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
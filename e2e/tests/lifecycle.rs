@@ -0,0 +1,400 @@
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{keccak, native_token::LAMPORTS_PER_SOL},
+};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use dex::{
+    client::{
+        instructions::{self as dex_instructions, LaunchDexKeys},
+        pda as dex_pda,
+    },
+    states::FeeRecipient,
+};
+use faucet::{
+    client::{
+        instructions::{self as faucet_instructions, ClaimProof},
+        pda as faucet_pda,
+    },
+    states::{generate_leaf, FaucetClaim, FAUCET_CLAIM_PERIOD_IN_SECONDS},
+};
+use launchpad_e2e::{Runtime, AMM_CONFIG, CREATE_POOL_FEE};
+use launchpad_indexer::{DexEvent, Event, FaucetEvent, ProgramIds};
+
+fn has_event(logs: &[String], predicate: impl Fn(&Event) -> bool) -> bool {
+    ProgramIds::default()
+        .decode_logs(logs)
+        .unwrap()
+        .iter()
+        .any(predicate)
+}
+
+#[tokio::test]
+async fn launch_lifecycle_test() {
+    let mut runtime = Runtime::start().await;
+    runtime.initialize_programs().await;
+    let launch = runtime.initialize_launch().await;
+    let payer = runtime.payer();
+
+    let launcher_config: launcher::states::ConfigState = runtime
+        .account(launcher::client::pda::config_address().0)
+        .await;
+    let team_vault = launcher::client::pda::team_vault_address(&launch.supply.mint).0;
+    let faucet_vault = faucet_pda::faucet_vault_address(&launch.supply.mint).0;
+    assert_eq!(
+        runtime.token_balance(team_vault).await,
+        launcher_config.team_tokens
+    );
+    assert_eq!(
+        runtime.token_balance(faucet_vault).await,
+        launcher_config.faucet_tokens
+    );
+    assert!(!runtime.dex_state(launch.dex_state).await.is_ready_to_launch);
+
+    // a first buy of a tenth of the reserve stays below the bound
+    let amount_in = runtime.token_balance(launch.reserve.token_account).await / 10;
+    runtime
+        .process(
+            &[dex_instructions::swap_base_input(
+                &launch.swap_keys(payer, true),
+                amount_in,
+                0,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let dex_state = runtime.dex_state(launch.dex_state).await;
+    assert!(!dex_state.is_ready_to_launch);
+    let keys = LaunchDexKeys {
+        payer,
+        dex_config: launch.dex_config,
+        dex_state: launch.dex_state,
+        token_0_mint: dex_state.token_0_mint,
+        token_1_mint: dex_state.token_1_mint,
+        token_0_program: dex_state.token_0_program,
+        token_1_program: dex_state.token_1_program,
+    };
+
+    // until the fee recipients are set the swap fees go to the single recipient passed
+    let payer_token_0 = get_associated_token_address_with_program_id(
+        &payer,
+        &keys.token_0_mint,
+        &keys.token_0_program,
+    );
+    let payer_token_1 = get_associated_token_address_with_program_id(
+        &payer,
+        &keys.token_1_mint,
+        &keys.token_1_program,
+    );
+    let swap_fees_0 = dex_state.swap_fees_token_0;
+    let swap_fees_1 = dex_state.swap_fees_token_1;
+    assert!(swap_fees_0 + swap_fees_1 > 0);
+    let payer_balance_0 = runtime.token_balance(payer_token_0).await;
+    let payer_balance_1 = runtime.token_balance(payer_token_1).await;
+    runtime
+        .process(
+            &[dex_instructions::withdraw_dex_fee(
+                payer,
+                launch.dex_state,
+                keys.token_0_mint,
+                keys.token_1_mint,
+                &[(payer_token_0, payer_token_1)],
+                Vec::new(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        runtime.token_balance(payer_token_0).await - payer_balance_0,
+        swap_fees_0
+    );
+    assert_eq!(
+        runtime.token_balance(payer_token_1).await - payer_balance_1,
+        swap_fees_1
+    );
+
+    // buying with the rest of the reserve fills the dex up to the bound, the rest stays with the payer
+    let amount_in = runtime.token_balance(launch.reserve.token_account).await;
+    let logs = runtime
+        .process(
+            &[dex_instructions::swap_base_input(
+                &launch.swap_keys(payer, true),
+                amount_in,
+                0,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    assert!(has_event(&logs, |event| matches!(
+        event,
+        Event::Dex(DexEvent::DexIsReadyToLaunchEvent(_))
+    )));
+
+    let dex_state = runtime.dex_state(launch.dex_state).await;
+    let vault_reserve_bound = dex_state.vault_reserve_bound;
+    assert!(dex_state.is_ready_to_launch);
+    assert_eq!(
+        runtime
+            .token_balance(dex_pda::dex_vault_address(&launch.dex_state, &launch.reserve.mint).0)
+            .await,
+        vault_reserve_bound
+    );
+    assert!(runtime.token_balance(launch.reserve.token_account).await > 0);
+
+    // the launch needs the dumped Raydium CP-Swap binary, the swap fees are split without it
+    if runtime.has_raydium() {
+        let create_pool_fee_receiver = raydium_cp_swap::create_pool_fee_reveiver::id();
+        let receiver_lamports = runtime.lamports(create_pool_fee_receiver).await;
+        let logs = runtime
+            .process(
+                &[dex_instructions::launch_dex(
+                    &keys,
+                    AMM_CONFIG,
+                    LAMPORTS_PER_SOL,
+                )],
+                &[],
+            )
+            .await
+            .unwrap();
+        let raydium_pool =
+            dex_pda::raydium_pool_address(&AMM_CONFIG, &keys.token_0_mint, &keys.token_1_mint).0;
+        assert!(has_event(&logs, |event| matches!(
+            event,
+            Event::Dex(DexEvent::DexLaunchedEvent(launched))
+                if launched.pool_id == raydium_pool && launched.raydium_id == raydium_pool
+        )));
+        assert!(runtime.dex_state(launch.dex_state).await.is_launched);
+        assert!(runtime.account_data(raydium_pool).await.is_some());
+        assert_eq!(
+            runtime.lamports(create_pool_fee_receiver).await - receiver_lamports,
+            CREATE_POOL_FEE
+        );
+    }
+
+    // the swap and launch fees are split between the payer and a second recipient
+    let recipient = Pubkey::new_unique();
+    runtime
+        .process(
+            &[dex_instructions::update_fee_recipients(
+                payer,
+                vec![
+                    FeeRecipient {
+                        wallet: payer,
+                        weight_bps: 5_000,
+                    },
+                    FeeRecipient {
+                        wallet: recipient,
+                        weight_bps: 5_000,
+                    },
+                ],
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let recipient_token_0 = runtime
+        .create_token_account(recipient, keys.token_0_mint, keys.token_0_program)
+        .await;
+    let recipient_token_1 = runtime
+        .create_token_account(recipient, keys.token_1_mint, keys.token_1_program)
+        .await;
+
+    let dex_state = runtime.dex_state(launch.dex_state).await;
+    let fees_0 = dex_state.swap_fees_token_0 + dex_state.launch_fees_token_0;
+    let fees_1 = dex_state.swap_fees_token_1 + dex_state.launch_fees_token_1;
+    assert!(fees_0 + fees_1 > 0);
+    let payer_balance_0 = runtime.token_balance(payer_token_0).await;
+    let payer_balance_1 = runtime.token_balance(payer_token_1).await;
+
+    runtime
+        .process(
+            &[dex_instructions::withdraw_dex_fee(
+                payer,
+                launch.dex_state,
+                keys.token_0_mint,
+                keys.token_1_mint,
+                &[
+                    (payer_token_0, payer_token_1),
+                    (recipient_token_0, recipient_token_1),
+                ],
+                Vec::new(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        runtime.token_balance(payer_token_0).await - payer_balance_0,
+        fees_0 / 2
+    );
+    assert_eq!(
+        runtime.token_balance(payer_token_1).await - payer_balance_1,
+        fees_1 / 2
+    );
+    assert_eq!(
+        runtime.token_balance(recipient_token_0).await,
+        fees_0 - fees_0 / 2
+    );
+    assert_eq!(
+        runtime.token_balance(recipient_token_1).await,
+        fees_1 - fees_1 / 2
+    );
+
+    let dex_state = runtime.dex_state(launch.dex_state).await;
+    let remaining_fees = [
+        dex_state.swap_fees_token_0,
+        dex_state.swap_fees_token_1,
+        dex_state.launch_fees_token_0,
+        dex_state.launch_fees_token_1,
+    ];
+    assert_eq!(remaining_fees, [0; 4]);
+}
+
+#[tokio::test]
+async fn faucet_claim_lifecycle_test() {
+    let mut runtime = Runtime::start().await;
+    runtime.initialize_programs().await;
+    let launch = runtime.initialize_launch().await;
+    let payer = runtime.payer();
+    let mint = launch.supply.mint;
+    let token_program = launch.supply.token_program;
+
+    let logs = runtime
+        .process(
+            &[faucet_instructions::initialize_faucet_claim(
+                payer,
+                mint,
+                token_program,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    assert!(has_event(&logs, |event| matches!(
+        event,
+        Event::Faucet(FaucetEvent::InitializeFaucetClaimEvent(_))
+    )));
+
+    let faucet_claim_address = faucet_pda::faucet_claim_address(&mint).0;
+    let faucet_claim: FaucetClaim = runtime.account(faucet_claim_address).await;
+    let faucet_vault = faucet_pda::faucet_vault_address(&mint).0;
+    assert_eq!(
+        faucet_claim.total_faucet_amount,
+        runtime.token_balance(faucet_vault).await
+    );
+
+    // a shard of two leaves, the proof of the payer is the leaf of the other address
+    let shard = faucet_pda::faucet_claim_shard_address(&faucet_claim_address, 0).0;
+    let amount = 1_000 * 10u64.pow(9);
+    let leaf = generate_leaf(&shard, &payer, 0, amount);
+    let other_leaf = generate_leaf(&shard, &Pubkey::new_unique(), 1, amount);
+    let merkle_root = if leaf <= other_leaf {
+        keccak::hashv(&[&leaf, &other_leaf]).0
+    } else {
+        keccak::hashv(&[&other_leaf, &leaf]).0
+    };
+    runtime
+        .process(
+            &[faucet_instructions::initialize_faucet_claim_shard(
+                payer,
+                mint,
+                token_program,
+                0,
+                merkle_root,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    let faucet_claim: FaucetClaim = runtime.account(faucet_claim_address).await;
+    assert_eq!(faucet_claim.shards, 1);
+
+    let proof = ClaimProof {
+        shard_index: 0,
+        index: 0,
+        amount,
+        proofs: vec![other_leaf],
+    };
+    let claim = faucet_instructions::claim(
+        payer,
+        launch.supply.token_account,
+        mint,
+        token_program,
+        proof,
+    );
+    let balance = runtime.token_balance(launch.supply.token_account).await;
+    let logs = runtime
+        .process(std::slice::from_ref(&claim), &[])
+        .await
+        .unwrap();
+    assert!(has_event(&logs, |event| matches!(
+        event,
+        Event::Faucet(FaucetEvent::ClaimEvent(claimed)) if claimed.amount == amount
+    )));
+    assert_eq!(
+        runtime.token_balance(launch.supply.token_account).await - balance,
+        amount
+    );
+    assert!(runtime.process(&[claim], &[]).await.is_err());
+
+    // the rest of the faucet is withdrawn once the claim period is over
+    let withdraw = faucet_instructions::withdraw_expired_faucet_claim(
+        payer,
+        launch.supply.token_account,
+        mint,
+        token_program,
+    );
+    assert!(runtime
+        .process(std::slice::from_ref(&withdraw), &[])
+        .await
+        .is_err());
+
+    runtime
+        .warp_timestamp(FAUCET_CLAIM_PERIOD_IN_SECONDS as i64 + 1)
+        .await;
+    let balance = runtime.token_balance(launch.supply.token_account).await;
+    runtime.process(&[withdraw], &[]).await.unwrap();
+    assert_eq!(runtime.token_balance(faucet_vault).await, 0);
+    assert_eq!(
+        runtime.token_balance(launch.supply.token_account).await - balance,
+        faucet_claim.total_faucet_amount - amount
+    );
+
+    // the claim is destroyed after its shards, which need it to be destroyed
+    let destroy_claim = faucet_instructions::destroy_faucet_claim(payer, mint);
+    assert!(runtime
+        .process(std::slice::from_ref(&destroy_claim), &[])
+        .await
+        .is_err());
+
+    let payer_lamports = runtime.lamports(payer).await;
+    runtime
+        .process(
+            &[faucet_instructions::destroy_faucet_claim_shard(
+                payer, mint, 0,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    assert!(runtime.account_data(shard).await.is_none());
+    assert!(runtime.lamports(payer).await > payer_lamports);
+    let faucet_claim: FaucetClaim = runtime.account(faucet_claim_address).await;
+    assert!(faucet_claim.is_all_shards_destroyed());
+
+    let logs = runtime.process(&[destroy_claim], &[]).await.unwrap();
+    assert!(has_event(&logs, |event| matches!(
+        event,
+        Event::Faucet(FaucetEvent::DestroyFaucetClaimEvent(_))
+    )));
+    assert!(runtime.account_data(faucet_claim_address).await.is_none());
+    assert!(runtime
+        .account_data(faucet_pda::authority_manager_address().0)
+        .await
+        .is_some());
+}
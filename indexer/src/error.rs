@@ -0,0 +1,23 @@
+use anchor_lang::prelude::Pubkey;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Logs are truncated, events are missing")]
+    TruncatedLogs,
+    #[error("Program data logged outside of a program invocation")]
+    DataOutsideInvocation,
+    #[error("Program {0} returned without being invoked")]
+    UnexpectedReturn(String),
+    #[error("Invalid program id {0}")]
+    InvalidProgramId(String),
+    #[error("Invalid base64 program data: {0}")]
+    InvalidData(#[from] base64::DecodeError),
+    #[error("Unknown event discriminator of program {0}")]
+    UnknownEvent(Pubkey),
+    #[error("Invalid event of program {0}: {1}")]
+    InvalidEvent(Pubkey, std::io::Error),
+    #[error("Store io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,211 @@
+use crate::{
+    error::{Error, Result},
+    logs::{parse_logs, ProgramData},
+};
+use anchor_lang::{prelude::Pubkey, AnchorDeserialize, Discriminator};
+
+/// Declare the events enum of a program and its decoding by discriminator
+macro_rules! program_events {
+    ($name:ident, $program:ident, [$($event:ident),* $(,)?]) => {
+        #[derive(Debug)]
+        pub enum $name {
+            $($event($program::states::$event),)*
+        }
+
+        impl $name {
+            fn decode(program_id: Pubkey, data: &[u8]) -> Result<Self> {
+                if data.len() < 8 {
+                    return Err(Error::UnknownEvent(program_id));
+                }
+                let (discriminator, mut event) = data.split_at(8);
+
+                $(
+                    if discriminator == $program::states::$event::DISCRIMINATOR {
+                        return $program::states::$event::deserialize(&mut event)
+                            .map(Self::$event)
+                            .map_err(|error| Error::InvalidEvent(program_id, error));
+                    }
+                )*
+
+                Err(Error::UnknownEvent(program_id))
+            }
+        }
+    };
+}
+
+program_events!(
+    DexEvent,
+    dex,
+    [
+        InitializeAuthorityManagerEvent,
+        UpdateAuthorityManagerAdminEvent,
+        UpdateAuthorityManagerCpiAuthorityEvent,
+        InitializeConfigEvent,
+        UpdateConfigSwapFeeRateEvent,
+        UpdateConfigLaunchFeeRateEvent,
+        UpdateConfigInitialReserveEvent,
+        UpdateConfigVaultReserveBoundEvent,
+        UpdateConfigCurveTypeEvent,
+        UpdateConfigVirtualReserveEvent,
+        UpdateConfigVirtualSupplyEvent,
+        UpdateConfigCreatorFeeRateEvent,
+        UpdateConfigReferralFeeRateEvent,
+        UpdateConfigDisabledEvent,
+        UpdateConfigSniperProtectionEvent,
+        UpdateConfigLaunchWindowEvent,
+        UpdateConfigLaunchLamportsEvent,
+        UpdateConfigLaunchTargetEvent,
+        UpdateConfigLpPolicyEvent,
+        UpdateConfigSniperFeeEvent,
+        InitializeDexEvent,
+        SwapEvent,
+        SwapRouteEvent,
+        DexIsReadyToLaunchEvent,
+        DexLaunchedEvent,
+        WithdrawDexFeeEvent,
+        WithdrawCreatorFeeEvent,
+        UpdateFeeRecipientsEvent,
+        DistributeDexFeeEvent,
+        ClaimLockedLpEvent,
+        RefundEvent,
+        WithdrawSeedReserveEvent,
+        PlaceLimitOrderEvent,
+        CancelLimitOrderEvent,
+        FillLimitOrderEvent,
+    ]
+);
+
+program_events!(
+    LauncherEvent,
+    launcher,
+    [
+        InitializeAuthorityManagerEvent,
+        UpdateAuthorityManagerAdminEvent,
+        UpdateAuthorityManagerFaucetAuthorityEvent,
+        WithdrawTeamTokensEvent,
+        InitializeConfigEvent,
+        UpdateConfigTeamTokensEvent,
+        UpdateConfigFaucetTokensEvent,
+        InitializeDexEvent,
+        InitializeDexWithFaucetEvent,
+    ]
+);
+
+program_events!(
+    FaucetEvent,
+    faucet,
+    [
+        InitializeAuthorityManagerEvent,
+        RemoveAuthorityManagerAdminEvent,
+        SetAuthorityManagerAdminEvent,
+        InitializeFaucetClaimEvent,
+        WithdrawExpiredFaucetClaimEvent,
+        DestroyFaucetClaimEvent,
+        InitializeFaucetClaimShardEvent,
+        ClaimEvent,
+        DestroyFaucetClaimShardEvent,
+    ]
+);
+
+#[derive(Debug)]
+pub enum Event {
+    Dex(DexEvent),
+    Launcher(LauncherEvent),
+    Faucet(FaucetEvent),
+}
+
+/// Addresses the programs are deployed at
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ProgramIds {
+    pub dex: Pubkey,
+    pub launcher: Pubkey,
+    pub faucet: Pubkey,
+}
+
+impl Default for ProgramIds {
+    fn default() -> Self {
+        Self {
+            dex: dex::ID,
+            launcher: launcher::ID,
+            faucet: faucet::ID,
+        }
+    }
+}
+
+impl ProgramIds {
+    /// Decode the event of a program data log, `None` when it is logged by another program
+    pub fn decode_event(&self, program_data: &ProgramData) -> Result<Option<Event>> {
+        let ProgramData {
+            program_id, data, ..
+        } = program_data;
+
+        let event = if *program_id == self.dex {
+            Event::Dex(DexEvent::decode(*program_id, data)?)
+        } else if *program_id == self.launcher {
+            Event::Launcher(LauncherEvent::decode(*program_id, data)?)
+        } else if *program_id == self.faucet {
+            Event::Faucet(FaucetEvent::decode(*program_id, data)?)
+        } else {
+            return Ok(None);
+        };
+
+        Ok(Some(event))
+    }
+
+    /// Decode the events of the programs from the logs of a successful transaction
+    pub fn decode_logs<S: AsRef<str>>(&self, logs: &[S]) -> Result<Vec<Event>> {
+        let mut events = Vec::new();
+        for program_data in parse_logs(logs)? {
+            if let Some(event) = self.decode_event(&program_data)? {
+                events.push(event);
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::Event as _;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    #[test]
+    fn decode_logs_test() {
+        let claim = faucet::states::ClaimEvent {
+            faucet_claim_id: Pubkey::new_unique(),
+            faucet_claim_shard_id: Pubkey::new_unique(),
+            address_id: Pubkey::new_unique(),
+            amount: 100,
+        };
+        let other_program = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program {} invoke [1]", faucet::ID),
+            format!("Program data: {}", STANDARD.encode(claim.data())),
+            format!("Program {other_program} invoke [2]"),
+            format!("Program data: {}", STANDARD.encode(claim.data())),
+            format!("Program {other_program} success"),
+            format!("Program {} success", faucet::ID),
+        ];
+
+        let events = ProgramIds::default().decode_logs(&logs).unwrap();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            Event::Faucet(FaucetEvent::ClaimEvent(event)) => {
+                assert_eq!(event.address_id, claim.address_id);
+                assert_eq!(event.amount, 100);
+            }
+            event => panic!("unexpected event {event:?}"),
+        }
+
+        let unknown = vec![
+            format!("Program {} invoke [1]", dex::ID),
+            format!("Program data: {}", STANDARD.encode(claim.data())),
+        ];
+        assert!(matches!(
+            ProgramIds::default().decode_logs(&unknown),
+            Err(Error::UnknownEvent(_))
+        ));
+    }
+}
@@ -0,0 +1,13 @@
+//! Decodes the events of the dex, launcher and faucet programs from transaction logs, including
+//! the events emitted inside CPIs, and folds them into a local store of pools, trades, launches
+//! and claims.
+
+pub mod error;
+pub mod events;
+pub mod logs;
+pub mod store;
+
+pub use error::*;
+pub use events::*;
+pub use logs::*;
+pub use store::*;
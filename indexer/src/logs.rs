@@ -0,0 +1,99 @@
+use crate::error::{Error, Result};
+use anchor_lang::prelude::Pubkey;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// Data logged by `sol_log_data`, which `emit!` uses for the events
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProgramData {
+    /// Program on top of the invocation stack when the data was logged
+    pub program_id: Pubkey,
+    /// 1 for the transaction instructions, higher inside CPIs
+    pub depth: usize,
+    pub data: Vec<u8>,
+}
+
+/// Attribute each `Program data:` log to the program logging it by following the invocation stack,
+/// events of a program called by CPI are attributed to the called program and not the caller.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Result<Vec<ProgramData>> {
+    let mut stack: Vec<Pubkey> = Vec::new();
+    let mut program_data = Vec::new();
+
+    for log in logs {
+        let log = log.as_ref();
+
+        if let Some(fields) = log.strip_prefix("Program data: ") {
+            let program_id = *stack.last().ok_or(Error::DataOutsideInvocation)?;
+            for field in fields.split(' ') {
+                program_data.push(ProgramData {
+                    program_id,
+                    depth: stack.len(),
+                    data: STANDARD.decode(field)?,
+                });
+            }
+        } else if log == "Log truncated" {
+            return Err(Error::TruncatedLogs);
+        } else if let Some((program, status)) = log
+            .strip_prefix("Program ")
+            .and_then(|log| log.split_once(' '))
+        {
+            if status.starts_with("invoke [") {
+                let program_id = program
+                    .parse()
+                    .map_err(|_| Error::InvalidProgramId(program.to_string()))?;
+                stack.push(program_id);
+            } else if status == "success" || status.starts_with("failed") {
+                match stack.pop() {
+                    Some(program_id) if program_id.to_string() == program => {}
+                    _ => return Err(Error::UnexpectedReturn(program.to_string())),
+                }
+            }
+        }
+    }
+
+    Ok(program_data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_logs_cpi_test() {
+        let launcher = launcher::ID;
+        let dex = dex::ID;
+        let logs = vec![
+            format!("Program {launcher} invoke [1]"),
+            "Program log: Instruction: InitializeDex".to_string(),
+            format!("Program {dex} invoke [2]"),
+            "Program log: Instruction: InitializeDex".to_string(),
+            format!("Program data: {}", STANDARD.encode([1, 2])),
+            format!("Program {dex} consumed 1000 of 200000 compute units"),
+            format!("Program {dex} success"),
+            format!("Program data: {}", STANDARD.encode([3])),
+            format!("Program return: {launcher} AQ=="),
+            format!("Program {launcher} success"),
+        ];
+
+        let program_data = parse_logs(&logs).unwrap();
+        assert_eq!(
+            program_data,
+            vec![
+                ProgramData {
+                    program_id: dex,
+                    depth: 2,
+                    data: vec![1, 2],
+                },
+                ProgramData {
+                    program_id: launcher,
+                    depth: 1,
+                    data: vec![3],
+                },
+            ]
+        );
+
+        let mut truncated = logs.clone();
+        truncated.truncate(5);
+        truncated.push("Log truncated".to_string());
+        assert!(matches!(parse_logs(&truncated), Err(Error::TruncatedLogs)));
+    }
+}
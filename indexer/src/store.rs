@@ -0,0 +1,426 @@
+use crate::{
+    error::Result,
+    events::{DexEvent, Event, FaucetEvent, LauncherEvent, ProgramIds},
+};
+use anchor_lang::prelude::*;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+/// Transaction the events are logged by
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TransactionInfo {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct Pool {
+    pub dex_id: Pubkey,
+    pub config_id: Pubkey,
+    pub creator_id: Pubkey,
+    pub mint_zero: Pubkey,
+    pub mint_one: Pubkey,
+    pub curve_type: u8,
+    pub launch_target: u8,
+    pub created_slot: u64,
+    /// Tokens kept by the launcher team and sent to the faucet, 0 for a dex not created by the launcher
+    pub team_tokens_amount: u64,
+    pub faucet_tokens_amount: u64,
+    pub is_ready_to_launch: bool,
+    pub is_launched: bool,
+    pub trade_count: u64,
+    /// Swapped amounts of token_0 and token_1 in both directions
+    pub volume_token_zero: u128,
+    pub volume_token_one: u128,
+    pub refunded_supply: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct Trade {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub dex_id: Pubkey,
+    pub zero_to_one: bool,
+    pub base_input: bool,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    pub referral_fee: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct Launch {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub dex_id: Pubkey,
+    /// Raydium CP-Swap pool or Orca Whirlpool the dex graduated to
+    pub pool_id: Pubkey,
+    pub launch_target: u8,
+    /// Admin or keeper paying for the launch
+    pub payer_id: Pubkey,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub lp_burned: u64,
+    pub lp_locked: u64,
+    pub keeper_reward: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct Faucet {
+    pub faucet_claim_id: Pubkey,
+    pub mint: Pubkey,
+    pub total_faucet_amount: u64,
+    pub total_claimed_amount: u64,
+    pub starts: u64,
+    pub ends: u64,
+    pub shards: u16,
+    pub is_destroyed: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct Claim {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub faucet_claim_id: Pubkey,
+    pub faucet_claim_shard_id: Pubkey,
+    pub address_id: Pubkey,
+    pub amount: u64,
+}
+
+/// Local index of the program events, transactions are indexed once whatever their order
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Default)]
+pub struct Store {
+    pools: BTreeMap<Pubkey, Pool>,
+    trades: Vec<Trade>,
+    launches: Vec<Launch>,
+    faucets: BTreeMap<Pubkey, Faucet>,
+    claims: Vec<Claim>,
+    signatures: BTreeSet<String>,
+}
+
+impl Store {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::try_from_slice(&fs::read(path)?)?)
+    }
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        Ok(fs::write(path, self.try_to_vec()?)?)
+    }
+
+    /// Decode and apply the events of a successful transaction, false if it is already indexed
+    pub fn index_transaction<S: AsRef<str>>(
+        &mut self,
+        program_ids: &ProgramIds,
+        transaction: &TransactionInfo,
+        logs: &[S],
+    ) -> Result<bool> {
+        if self.signatures.contains(&transaction.signature) {
+            return Ok(false);
+        }
+
+        for event in program_ids.decode_logs(logs)? {
+            self.apply(transaction, &event);
+        }
+        self.signatures.insert(transaction.signature.clone());
+
+        Ok(true)
+    }
+
+    pub fn apply(&mut self, transaction: &TransactionInfo, event: &Event) {
+        match event {
+            Event::Dex(event) => self.apply_dex(transaction, event),
+            Event::Launcher(event) => self.apply_launcher(event),
+            Event::Faucet(event) => self.apply_faucet(transaction, event),
+        }
+    }
+
+    fn apply_dex(&mut self, transaction: &TransactionInfo, event: &DexEvent) {
+        match event {
+            DexEvent::InitializeDexEvent(event) => {
+                let pool = self.pool_mut(event.dex_id);
+                pool.config_id = event.config_id;
+                pool.creator_id = event.payer_id;
+                pool.mint_zero = event.mint_zero;
+                pool.mint_one = event.mint_one;
+                pool.curve_type = event.curve_type;
+                pool.launch_target = event.launch_target;
+                pool.created_slot = transaction.slot;
+            }
+            DexEvent::SwapEvent(event) => {
+                let pool = self.pool_mut(event.dex_id);
+                let (amount_zero, amount_one) = if event.zero_to_one {
+                    (event.input_amount, event.output_amount)
+                } else {
+                    (event.output_amount, event.input_amount)
+                };
+                pool.trade_count += 1;
+                pool.volume_token_zero += u128::from(amount_zero);
+                pool.volume_token_one += u128::from(amount_one);
+
+                self.trades.push(Trade {
+                    signature: transaction.signature.clone(),
+                    slot: transaction.slot,
+                    block_time: transaction.block_time,
+                    dex_id: event.dex_id,
+                    zero_to_one: event.zero_to_one,
+                    base_input: event.base_input,
+                    input_amount: event.input_amount,
+                    output_amount: event.output_amount,
+                    protocol_fee: event.protocol_fee,
+                    creator_fee: event.creator_fee,
+                    referral_fee: event.referral_fee,
+                });
+            }
+            DexEvent::DexIsReadyToLaunchEvent(event) => {
+                self.pool_mut(event.dex_id).is_ready_to_launch = true;
+            }
+            DexEvent::DexLaunchedEvent(event) => {
+                self.pool_mut(event.dex_id).is_launched = true;
+
+                self.launches.push(Launch {
+                    signature: transaction.signature.clone(),
+                    slot: transaction.slot,
+                    block_time: transaction.block_time,
+                    dex_id: event.dex_id,
+                    pool_id: event.pool_id,
+                    launch_target: event.launch_target,
+                    payer_id: event.admin_id,
+                    amount_0: event.amount_0,
+                    amount_1: event.amount_1,
+                    lp_burned: event.lp_burned,
+                    lp_locked: event.lp_locked,
+                    keeper_reward: event.keeper_reward,
+                });
+            }
+            DexEvent::RefundEvent(event) => {
+                self.pool_mut(event.dex_id).refunded_supply += event.supply_amount;
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_launcher(&mut self, event: &LauncherEvent) {
+        match event {
+            LauncherEvent::InitializeDexEvent(event) => {
+                self.pool_mut(event.dex_id).team_tokens_amount = event.team_tokens_amount;
+            }
+            LauncherEvent::InitializeDexWithFaucetEvent(event) => {
+                let pool = self.pool_mut(event.dex_id);
+                pool.team_tokens_amount = event.team_tokens_amount;
+                pool.faucet_tokens_amount = event.faucet_tokens_amount;
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_faucet(&mut self, transaction: &TransactionInfo, event: &FaucetEvent) {
+        match event {
+            FaucetEvent::InitializeFaucetClaimEvent(event) => {
+                let faucet = self.faucet_mut(event.faucet_claim_id);
+                faucet.mint = event.mint_id;
+                faucet.total_faucet_amount = event.total_faucet_amount;
+                faucet.starts = event.starts;
+                faucet.ends = event.ends;
+            }
+            FaucetEvent::InitializeFaucetClaimShardEvent(event) => {
+                self.faucet_mut(event.faucet_claim_id).shards += 1;
+            }
+            FaucetEvent::DestroyFaucetClaimEvent(event) => {
+                self.faucet_mut(event.faucet_claim_id).is_destroyed = true;
+            }
+            FaucetEvent::ClaimEvent(event) => {
+                self.faucet_mut(event.faucet_claim_id).total_claimed_amount += event.amount;
+
+                self.claims.push(Claim {
+                    signature: transaction.signature.clone(),
+                    slot: transaction.slot,
+                    block_time: transaction.block_time,
+                    faucet_claim_id: event.faucet_claim_id,
+                    faucet_claim_shard_id: event.faucet_claim_shard_id,
+                    address_id: event.address_id,
+                    amount: event.amount,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn pool_mut(&mut self, dex_id: Pubkey) -> &mut Pool {
+        self.pools.entry(dex_id).or_insert_with(|| Pool {
+            dex_id,
+            ..Default::default()
+        })
+    }
+    fn faucet_mut(&mut self, faucet_claim_id: Pubkey) -> &mut Faucet {
+        self.faucets
+            .entry(faucet_claim_id)
+            .or_insert_with(|| Faucet {
+                faucet_claim_id,
+                ..Default::default()
+            })
+    }
+
+    pub fn is_indexed(&self, signature: &str) -> bool {
+        self.signatures.contains(signature)
+    }
+    pub fn pool(&self, dex_id: &Pubkey) -> Option<&Pool> {
+        self.pools.get(dex_id)
+    }
+    pub fn pools(&self) -> impl Iterator<Item = &Pool> {
+        self.pools.values()
+    }
+    /// Pools trading a mint, the mints of the launcher are used by a single pool
+    pub fn pools_by_mint<'a>(&'a self, mint: &'a Pubkey) -> impl Iterator<Item = &'a Pool> {
+        self.pools
+            .values()
+            .filter(move |pool| pool.mint_zero == *mint || pool.mint_one == *mint)
+    }
+    /// Trades of a pool in indexing order
+    pub fn trades<'a>(&'a self, dex_id: &'a Pubkey) -> impl Iterator<Item = &'a Trade> {
+        self.trades
+            .iter()
+            .filter(move |trade| trade.dex_id == *dex_id)
+    }
+    pub fn launches(&self) -> &[Launch] {
+        &self.launches
+    }
+    pub fn launch(&self, dex_id: &Pubkey) -> Option<&Launch> {
+        self.launches.iter().find(|launch| launch.dex_id == *dex_id)
+    }
+    pub fn faucet(&self, faucet_claim_id: &Pubkey) -> Option<&Faucet> {
+        self.faucets.get(faucet_claim_id)
+    }
+    pub fn faucets_by_mint<'a>(&'a self, mint: &'a Pubkey) -> impl Iterator<Item = &'a Faucet> {
+        self.faucets
+            .values()
+            .filter(move |faucet| faucet.mint == *mint)
+    }
+    pub fn claims<'a>(&'a self, faucet_claim_id: &'a Pubkey) -> impl Iterator<Item = &'a Claim> {
+        self.claims
+            .iter()
+            .filter(move |claim| claim.faucet_claim_id == *faucet_claim_id)
+    }
+    pub fn claims_by_address<'a>(&'a self, address: &'a Pubkey) -> impl Iterator<Item = &'a Claim> {
+        self.claims
+            .iter()
+            .filter(move |claim| claim.address_id == *address)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::Event as _;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use dex::states::{InitializeDexEvent, SwapEvent};
+    use launcher::states::InitializeDexWithFaucetEvent;
+
+    #[test]
+    fn index_transaction_test() {
+        let dex_id = Pubkey::new_unique();
+        let initialize = InitializeDexEvent {
+            dex_id,
+            config_id: Pubkey::new_unique(),
+            payer_id: Pubkey::new_unique(),
+            mint_zero: Pubkey::new_unique(),
+            mint_one: Pubkey::new_unique(),
+            token_zero_amount: 0,
+            token_one_amount: 0,
+            reserve_bound: 0,
+            vault_for_reserve_bound: false,
+            curve_type: 0,
+            virtual_token_zero_amount: 0,
+            virtual_token_one_amount: 0,
+            sniper_window_end: 0,
+            sniper_fee_rate: 0,
+            launch_deadline: 0,
+            launch_lamports: 0,
+            launch_target: 0,
+        };
+        let launcher_initialize = InitializeDexWithFaucetEvent {
+            dex_id,
+            payer_id: initialize.payer_id,
+            mint_zero_id: initialize.mint_zero,
+            mint_one_id: initialize.mint_one,
+            team_tokens_amount: 10,
+            faucet_tokens_amount: 20,
+        };
+        let logs = vec![
+            format!("Program {} invoke [1]", launcher::ID),
+            format!("Program {} invoke [2]", dex::ID),
+            format!("Program data: {}", STANDARD.encode(initialize.data())),
+            format!("Program {} success", dex::ID),
+            format!(
+                "Program data: {}",
+                STANDARD.encode(launcher_initialize.data())
+            ),
+            format!("Program {} success", launcher::ID),
+        ];
+
+        let swap = SwapEvent {
+            dex_id,
+            input_vault_before: 0,
+            output_vault_before: 0,
+            input_amount: 100,
+            output_amount: 40,
+            input_transfer_fee: 0,
+            output_transfer_fee: 0,
+            remaining_tokens: 0,
+            protocol_fee: 1,
+            creator_fee: 0,
+            referral_fee: 0,
+            unfilled_amount: 0,
+            base_input: true,
+            zero_to_one: false,
+        };
+        let swap_logs = vec![
+            format!("Program {} invoke [1]", dex::ID),
+            format!("Program data: {}", STANDARD.encode(swap.data())),
+            format!("Program {} success", dex::ID),
+        ];
+
+        let program_ids = ProgramIds::default();
+        let mut store = Store::default();
+        let create = TransactionInfo {
+            signature: "create".to_string(),
+            slot: 1,
+            block_time: None,
+        };
+        let trade = TransactionInfo {
+            signature: "trade".to_string(),
+            slot: 2,
+            block_time: None,
+        };
+        assert!(store
+            .index_transaction(&program_ids, &create, &logs)
+            .unwrap());
+        assert!(store
+            .index_transaction(&program_ids, &trade, &swap_logs)
+            .unwrap());
+        assert!(!store
+            .index_transaction(&program_ids, &trade, &swap_logs)
+            .unwrap());
+
+        let pool = store.pool(&dex_id).unwrap();
+        assert_eq!(pool.mint_zero, initialize.mint_zero);
+        assert_eq!(pool.created_slot, 1);
+        assert_eq!(pool.team_tokens_amount, 10);
+        assert_eq!(pool.faucet_tokens_amount, 20);
+        assert_eq!(pool.trade_count, 1);
+        assert_eq!(pool.volume_token_zero, 40);
+        assert_eq!(pool.volume_token_one, 100);
+        assert_eq!(store.trades(&dex_id).count(), 1);
+        assert_eq!(store.pools_by_mint(&initialize.mint_one).count(), 1);
+
+        let restored = Store::try_from_slice(&store.try_to_vec().unwrap()).unwrap();
+        assert_eq!(restored.pool(&dex_id), Some(pool));
+        assert!(restored.is_indexed("trade"));
+    }
+}
@@ -39,6 +39,9 @@ solana-test-validator \
   --clone So11111111111111111111111111111111111111112 \
   --clone metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s \
   --clone PwDiXFxQsGra4sFFTT8r1QWRMd4vfumiWC1jfWNfdYT \
+  --clone whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc \
+  --clone 2LecshUwdy9xi7meFgHtFJQNSKk4KdTrcpvaB56dP2NQ \
+  --clone BGnhGXT9CCt5WYS23zg9sqsAT2MGXkq7VSwch9pML82W \
   > validator.log 2>&1 &
 
 # Store the PID of the background process
@@ -0,0 +1,1050 @@
+use super::pda::*;
+use crate::instructions::{FULL_RANGE_TICK_INDEX, WHIRLPOOLS_CONFIG};
+use crate::states::FeeRecipient;
+use crate::{accounts, instruction};
+use anchor_lang::{
+    prelude::*, solana_program::instruction::Instruction, solana_program::sysvar, system_program,
+    InstructionData,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address_with_program_id},
+    token::{self, spl_token::native_mint},
+    token_2022,
+};
+
+/// A token of the instruction with the user token account of it
+#[derive(Clone, Copy, Debug)]
+pub struct UserToken {
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub token_account: Pubkey,
+}
+
+/// Accounts of a swap, the native swaps replace the user token account of the native mint
+/// with the native account of the payer
+#[derive(Clone, Debug)]
+pub struct SwapKeys {
+    pub payer: Pubkey,
+    pub config: Pubkey,
+    pub dex_state: Pubkey,
+    pub input: UserToken,
+    pub output: UserToken,
+    /// Token account of the input mint receiving the referral fee
+    pub referrer: Option<Pubkey>,
+    /// Pass the wallet limit of the payer, required for buys during the anti-sniper window
+    pub wallet_limit: bool,
+    /// Record the swap in the trader stats of the payer
+    pub trader_stats: bool,
+    /// Extra accounts of the mint transfer hooks
+    pub hook_accounts: Vec<AccountMeta>,
+}
+
+/// Accounts of a limit order, the input token account of the owner funds the escrow
+/// and receives it back on cancel
+#[derive(Clone, Debug)]
+pub struct LimitOrderKeys {
+    pub owner: Pubkey,
+    pub dex_state: Pubkey,
+    pub order_id: u64,
+    pub input: UserToken,
+    /// Extra accounts of the mint transfer hooks
+    pub hook_accounts: Vec<AccountMeta>,
+}
+
+/// A limit order filled by the crank, the owner token account of the output mint receives the swap output
+#[derive(Clone, Copy, Debug)]
+pub struct OrderFill {
+    pub order: Pubkey,
+    pub owner: Pubkey,
+    pub output_token_account: Pubkey,
+}
+
+/// Accounts of the dex launch, the dex vaults are the creator token accounts of the pool
+#[derive(Clone, Copy, Debug)]
+pub struct LaunchDexKeys {
+    pub payer: Pubkey,
+    pub dex_config: Pubkey,
+    pub dex_state: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub token_0_program: Pubkey,
+    pub token_1_program: Pubkey,
+}
+
+/// Accounts of the dex creation, the tokens are sorted by mint
+#[derive(Clone, Debug)]
+pub struct InitializeDexKeys {
+    pub cpi_authority: Pubkey,
+    pub payer: Pubkey,
+    pub config: Pubkey,
+    pub token_a: UserToken,
+    pub token_b: UserToken,
+    /// Extra accounts of the mint transfer hooks
+    pub hook_accounts: Vec<AccountMeta>,
+}
+
+pub fn initialize_authority_manager(payer: Pubkey, cpi_authority: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeAuthorityManager {
+            payer,
+            authority_manager: authority_manager_address().0,
+            authority: authority_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeAuthorityManager { cpi_authority },
+        Vec::new(),
+    )
+}
+
+pub fn update_authority_manager_admin(payer: Pubkey, admin: Pubkey) -> Instruction {
+    build(
+        update_authority_manager_accounts(payer),
+        instruction::UpdateAuthorityManagerAdmin { admin },
+        Vec::new(),
+    )
+}
+
+pub fn update_authority_manager_cpi_authority(payer: Pubkey, cpi_authority: Pubkey) -> Instruction {
+    build(
+        update_authority_manager_accounts(payer),
+        instruction::UpdateAuthorityManagerCpiAuthority { cpi_authority },
+        Vec::new(),
+    )
+}
+
+fn update_authority_manager_accounts(payer: Pubkey) -> accounts::UpdateAuthorityManager {
+    accounts::UpdateAuthorityManager {
+        payer,
+        authority_manager: authority_manager_address().0,
+    }
+}
+
+pub fn initialize_config(admin: Pubkey, index: u16) -> Instruction {
+    build(
+        accounts::InitializeConfigState {
+            admin,
+            authority_manager: authority_manager_address().0,
+            config: config_address(index).0,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeConfig { index },
+        Vec::new(),
+    )
+}
+
+pub fn update_config_swap_fee_rate(
+    admin: Pubkey,
+    config: Pubkey,
+    swap_fee_rate: u64,
+) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigSwapFeeRate { swap_fee_rate },
+    )
+}
+
+pub fn update_config_launch_fee_rate(
+    admin: Pubkey,
+    config: Pubkey,
+    launch_fee_rate: u64,
+) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigLaunchFeeRate { launch_fee_rate },
+    )
+}
+
+pub fn update_config_vault_reserve_bound(
+    admin: Pubkey,
+    config: Pubkey,
+    vault_reserve_bound: u64,
+) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigVaultReserveBound {
+            vault_reserve_bound,
+        },
+    )
+}
+
+pub fn update_config_initial_reserve(
+    admin: Pubkey,
+    config: Pubkey,
+    initial_reserve: u64,
+) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigInitialReserve { initial_reserve },
+    )
+}
+
+pub fn update_config_curve_type(admin: Pubkey, config: Pubkey, curve_type: u8) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigCurveType { curve_type },
+    )
+}
+
+pub fn update_config_virtual_reserve(
+    admin: Pubkey,
+    config: Pubkey,
+    virtual_reserve: u64,
+) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigVirtualReserve { virtual_reserve },
+    )
+}
+
+pub fn update_config_virtual_supply(
+    admin: Pubkey,
+    config: Pubkey,
+    virtual_supply: u64,
+) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigVirtualSupply { virtual_supply },
+    )
+}
+
+pub fn update_config_creator_fee_rate(
+    admin: Pubkey,
+    config: Pubkey,
+    creator_fee_rate: u64,
+) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigCreatorFeeRate { creator_fee_rate },
+    )
+}
+
+pub fn update_config_referral_fee_rate(
+    admin: Pubkey,
+    config: Pubkey,
+    referral_fee_rate: u64,
+) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigReferralFeeRate { referral_fee_rate },
+    )
+}
+
+pub fn update_config_disabled(admin: Pubkey, config: Pubkey, disabled: bool) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigDisabled { disabled },
+    )
+}
+
+pub fn update_config_sniper_protection(
+    admin: Pubkey,
+    config: Pubkey,
+    sniper_window: u64,
+    sniper_max_swap_input: u64,
+    sniper_max_wallet_input: u64,
+) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigSniperProtection {
+            sniper_window,
+            sniper_max_swap_input,
+            sniper_max_wallet_input,
+        },
+    )
+}
+
+pub fn update_config_launch_window(
+    admin: Pubkey,
+    config: Pubkey,
+    launch_window: u64,
+) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigLaunchWindow { launch_window },
+    )
+}
+
+pub fn update_config_launch_lamports(
+    admin: Pubkey,
+    config: Pubkey,
+    launch_lamports: u64,
+    keeper_reward: u64,
+) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigLaunchLamports {
+            launch_lamports,
+            keeper_reward,
+        },
+    )
+}
+
+pub fn update_config_launch_target(
+    admin: Pubkey,
+    config: Pubkey,
+    launch_target: u8,
+) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigLaunchTarget { launch_target },
+    )
+}
+
+pub fn update_config_lp_policy(
+    admin: Pubkey,
+    config: Pubkey,
+    lp_burn_rate: u64,
+    lp_lock_cliff: u64,
+    lp_vesting_period: u64,
+) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigLpPolicy {
+            lp_burn_rate,
+            lp_lock_cliff,
+            lp_vesting_period,
+        },
+    )
+}
+
+pub fn update_config_sniper_fee(
+    admin: Pubkey,
+    config: Pubkey,
+    sniper_fee_rate: u64,
+    sniper_fee_period: u64,
+    sniper_fee_steps: u64,
+) -> Instruction {
+    update_config(
+        admin,
+        config,
+        instruction::UpdateConfigSniperFee {
+            sniper_fee_rate,
+            sniper_fee_period,
+            sniper_fee_steps,
+        },
+    )
+}
+
+fn update_config(admin: Pubkey, config: Pubkey, data: impl InstructionData) -> Instruction {
+    build(
+        accounts::UpdateConfigState {
+            admin,
+            config,
+            authority_manager: authority_manager_address().0,
+        },
+        data,
+        Vec::new(),
+    )
+}
+
+/// Withdraw the protocol fees, the recipient token accounts are the token_0 and token_1 accounts
+/// of each fee recipient in the fee distribution order
+pub fn withdraw_dex_fee(
+    admin: Pubkey,
+    dex_state: Pubkey,
+    token_0_mint: Pubkey,
+    token_1_mint: Pubkey,
+    recipient_token_accounts: &[(Pubkey, Pubkey)],
+    hook_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut remaining_accounts = recipient_token_accounts
+        .iter()
+        .flat_map(|(token_0_account, token_1_account)| {
+            [
+                AccountMeta::new(*token_0_account, false),
+                AccountMeta::new(*token_1_account, false),
+            ]
+        })
+        .collect::<Vec<_>>();
+    remaining_accounts.extend(hook_accounts);
+
+    build(
+        accounts::WithdrawDexFee {
+            admin,
+            authority_manager: authority_manager_address().0,
+            authority: authority_address().0,
+            dex_state,
+            token_0_vault: dex_vault_address(&dex_state, &token_0_mint).0,
+            token_1_vault: dex_vault_address(&dex_state, &token_1_mint).0,
+            vault_0_mint: token_0_mint,
+            vault_1_mint: token_1_mint,
+            fee_distribution: fee_distribution_address().0,
+            token_program: token::ID,
+            token_program_2022: token_2022::ID,
+        },
+        instruction::WithdrawDexFee {},
+        remaining_accounts,
+    )
+}
+
+pub fn update_fee_recipients(admin: Pubkey, recipients: Vec<FeeRecipient>) -> Instruction {
+    build(
+        accounts::UpdateFeeRecipients {
+            admin,
+            authority_manager: authority_manager_address().0,
+            fee_distribution: fee_distribution_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::UpdateFeeRecipients { recipients },
+        Vec::new(),
+    )
+}
+
+pub fn withdraw_creator_fee(
+    pool_creator: Pubkey,
+    dex_state: Pubkey,
+    token_0_mint: Pubkey,
+    token_1_mint: Pubkey,
+    recipient_token_0_account: Pubkey,
+    recipient_token_1_account: Pubkey,
+    hook_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    build(
+        accounts::WithdrawCreatorFee {
+            pool_creator,
+            authority_manager: authority_manager_address().0,
+            authority: authority_address().0,
+            dex_state,
+            token_0_vault: dex_vault_address(&dex_state, &token_0_mint).0,
+            token_1_vault: dex_vault_address(&dex_state, &token_1_mint).0,
+            vault_0_mint: token_0_mint,
+            vault_1_mint: token_1_mint,
+            recipient_token_0_account,
+            recipient_token_1_account,
+            token_program: token::ID,
+            token_program_2022: token_2022::ID,
+        },
+        instruction::WithdrawCreatorFee {},
+        hook_accounts,
+    )
+}
+
+/// Claim the vested LP tokens locked in the dex authority LP token account
+pub fn claim_locked_lp(
+    pool_creator: Pubkey,
+    dex_state: Pubkey,
+    lp_mint: Pubkey,
+    recipient_lp_token: Pubkey,
+) -> Instruction {
+    let authority = authority_address().0;
+    build(
+        accounts::ClaimLockedLp {
+            pool_creator,
+            authority_manager: authority_manager_address().0,
+            authority,
+            dex_state,
+            lp_mint,
+            lp_vault: get_associated_token_address_with_program_id(
+                &authority,
+                &lp_mint,
+                &token::ID,
+            ),
+            recipient_lp_token,
+            token_program: token::ID,
+        },
+        instruction::ClaimLockedLp {},
+        Vec::new(),
+    )
+}
+
+pub fn initialize_dex(
+    keys: &InitializeDexKeys,
+    init_amount: u64,
+    vault_for_reserve_bound: bool,
+) -> Instruction {
+    let (token_zero, token_one) = if keys.token_a.mint < keys.token_b.mint {
+        (keys.token_a, keys.token_b)
+    } else {
+        (keys.token_b, keys.token_a)
+    };
+    let dex_state = dex_state_address(&token_zero.mint, &token_one.mint).0;
+
+    build(
+        accounts::InitializeDex {
+            cpi_authority: keys.cpi_authority,
+            payer: keys.payer,
+            config: keys.config,
+            authority_manager: authority_manager_address().0,
+            authority: authority_address().0,
+            dex_state,
+            mint_zero: token_zero.mint,
+            mint_one: token_one.mint,
+            payer_vault_zero: token_zero.token_account,
+            payer_vault_one: token_one.token_account,
+            dex_vault_zero: dex_vault_address(&dex_state, &token_zero.mint).0,
+            dex_vault_one: dex_vault_address(&dex_state, &token_one.mint).0,
+            observation_state: observation_address(&dex_state).0,
+            token_program: token::ID,
+            token_program_zero: token_zero.token_program,
+            token_program_one: token_one.token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::InitializeDex {
+            init_amount,
+            vault_for_reserve_bound,
+        },
+        keys.hook_accounts.clone(),
+    )
+}
+
+pub fn swap_base_input(keys: &SwapKeys, amount_in: u64, minimum_amount_out: u64) -> Instruction {
+    build(
+        swap_accounts(keys),
+        instruction::SwapBaseInput {
+            amount_in,
+            minimum_amount_out,
+        },
+        swap_remaining_accounts(keys),
+    )
+}
+
+pub fn swap_base_output(keys: &SwapKeys, max_amount_in: u64, amount_out: u64) -> Instruction {
+    build(
+        swap_accounts(keys),
+        instruction::SwapBaseOutput {
+            max_amount_in,
+            amount_out,
+        },
+        swap_remaining_accounts(keys),
+    )
+}
+
+pub fn swap_base_input_native(
+    keys: &SwapKeys,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Instruction {
+    build(
+        swap_native_accounts(keys),
+        instruction::SwapBaseInputNative {
+            amount_in,
+            minimum_amount_out,
+        },
+        swap_remaining_accounts(keys),
+    )
+}
+
+pub fn swap_base_output_native(
+    keys: &SwapKeys,
+    max_amount_in: u64,
+    amount_out: u64,
+) -> Instruction {
+    build(
+        swap_native_accounts(keys),
+        instruction::SwapBaseOutputNative {
+            max_amount_in,
+            amount_out,
+        },
+        swap_remaining_accounts(keys),
+    )
+}
+
+/// Quote swap_base_input of the keys, the `SwapQuote` is the return data of the simulation
+pub fn quote_base_input(keys: &SwapKeys, amount_in: u64, minimum_amount_out: u64) -> Instruction {
+    build(
+        quote_accounts(keys),
+        instruction::QuoteBaseInput {
+            amount_in,
+            minimum_amount_out,
+            with_referrer: keys.referrer.is_some(),
+        },
+        Vec::new(),
+    )
+}
+
+/// Quote swap_base_output of the keys, the `SwapQuote` is the return data of the simulation
+pub fn quote_base_output(
+    keys: &SwapKeys,
+    max_amount_in: u64,
+    amount_out_less_fee: u64,
+) -> Instruction {
+    build(
+        quote_accounts(keys),
+        instruction::QuoteBaseOutput {
+            max_amount_in,
+            amount_out_less_fee,
+            with_referrer: keys.referrer.is_some(),
+        },
+        Vec::new(),
+    )
+}
+
+/// Swap through two dex, the output token account of the first swap is the input token account
+/// of the second one. The referrer and hook accounts of each hop come from its keys
+pub fn swap_route(
+    first: &SwapKeys,
+    second: &SwapKeys,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Instruction {
+    let first_remaining_accounts = swap_remaining_accounts(first);
+    let first_hop_accounts = u8::try_from(first_remaining_accounts.len()).unwrap();
+
+    build(
+        accounts::SwapRoute {
+            first: swap_accounts(first),
+            second: swap_accounts(second),
+        },
+        instruction::SwapRoute {
+            amount_in,
+            minimum_amount_out,
+            first_hop_accounts,
+        },
+        first_remaining_accounts
+            .into_iter()
+            .chain(swap_remaining_accounts(second))
+            .collect(),
+    )
+}
+
+pub fn place_limit_order(
+    keys: &LimitOrderKeys,
+    amount_in: u64,
+    minimum_amount_out: u64,
+    trigger_price_x32: u128,
+    trigger_above: bool,
+) -> Instruction {
+    let order = limit_order_address(&keys.dex_state, &keys.owner, keys.order_id).0;
+
+    build(
+        accounts::PlaceLimitOrder {
+            owner: keys.owner,
+            dex_state: keys.dex_state,
+            order,
+            escrow: limit_order_escrow_address(&order).0,
+            input_token_account: keys.input.token_account,
+            input_token_mint: keys.input.mint,
+            input_token_program: keys.input.token_program,
+            system_program: system_program::ID,
+        },
+        instruction::PlaceLimitOrder {
+            order_id: keys.order_id,
+            amount_in,
+            minimum_amount_out,
+            trigger_price_x32,
+            trigger_above,
+        },
+        keys.hook_accounts.clone(),
+    )
+}
+
+pub fn cancel_limit_order(keys: &LimitOrderKeys) -> Instruction {
+    let order = limit_order_address(&keys.dex_state, &keys.owner, keys.order_id).0;
+
+    build(
+        accounts::CancelLimitOrder {
+            owner: keys.owner,
+            order,
+            escrow: limit_order_escrow_address(&order).0,
+            input_token_account: keys.input.token_account,
+            input_token_mint: keys.input.mint,
+            input_token_program: keys.input.token_program,
+        },
+        instruction::CancelLimitOrder {},
+        keys.hook_accounts.clone(),
+    )
+}
+
+/// Fill limit orders selling the input token of the keys, the payer is the keeper,
+/// the user token accounts and the referrer of the keys are not used
+pub fn fill_orders(keys: &SwapKeys, orders: &[OrderFill]) -> Instruction {
+    let remaining_accounts = orders
+        .iter()
+        .flat_map(|fill| {
+            [
+                AccountMeta::new(fill.order, false),
+                AccountMeta::new(limit_order_escrow_address(&fill.order).0, false),
+                AccountMeta::new(fill.owner, false),
+                AccountMeta::new(fill.output_token_account, false),
+            ]
+        })
+        .chain(keys.hook_accounts.iter().cloned())
+        .collect();
+
+    build(
+        accounts::FillOrders {
+            keeper: keys.payer,
+            config: keys.config,
+            authority_manager: authority_manager_address().0,
+            authority: authority_address().0,
+            dex_state: keys.dex_state,
+            observation_state: observation_address(&keys.dex_state).0,
+            input_vault: dex_vault_address(&keys.dex_state, &keys.input.mint).0,
+            output_vault: dex_vault_address(&keys.dex_state, &keys.output.mint).0,
+            input_token_program: keys.input.token_program,
+            output_token_program: keys.output.token_program,
+            input_token_mint: keys.input.mint,
+            output_token_mint: keys.output.mint,
+            system_program: system_program::ID,
+        },
+        instruction::FillOrders {
+            order_count: u8::try_from(orders.len()).unwrap(),
+        },
+        remaining_accounts,
+    )
+}
+
+/// Redeem supply tokens once the dex missed its launch deadline
+pub fn refund(
+    payer: Pubkey,
+    dex_state: Pubkey,
+    supply: UserToken,
+    reserve: UserToken,
+    amount: u64,
+    hook_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    build(
+        accounts::Refund {
+            payer,
+            authority_manager: authority_manager_address().0,
+            authority: authority_address().0,
+            dex_state,
+            supply_token_account: supply.token_account,
+            reserve_token_account: reserve.token_account,
+            supply_vault: dex_vault_address(&dex_state, &supply.mint).0,
+            reserve_vault: dex_vault_address(&dex_state, &reserve.mint).0,
+            supply_token_program: supply.token_program,
+            reserve_token_program: reserve.token_program,
+            supply_token_mint: supply.mint,
+            reserve_token_mint: reserve.mint,
+        },
+        instruction::Refund { amount },
+        hook_accounts,
+    )
+}
+
+/// Return the seed reserve to the pool creator once the dex missed its launch deadline
+pub fn withdraw_seed_reserve(
+    pool_creator: Pubkey,
+    dex_state: Pubkey,
+    reserve: UserToken,
+    hook_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    build(
+        accounts::WithdrawSeedReserve {
+            pool_creator,
+            authority_manager: authority_manager_address().0,
+            authority: authority_address().0,
+            dex_state,
+            reserve_token_account: reserve.token_account,
+            reserve_vault: dex_vault_address(&dex_state, &reserve.mint).0,
+            reserve_token_program: reserve.token_program,
+            reserve_token_mint: reserve.mint,
+        },
+        instruction::WithdrawSeedReserve {},
+        hook_accounts,
+    )
+}
+
+pub fn launch_dex(keys: &LaunchDexKeys, amm_config: Pubkey, shared_lamports: u64) -> Instruction {
+    build(
+        launch_dex_accounts(keys, amm_config),
+        instruction::LaunchDex { shared_lamports },
+        Vec::new(),
+    )
+}
+
+pub fn crank_launch_dex(keys: &LaunchDexKeys, amm_config: Pubkey) -> Instruction {
+    build(
+        launch_dex_accounts(keys, amm_config),
+        instruction::CrankLaunchDex {},
+        Vec::new(),
+    )
+}
+
+pub fn launch_dex_clmm(keys: &LaunchDexKeys, shared_lamports: u64) -> Instruction {
+    build(
+        launch_dex_clmm_accounts(keys),
+        instruction::LaunchDexClmm { shared_lamports },
+        Vec::new(),
+    )
+}
+
+pub fn crank_launch_dex_clmm(keys: &LaunchDexKeys) -> Instruction {
+    build(
+        launch_dex_clmm_accounts(keys),
+        instruction::CrankLaunchDexClmm {},
+        Vec::new(),
+    )
+}
+
+fn swap_accounts(keys: &SwapKeys) -> accounts::Swap {
+    accounts::Swap {
+        payer: keys.payer,
+        authority: authority_address().0,
+        authority_manager: authority_manager_address().0,
+        config: keys.config,
+        dex_state: keys.dex_state,
+        observation_state: observation_address(&keys.dex_state).0,
+        input_token_account: keys.input.token_account,
+        output_token_account: keys.output.token_account,
+        input_vault: dex_vault_address(&keys.dex_state, &keys.input.mint).0,
+        output_vault: dex_vault_address(&keys.dex_state, &keys.output.mint).0,
+        input_token_program: keys.input.token_program,
+        output_token_program: keys.output.token_program,
+        input_token_mint: keys.input.mint,
+        output_token_mint: keys.output.mint,
+        wallet_limit: keys
+            .wallet_limit
+            .then(|| wallet_limit_address(&keys.dex_state, &keys.payer).0),
+        trader_stats: keys
+            .trader_stats
+            .then(|| trader_stats_address(&keys.dex_state, &keys.payer).0),
+        system_program: system_program::ID,
+    }
+}
+
+fn swap_native_accounts(keys: &SwapKeys) -> accounts::SwapNative {
+    // the payer swaps lamports for the native mint side
+    let token_account = if keys.input.mint == native_mint::ID {
+        keys.output.token_account
+    } else {
+        keys.input.token_account
+    };
+
+    accounts::SwapNative {
+        payer: keys.payer,
+        config: keys.config,
+        authority_manager: authority_manager_address().0,
+        authority: authority_address().0,
+        dex_state: keys.dex_state,
+        observation_state: observation_address(&keys.dex_state).0,
+        token_account,
+        native_token_account: native_account_address(&keys.payer).0,
+        input_vault: dex_vault_address(&keys.dex_state, &keys.input.mint).0,
+        output_vault: dex_vault_address(&keys.dex_state, &keys.output.mint).0,
+        input_token_program: keys.input.token_program,
+        output_token_program: keys.output.token_program,
+        input_token_mint: keys.input.mint,
+        output_token_mint: keys.output.mint,
+        wallet_limit: keys
+            .wallet_limit
+            .then(|| wallet_limit_address(&keys.dex_state, &keys.payer).0),
+        trader_stats: keys
+            .trader_stats
+            .then(|| trader_stats_address(&keys.dex_state, &keys.payer).0),
+        system_program: system_program::ID,
+    }
+}
+
+/// The dex program id stands for no referrer when the hook accounts follow
+fn swap_remaining_accounts(keys: &SwapKeys) -> Vec<AccountMeta> {
+    if keys.hook_accounts.is_empty() {
+        return keys
+            .referrer
+            .map(|referrer| vec![AccountMeta::new(referrer, false)])
+            .unwrap_or_default();
+    }
+
+    let referrer = match keys.referrer {
+        Some(referrer) => AccountMeta::new(referrer, false),
+        None => AccountMeta::new_readonly(crate::id(), false),
+    };
+    std::iter::once(referrer)
+        .chain(keys.hook_accounts.iter().cloned())
+        .collect()
+}
+
+fn quote_accounts(keys: &SwapKeys) -> accounts::QuoteSwap {
+    accounts::QuoteSwap {
+        config: keys.config,
+        dex_state: keys.dex_state,
+        input_vault: dex_vault_address(&keys.dex_state, &keys.input.mint).0,
+        output_vault: dex_vault_address(&keys.dex_state, &keys.output.mint).0,
+        input_token_mint: keys.input.mint,
+        output_token_mint: keys.output.mint,
+    }
+}
+
+fn launch_dex_accounts(keys: &LaunchDexKeys, amm_config: Pubkey) -> accounts::LaunchDex {
+    let dex_authority = authority_address().0;
+    let pool_state = raydium_pool_address(&amm_config, &keys.token_0_mint, &keys.token_1_mint).0;
+    let lp_mint = raydium_lp_mint_address(&pool_state).0;
+
+    accounts::LaunchDex {
+        payer: keys.payer,
+        dex_authority,
+        dex_authority_manager: authority_manager_address().0,
+        dex_config: keys.dex_config,
+        dex_state: keys.dex_state,
+        cp_swap_program: raydium_cp_swap::id(),
+        amm_config,
+        authority: raydium_authority_address().0,
+        pool_state,
+        token_0_mint: keys.token_0_mint,
+        token_1_mint: keys.token_1_mint,
+        lp_mint,
+        creator_token_0: dex_vault_address(&keys.dex_state, &keys.token_0_mint).0,
+        creator_token_1: dex_vault_address(&keys.dex_state, &keys.token_1_mint).0,
+        creator_lp_token: get_associated_token_address_with_program_id(
+            &dex_authority,
+            &lp_mint,
+            &token::ID,
+        ),
+        token_0_vault: raydium_vault_address(&pool_state, &keys.token_0_mint).0,
+        token_1_vault: raydium_vault_address(&pool_state, &keys.token_1_mint).0,
+        create_pool_fee: raydium_cp_swap::create_pool_fee_reveiver::id(),
+        observation_state: raydium_observation_address(&pool_state).0,
+        token_program: token::ID,
+        token_0_program: keys.token_0_program,
+        token_1_program: keys.token_1_program,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+    }
+}
+
+fn launch_dex_clmm_accounts(keys: &LaunchDexKeys) -> accounts::LaunchDexClmm {
+    let dex_authority = authority_address().0;
+    let pool_state = whirlpool_address(&keys.token_0_mint, &keys.token_1_mint).0;
+    let position_mint = clmm_position_mint_address(&keys.dex_state).0;
+
+    accounts::LaunchDexClmm {
+        payer: keys.payer,
+        dex_authority,
+        dex_authority_manager: authority_manager_address().0,
+        dex_config: keys.dex_config,
+        dex_state: keys.dex_state,
+        whirlpool_program: whirlpool_cpi::id(),
+        whirlpools_config: WHIRLPOOLS_CONFIG,
+        fee_tier: whirlpool_fee_tier_address().0,
+        pool_state,
+        token_0_mint: keys.token_0_mint,
+        token_1_mint: keys.token_1_mint,
+        creator_token_0: dex_vault_address(&keys.dex_state, &keys.token_0_mint).0,
+        creator_token_1: dex_vault_address(&keys.dex_state, &keys.token_1_mint).0,
+        token_0_vault: clmm_vault_address(&keys.dex_state, &keys.token_0_mint).0,
+        token_1_vault: clmm_vault_address(&keys.dex_state, &keys.token_1_mint).0,
+        tick_array_lower: whirlpool_tick_array_address(&pool_state, -FULL_RANGE_TICK_INDEX).0,
+        tick_array_upper: whirlpool_tick_array_address(&pool_state, FULL_RANGE_TICK_INDEX).0,
+        position_mint,
+        position: whirlpool_position_address(&position_mint).0,
+        position_token_account: get_associated_token_address_with_program_id(
+            &dex_authority,
+            &position_mint,
+            &token::ID,
+        ),
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+    }
+}
+
+fn build(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(remaining_accounts);
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: account_metas,
+        data: data.data(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn swap_keys() -> SwapKeys {
+        let input_mint = Pubkey::new_unique();
+        let output_mint = Pubkey::new_unique();
+        SwapKeys {
+            payer: Pubkey::new_unique(),
+            config: config_address(0).0,
+            dex_state: dex_state_address(&input_mint, &output_mint).0,
+            input: UserToken {
+                mint: input_mint,
+                token_program: token::ID,
+                token_account: Pubkey::new_unique(),
+            },
+            output: UserToken {
+                mint: output_mint,
+                token_program: token_2022::ID,
+                token_account: Pubkey::new_unique(),
+            },
+            referrer: None,
+            wallet_limit: false,
+            trader_stats: false,
+            hook_accounts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn swap_base_input_accounts_test() {
+        let mut keys = swap_keys();
+
+        let ix = swap_base_input(&keys, 1_000, 900);
+        assert_eq!(ix.program_id, crate::id());
+        assert_eq!(ix.accounts.len(), 17);
+        // the program id stands for the wallet limit and trader stats not passed
+        assert_eq!(ix.accounts[14].pubkey, crate::id());
+        assert_eq!(ix.accounts[15].pubkey, crate::id());
+
+        keys.wallet_limit = true;
+        keys.trader_stats = true;
+        keys.hook_accounts = vec![AccountMeta::new_readonly(Pubkey::new_unique(), false)];
+
+        let ix = swap_base_input(&keys, 1_000, 900);
+        assert_eq!(
+            ix.accounts[14].pubkey,
+            wallet_limit_address(&keys.dex_state, &keys.payer).0
+        );
+        assert_eq!(
+            ix.accounts[15].pubkey,
+            trader_stats_address(&keys.dex_state, &keys.payer).0
+        );
+        assert_eq!(ix.accounts.len(), 19);
+        assert_eq!(ix.accounts[17].pubkey, crate::id());
+        assert_eq!(ix.accounts[18].pubkey, keys.hook_accounts[0].pubkey);
+    }
+
+    #[test]
+    fn swap_route_remaining_accounts_test() {
+        let mut first = swap_keys();
+        let mut second = swap_keys();
+        first.referrer = Some(Pubkey::new_unique());
+        second.hook_accounts = vec![AccountMeta::new_readonly(Pubkey::new_unique(), false)];
+
+        let ix = swap_route(&first, &second, 1_000, 900);
+        let data = instruction::SwapRoute::try_from_slice(&ix.data[8..]).unwrap();
+        assert_eq!(data.first_hop_accounts, 1);
+        assert_eq!(ix.accounts.len(), 2 * 17 + 3);
+        assert_eq!(ix.accounts[34].pubkey, first.referrer.unwrap());
+        // the program id stands for no referrer of the second hop
+        assert_eq!(ix.accounts[35].pubkey, crate::id());
+        assert_eq!(ix.accounts[36].pubkey, second.hook_accounts[0].pubkey);
+    }
+
+    #[test]
+    fn dex_state_address_test() {
+        let mint_a = Pubkey::new_unique();
+        let mint_b = Pubkey::new_unique();
+
+        assert_eq!(
+            dex_state_address(&mint_a, &mint_b),
+            dex_state_address(&mint_b, &mint_a)
+        );
+    }
+}
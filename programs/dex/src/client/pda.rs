@@ -0,0 +1,217 @@
+use crate::instructions::{tick_array_start_index, WHIRLPOOLS_CONFIG, WHIRLPOOL_TICK_SPACING};
+use crate::states::*;
+use anchor_lang::prelude::Pubkey;
+use raydium_cp_swap::{
+    states::{oracle::OBSERVATION_SEED, POOL_LP_MINT_SEED},
+    AUTH_SEED,
+};
+
+pub fn authority_manager_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DEX_AUTHORITY_MANAGER_SEED.as_bytes()], &crate::id())
+}
+
+pub fn authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DEX_AUTHORITY_SEED.as_bytes()], &crate::id())
+}
+
+pub fn config_address(index: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DEX_CONFIG_SEED.as_bytes(), &ConfigState::index_seed(index)],
+        &crate::id(),
+    )
+}
+
+/// The dex of two mints, in any order
+pub fn dex_state_address(mint_a: &Pubkey, mint_b: &Pubkey) -> (Pubkey, u8) {
+    let (mint_0, mint_1) = sort_mints(mint_a, mint_b);
+    Pubkey::find_program_address(
+        &[DEX_STATE_SEED.as_bytes(), mint_0.as_ref(), mint_1.as_ref()],
+        &crate::id(),
+    )
+}
+
+pub fn dex_vault_address(dex_state: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DEX_VAULT_SEED.as_bytes(), dex_state.as_ref(), mint.as_ref()],
+        &crate::id(),
+    )
+}
+
+pub fn observation_address(dex_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DEX_OBSERVATION_SEED.as_bytes(), dex_state.as_ref()],
+        &crate::id(),
+    )
+}
+
+pub fn wallet_limit_address(dex_state: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            DEX_WALLET_LIMIT_SEED.as_bytes(),
+            dex_state.as_ref(),
+            wallet.as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn trader_stats_address(dex_state: &Pubkey, trader: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            DEX_TRADER_STATS_SEED.as_bytes(),
+            dex_state.as_ref(),
+            trader.as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn limit_order_address(dex_state: &Pubkey, owner: &Pubkey, order_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            DEX_LIMIT_ORDER_SEED.as_bytes(),
+            dex_state.as_ref(),
+            owner.as_ref(),
+            &order_id.to_be_bytes(),
+        ],
+        &crate::id(),
+    )
+}
+
+pub fn limit_order_escrow_address(order: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DEX_LIMIT_ORDER_ESCROW_SEED.as_bytes(), order.as_ref()],
+        &crate::id(),
+    )
+}
+
+pub fn fee_distribution_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DEX_FEE_DISTRIBUTION_SEED.as_bytes()], &crate::id())
+}
+
+pub fn native_account_address(payer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DEX_NATIVE_ACCOUNT_SEED.as_bytes(), payer.as_ref()],
+        &crate::id(),
+    )
+}
+
+pub fn raydium_authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &raydium_cp_swap::id())
+}
+
+pub fn raydium_pool_address(
+    amm_config: &Pubkey,
+    token_0_mint: &Pubkey,
+    token_1_mint: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            raydium_cp_swap::states::POOL_SEED.as_bytes(),
+            amm_config.as_ref(),
+            token_0_mint.as_ref(),
+            token_1_mint.as_ref(),
+        ],
+        &raydium_cp_swap::id(),
+    )
+}
+
+pub fn raydium_lp_mint_address(pool_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[POOL_LP_MINT_SEED.as_bytes(), pool_state.as_ref()],
+        &raydium_cp_swap::id(),
+    )
+}
+
+pub fn raydium_vault_address(pool_state: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            raydium_cp_swap::states::POOL_VAULT_SEED.as_bytes(),
+            pool_state.as_ref(),
+            mint.as_ref(),
+        ],
+        &raydium_cp_swap::id(),
+    )
+}
+
+pub fn raydium_observation_address(pool_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[OBSERVATION_SEED.as_bytes(), pool_state.as_ref()],
+        &raydium_cp_swap::id(),
+    )
+}
+
+pub fn whirlpool_address(token_0_mint: &Pubkey, token_1_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            whirlpool_cpi::state::WHIRLPOOL_SEED.as_bytes(),
+            WHIRLPOOLS_CONFIG.as_ref(),
+            token_0_mint.as_ref(),
+            token_1_mint.as_ref(),
+            WHIRLPOOL_TICK_SPACING.to_le_bytes().as_ref(),
+        ],
+        &whirlpool_cpi::id(),
+    )
+}
+
+pub fn whirlpool_fee_tier_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            whirlpool_cpi::state::FEE_TIER_SEED.as_bytes(),
+            WHIRLPOOLS_CONFIG.as_ref(),
+            WHIRLPOOL_TICK_SPACING.to_le_bytes().as_ref(),
+        ],
+        &whirlpool_cpi::id(),
+    )
+}
+
+/// Tick array of the whirlpool holding the tick index
+pub fn whirlpool_tick_array_address(whirlpool: &Pubkey, tick_index: i32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            whirlpool_cpi::state::TICK_ARRAY_SEED.as_bytes(),
+            whirlpool.as_ref(),
+            tick_array_start_index(tick_index).to_string().as_bytes(),
+        ],
+        &whirlpool_cpi::id(),
+    )
+}
+
+pub fn whirlpool_position_address(position_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            whirlpool_cpi::state::POSITION_SEED.as_bytes(),
+            position_mint.as_ref(),
+        ],
+        &whirlpool_cpi::id(),
+    )
+}
+
+/// Whirlpool vault of the dex launch, created at a dex pda
+pub fn clmm_vault_address(dex_state: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            DEX_CLMM_VAULT_SEED.as_bytes(),
+            dex_state.as_ref(),
+            mint.as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+/// Whirlpool position mint of the dex launch, created at a dex pda
+pub fn clmm_position_mint_address(dex_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DEX_CLMM_POSITION_MINT_SEED.as_bytes(), dex_state.as_ref()],
+        &crate::id(),
+    )
+}
+
+/// Token_0 and token_1 mints of a dex, token_0 has the smaller key
+pub fn sort_mints<'a>(mint_a: &'a Pubkey, mint_b: &'a Pubkey) -> (&'a Pubkey, &'a Pubkey) {
+    if mint_a < mint_b {
+        (mint_a, mint_b)
+    } else {
+        (mint_b, mint_a)
+    }
+}
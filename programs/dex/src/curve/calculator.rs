@@ -1,6 +1,9 @@
 //! Swap calculations
 
-use crate::curve::{constant_product::ConstantProductCurve, fees::Fees};
+use crate::curve::{
+    constant_product::ConstantProductCurve, exponential_price::ExponentialPriceCurve, fees::Fees,
+    linear_price::LinearPriceCurve,
+};
 use anchor_lang::prelude::*;
 use {crate::error::ErrorCode, std::fmt::Debug};
 
@@ -54,6 +57,69 @@ impl TradeDirection {
     }
 }
 
+/// Curve types supported by the dex program
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CurveType {
+    /// Uniswap-style constant product curve, invariant = token_a_amount * token_b_amount
+    ConstantProduct,
+    /// Price of the supply token grows linearly with the amount sold
+    LinearPrice,
+    /// Price of the supply token grows exponentially with the amount sold
+    ExponentialPrice,
+}
+
+impl TryFrom<u8> for CurveType {
+    type Error = Error;
+
+    fn try_from(curve_type: u8) -> Result<Self> {
+        match curve_type {
+            0 => Ok(CurveType::ConstantProduct),
+            1 => Ok(CurveType::LinearPrice),
+            2 => Ok(CurveType::ExponentialPrice),
+            _ => err!(ErrorCode::InvalidCurveType),
+        }
+    }
+}
+
+impl From<CurveType> for u8 {
+    fn from(val: CurveType) -> Self {
+        val as u8
+    }
+}
+
+/// Trait for packing of trait objects, required because structs that implement
+/// `Curve` need to be stored in `CurveCalculator`.
+pub trait Curve: Debug {
+    /// Calculate how much destination token will be provided given an amount
+    /// of source token, without fees.
+    fn swap_base_input(
+        &self,
+        source_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> Option<u128>;
+
+    /// Calculate how much source token is required to get an amount of
+    /// destination token, without fees.
+    fn swap_base_output(
+        &self,
+        destination_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> Option<u128>;
+
+    /// Calculate the value of the pool given the vault amounts. A swap must
+    /// never decrease it.
+    fn normalized_value(
+        &self,
+        swap_token_0_amount: u128,
+        swap_token_1_amount: u128,
+    ) -> Option<u128>;
+}
+
 /// Encodes results of depositing both sides at once
 #[derive(Debug, PartialEq)]
 pub struct TradingTokenResult {
@@ -76,15 +142,56 @@ pub struct SwapResult {
     pub destination_amount_swapped: u128,
     /// Amount of source tokens going to protocol
     pub protocol_fee: u128,
+    /// Amount of source tokens going to pool creator, part of protocol fee
+    pub creator_fee: u128,
+    /// Amount of source tokens going to referrer, part of protocol fee
+    pub referral_fee: u128,
     pub constant_before: u128,
     pub constant_after: u128,
 }
 
 /// Concrete struct to wrap around the trait object which performs calculation.
-#[derive(Clone, Debug, Default, PartialEq)]
-pub struct CurveCalculator {}
+#[derive(Debug)]
+pub struct CurveCalculator {
+    /// The type of curve contained in the calculator, helpful for outside
+    /// queries
+    pub curve_type: CurveType,
+    /// The actual calculator, represented as a trait object to allow for many
+    /// different types of curves
+    pub curve: Box<dyn Curve>,
+}
 
 impl CurveCalculator {
+    /// Build the calculator for a curve type.
+    ///
+    /// Non constant product curves are anchored to the vault amounts at dex
+    /// initialization and the vault reserve bound.
+    pub fn new(
+        curve_type: CurveType,
+        vault_for_reserve_bound: bool,
+        initial_supply: u64,
+        initial_reserve: u64,
+        vault_reserve_bound: u64,
+    ) -> Self {
+        let curve: Box<dyn Curve> = match curve_type {
+            CurveType::ConstantProduct => Box::new(ConstantProductCurve),
+            CurveType::LinearPrice => Box::new(LinearPriceCurve::new(
+                vault_for_reserve_bound,
+                initial_supply,
+                initial_reserve,
+                vault_reserve_bound,
+            )),
+            CurveType::ExponentialPrice => Box::new(ExponentialPriceCurve::new(
+                vault_for_reserve_bound,
+                initial_supply,
+                initial_reserve,
+                vault_reserve_bound,
+            )),
+        };
+
+        Self { curve_type, curve }
+    }
+
     pub fn validate_supply(token_0_amount: u64, token_1_amount: u64) -> Result<()> {
         if token_0_amount == 0 || token_1_amount == 0 {
             return Err(ErrorCode::EmptySupply.into());
@@ -95,21 +202,29 @@ impl CurveCalculator {
 
     /// Subtract fees and calculate how much destination token will be provided
     /// given an amount of source token.
+    #[allow(clippy::too_many_arguments)]
     pub fn swap_base_input(
+        &self,
         source_amount: u128,
         swap_source_amount: u128,
         swap_destination_amount: u128,
         protocol_fee_rate: u64,
+        creator_fee_rate: u64,
+        referral_fee_rate: u64,
+        trade_direction: TradeDirection,
     ) -> Option<SwapResult> {
         // debit the fee to calculate the amount swapped
         let protocol_fee = Fees::protocol_fee(source_amount, protocol_fee_rate)?;
         let source_amount_less_fees = source_amount.checked_sub(protocol_fee)?;
+        let creator_fee = Fees::creator_fee(protocol_fee, creator_fee_rate)?;
+        let referral_fee = Fees::referral_fee(protocol_fee, referral_fee_rate)?;
 
-        let destination_amount_swapped = ConstantProductCurve::swap_base_input_without_fees(
+        let destination_amount_swapped = self.curve.swap_base_input(
             source_amount_less_fees,
             swap_source_amount,
             swap_destination_amount,
-        );
+            trade_direction,
+        )?;
 
         let new_swap_source_amount = swap_source_amount.checked_add(source_amount)?;
         let new_swap_destination_amount =
@@ -121,30 +236,46 @@ impl CurveCalculator {
             source_amount_swapped: source_amount,
             destination_amount_swapped,
             protocol_fee,
-            constant_before: swap_source_amount.checked_mul(swap_destination_amount)?,
-            constant_after: new_swap_source_amount
-                .checked_sub(protocol_fee)?
-                .checked_mul(new_swap_destination_amount)?,
+            creator_fee,
+            referral_fee,
+            constant_before: self.normalized_value(
+                swap_source_amount,
+                swap_destination_amount,
+                trade_direction,
+            )?,
+            constant_after: self.normalized_value(
+                new_swap_source_amount.checked_sub(protocol_fee)?,
+                new_swap_destination_amount,
+                trade_direction,
+            )?,
         };
 
         Some(result)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn swap_base_output(
+        &self,
         destination_amount: u128,
         swap_source_amount: u128,
         swap_destination_amount: u128,
         protocol_fee_rate: u64,
+        creator_fee_rate: u64,
+        referral_fee_rate: u64,
+        trade_direction: TradeDirection,
     ) -> Option<SwapResult> {
-        let source_amount_swapped = ConstantProductCurve::swap_base_output_without_fees(
+        let source_amount_swapped = self.curve.swap_base_output(
             destination_amount,
             swap_source_amount,
             swap_destination_amount,
-        );
+            trade_direction,
+        )?;
 
         let source_amount =
             Fees::calculate_pre_fee_amount(source_amount_swapped, protocol_fee_rate).unwrap();
         let protocol_fee = Fees::protocol_fee(source_amount, protocol_fee_rate)?;
+        let creator_fee = Fees::creator_fee(protocol_fee, creator_fee_rate)?;
+        let referral_fee = Fees::referral_fee(protocol_fee, referral_fee_rate)?;
 
         let new_swap_source_amount = swap_source_amount.checked_add(source_amount)?;
         let new_swap_destination_amount =
@@ -156,14 +287,39 @@ impl CurveCalculator {
             source_amount_swapped: source_amount,
             destination_amount_swapped: destination_amount,
             protocol_fee,
-            constant_before: swap_source_amount.checked_mul(swap_destination_amount)?,
-            constant_after: new_swap_source_amount
-                .checked_sub(protocol_fee)?
-                .checked_mul(new_swap_destination_amount)?,
+            creator_fee,
+            referral_fee,
+            constant_before: self.normalized_value(
+                swap_source_amount,
+                swap_destination_amount,
+                trade_direction,
+            )?,
+            constant_after: self.normalized_value(
+                new_swap_source_amount.checked_sub(protocol_fee)?,
+                new_swap_destination_amount,
+                trade_direction,
+            )?,
         };
 
         Some(result)
     }
+
+    /// Curve value of the source and destination vault amounts
+    fn normalized_value(
+        &self,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> Option<u128> {
+        match trade_direction {
+            TradeDirection::ZeroForOne => self
+                .curve
+                .normalized_value(swap_source_amount, swap_destination_amount),
+            TradeDirection::OneForZero => self
+                .curve
+                .normalized_value(swap_destination_amount, swap_source_amount),
+        }
+    }
 }
 
 /// Test helpers for curves
@@ -201,23 +357,27 @@ pub mod test {
     /// This test guarantees that the relative change in value will be at most
     /// 1 normalized token, and that the value will never decrease from a trade.
     pub fn check_curve_value_from_swap(
+        curve: &dyn Curve,
         source_token_amount: u128,
         swap_source_amount: u128,
         swap_destination_amount: u128,
         trade_direction: TradeDirection,
     ) {
-        let destination_amount_swapped = ConstantProductCurve::swap_base_input_without_fees(
-            source_token_amount,
-            swap_source_amount,
-            swap_destination_amount,
-        );
+        let destination_amount_swapped = curve
+            .swap_base_input(
+                source_token_amount,
+                swap_source_amount,
+                swap_destination_amount,
+                trade_direction,
+            )
+            .unwrap();
 
         let (swap_token_0_amount, swap_token_1_amount) = match trade_direction {
             TradeDirection::ZeroForOne => (swap_source_amount, swap_destination_amount),
             TradeDirection::OneForZero => (swap_destination_amount, swap_source_amount),
         };
-        let previous_value = swap_token_0_amount
-            .checked_mul(swap_token_1_amount)
+        let previous_value = curve
+            .normalized_value(swap_token_0_amount, swap_token_1_amount)
             .unwrap();
 
         let new_swap_source_amount = swap_source_amount.checked_add(source_token_amount).unwrap();
@@ -229,8 +389,53 @@ pub mod test {
             TradeDirection::OneForZero => (new_swap_destination_amount, new_swap_source_amount),
         };
 
-        let new_value = swap_token_0_amount
-            .checked_mul(swap_token_1_amount)
+        let new_value = curve
+            .normalized_value(swap_token_0_amount, swap_token_1_amount)
+            .unwrap();
+        assert!(new_value >= previous_value);
+    }
+
+    /// Test function checking that a base output swap never reduces the
+    /// overall value of the pool.
+    pub fn check_curve_value_from_swap_base_output(
+        curve: &dyn Curve,
+        destination_token_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) {
+        let source_amount_swapped = match curve.swap_base_output(
+            destination_token_amount,
+            swap_source_amount,
+            swap_destination_amount,
+            trade_direction,
+        ) {
+            Some(source_amount_swapped) => source_amount_swapped,
+            // the curve can not provide that much destination token
+            None => return,
+        };
+
+        let (swap_token_0_amount, swap_token_1_amount) = match trade_direction {
+            TradeDirection::ZeroForOne => (swap_source_amount, swap_destination_amount),
+            TradeDirection::OneForZero => (swap_destination_amount, swap_source_amount),
+        };
+        let previous_value = curve
+            .normalized_value(swap_token_0_amount, swap_token_1_amount)
+            .unwrap();
+
+        let new_swap_source_amount = swap_source_amount
+            .checked_add(source_amount_swapped)
+            .unwrap();
+        let new_swap_destination_amount = swap_destination_amount
+            .checked_sub(destination_token_amount)
+            .unwrap();
+        let (swap_token_0_amount, swap_token_1_amount) = match trade_direction {
+            TradeDirection::ZeroForOne => (new_swap_source_amount, new_swap_destination_amount),
+            TradeDirection::OneForZero => (new_swap_destination_amount, new_swap_source_amount),
+        };
+
+        let new_value = curve
+            .normalized_value(swap_token_0_amount, swap_token_1_amount)
             .unwrap();
         assert!(new_value >= previous_value);
     }
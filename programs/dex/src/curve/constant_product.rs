@@ -1,6 +1,9 @@
 //! The Uniswap invariantConstantProductCurve::
 
-use crate::utils::CheckedCeilDiv;
+use crate::{
+    curve::calculator::{Curve, TradeDirection},
+    utils::CheckedCeilDiv,
+};
 
 /// ConstantProductCurve struct implementing CurveCalculator
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -41,11 +44,56 @@ impl ConstantProductCurve {
     }
 }
 
+impl Curve for ConstantProductCurve {
+    fn swap_base_input(
+        &self,
+        source_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        _trade_direction: TradeDirection,
+    ) -> Option<u128> {
+        Some(Self::swap_base_input_without_fees(
+            source_amount,
+            swap_source_amount,
+            swap_destination_amount,
+        ))
+    }
+
+    fn swap_base_output(
+        &self,
+        destination_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        _trade_direction: TradeDirection,
+    ) -> Option<u128> {
+        if destination_amount >= swap_destination_amount {
+            return None;
+        }
+        Some(Self::swap_base_output_without_fees(
+            destination_amount,
+            swap_source_amount,
+            swap_destination_amount,
+        ))
+    }
+
+    /// The constant product implementation is the Uniswap invariant
+    fn normalized_value(
+        &self,
+        swap_token_0_amount: u128,
+        swap_token_1_amount: u128,
+    ) -> Option<u128> {
+        swap_token_0_amount.checked_mul(swap_token_1_amount)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {
         super::*,
-        crate::curve::calculator::{test::check_curve_value_from_swap, TradeDirection},
+        crate::curve::calculator::test::{
+            check_curve_value_from_swap, check_curve_value_from_swap_base_output,
+            total_and_intermediate,
+        },
         proptest::prelude::*,
     };
 
@@ -122,6 +170,7 @@ mod tests {
             swap_destination_amount in 1..u64::MAX,
         ) {
             check_curve_value_from_swap(
+                &ConstantProductCurve,
                 source_token_amount as u128,
                 swap_source_amount as u128,
                 swap_destination_amount as u128,
@@ -129,4 +178,20 @@ mod tests {
             );
         }
     }
+
+    proptest! {
+        #[test]
+        fn curve_value_does_not_decrease_from_swap_base_output(
+            (swap_destination_amount, destination_token_amount) in total_and_intermediate(u64::MAX),
+            swap_source_amount in 1..u64::MAX,
+        ) {
+            check_curve_value_from_swap_base_output(
+                &ConstantProductCurve,
+                destination_token_amount as u128,
+                swap_source_amount as u128,
+                swap_destination_amount as u128,
+                TradeDirection::OneForZero
+            );
+        }
+    }
 }
@@ -0,0 +1,239 @@
+//! Supply token price growing exponentially with the amount sold
+
+use crate::{
+    curve::supply::{PriceFunction, SupplyCurve},
+    utils::U256,
+};
+
+/// Times the price doubles until the vault reserve bound is reached.
+/// The curve can only price up to 63 doublings, so the amount sold is bounded
+/// by `63 / EXPONENTIAL_PRICE_DOUBLINGS` times the amount sold at the bound.
+pub const EXPONENTIAL_PRICE_DOUBLINGS: u128 = 5;
+
+const ONE: u128 = 1 << 64;
+/// ln(2) in Q64.64
+const LN_2: u128 = 12786308645202655659;
+
+/// ExponentialPrice struct implementing PriceFunction
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExponentialPrice;
+
+pub type ExponentialPriceCurve = SupplyCurve<ExponentialPrice>;
+
+/// 2^x for x in Q64.64, rounded down
+fn exp2_x64(x: u128) -> Option<u128> {
+    let integer = x >> 64;
+    if integer >= 63 {
+        return None;
+    }
+    // 2^frac = e^(frac * ln2), taylor series converges fast for frac * ln2 < 1
+    let y = (x & (ONE - 1)).checked_mul(LN_2)? >> 64;
+    let mut term = ONE;
+    let mut sum = ONE;
+    for i in 1..=20 {
+        term = term.checked_mul(y)? >> 64;
+        term /= i;
+        if term == 0 {
+            break;
+        }
+        sum = sum.checked_add(term)?;
+    }
+    sum.checked_shl(integer as u32)
+}
+
+/// log2(x) for x >= 1 in Q64.64, rounded down
+fn log2_x64(x: u128) -> Option<u128> {
+    if x < ONE {
+        return None;
+    }
+    let integer = 127 - x.leading_zeros() - 64;
+    // mantissa in [1, 2) as Q1.63
+    let mut mantissa = (x >> integer) >> 1;
+    let mut result = u128::from(integer) << 64;
+    for bit in (0..64).rev() {
+        mantissa = mantissa.checked_mul(mantissa)? >> 63;
+        if mantissa >= 1 << 64 {
+            mantissa >>= 1;
+            result |= 1 << bit;
+        }
+    }
+    Some(result)
+}
+
+impl PriceFunction for ExponentialPrice {
+    /// price(s) = p0 * 2^(d * s / S)
+    /// with p0 chosen so that reserve(S) = R:
+    /// reserve(s) = R * (2^(d * s / S) - 1) / (2^d - 1)
+    fn reserve_for_sold(
+        &self,
+        sold: u128,
+        target_sold: u128,
+        target_reserve: u128,
+    ) -> Option<u128> {
+        let exponent = U256::from(EXPONENTIAL_PRICE_DOUBLINGS)
+            .checked_mul(U256::from(sold))?
+            .checked_mul(U256::from(ONE))?
+            .checked_div(U256::from(target_sold))?;
+        let growth = exp2_x64(u128::try_from(exponent).ok()?)?.checked_sub(ONE)?;
+
+        let numerator = U256::from(target_reserve).checked_mul(U256::from(growth))?;
+        let denominator =
+            U256::from((1 << EXPONENTIAL_PRICE_DOUBLINGS) - 1).checked_mul(U256::from(ONE))?;
+
+        u128::try_from(numerator.checked_div(denominator)?).ok()
+    }
+
+    /// s = S * log2(1 + (2^d - 1) * r / R) / d
+    fn sold_for_reserve(
+        &self,
+        reserve: u128,
+        target_sold: u128,
+        target_reserve: u128,
+    ) -> Option<u128> {
+        let growth = U256::from((1 << EXPONENTIAL_PRICE_DOUBLINGS) - 1)
+            .checked_mul(U256::from(reserve))?
+            .checked_mul(U256::from(ONE))?
+            .checked_div(U256::from(target_reserve))?
+            .checked_add(U256::from(ONE))?;
+        let growth = u128::try_from(growth).unwrap_or(u128::MAX);
+
+        let sold = U256::from(log2_x64(growth)?)
+            .checked_mul(U256::from(target_sold))?
+            .checked_div(U256::from(EXPONENTIAL_PRICE_DOUBLINGS))?
+            >> 64;
+
+        u128::try_from(sold).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::curve::calculator::{
+            test::{check_curve_value_from_swap, check_curve_value_from_swap_base_output},
+            Curve, TradeDirection,
+        },
+        proptest::prelude::*,
+    };
+
+    const INITIAL_SUPPLY: u64 = 800_000_000 * 10u64.pow(9);
+    const INITIAL_RESERVE: u64 = 2 * 10u64.pow(9);
+    const VAULT_RESERVE_BOUND: u64 = 205_000_000 * 10u64.pow(9);
+    const TARGET_RESERVE: u128 = (VAULT_RESERVE_BOUND - INITIAL_RESERVE) as u128;
+    const TARGET_SOLD: u128 = INITIAL_SUPPLY as u128 * TARGET_RESERVE / VAULT_RESERVE_BOUND as u128;
+
+    fn curve() -> ExponentialPriceCurve {
+        ExponentialPriceCurve::new(true, INITIAL_SUPPLY, INITIAL_RESERVE, VAULT_RESERVE_BOUND)
+    }
+
+    /// Reserve vault amount once the curve sold `sold`, the dex stops at the vault reserve bound
+    fn swap_reserve_amount(sold: u128) -> u128 {
+        u128::from(INITIAL_RESERVE)
+            + ExponentialPrice
+                .reserve_for_sold(sold, TARGET_SOLD, TARGET_RESERVE)
+                .unwrap()
+    }
+
+    #[test]
+    fn exp2_and_log2() {
+        assert_eq!(exp2_x64(0), Some(ONE));
+        assert_eq!(exp2_x64(5 * ONE), Some(32 * ONE));
+        assert_eq!(exp2_x64(63 * ONE), None);
+        let sqrt_2 = exp2_x64(ONE / 2).unwrap();
+        assert!(sqrt_2.abs_diff(26087635650665564424) < 1 << 10);
+
+        assert_eq!(log2_x64(ONE), Some(0));
+        assert_eq!(log2_x64(32 * ONE), Some(5 * ONE));
+        assert_eq!(log2_x64(ONE - 1), None);
+        let half = log2_x64(sqrt_2).unwrap();
+        assert!(half.abs_diff(ONE / 2) < 1 << 34);
+    }
+
+    #[test]
+    fn exponential_price_reaches_reserve_bound_with_constant_product() {
+        let target_reserve = u128::from(VAULT_RESERVE_BOUND - INITIAL_RESERVE);
+        let target_sold =
+            u128::from(INITIAL_SUPPLY) * target_reserve / u128::from(VAULT_RESERVE_BOUND);
+
+        assert_eq!(
+            ExponentialPrice.reserve_for_sold(0, target_sold, target_reserve),
+            Some(0)
+        );
+        assert_eq!(
+            ExponentialPrice.reserve_for_sold(target_sold, target_sold, target_reserve),
+            Some(target_reserve)
+        );
+
+        let bought = curve()
+            .swap_base_input(
+                target_reserve,
+                u128::from(INITIAL_RESERVE),
+                u128::from(INITIAL_SUPPLY),
+                TradeDirection::OneForZero,
+            )
+            .unwrap();
+        assert!(bought >= target_sold);
+        assert!(bought - target_sold <= 1 << EXPONENTIAL_PRICE_DOUBLINGS);
+    }
+
+    proptest! {
+        #[test]
+        fn curve_value_does_not_decrease_from_swap(
+            source_token_amount in 1..u64::MAX,
+            sold in 0..=TARGET_SOLD,
+            buy in proptest::bool::ANY,
+        ) {
+            let curve = curve();
+            let swap_supply_amount = u128::from(INITIAL_SUPPLY) - sold;
+            let swap_reserve_amount = swap_reserve_amount(sold);
+            let (swap_source_amount, swap_destination_amount, trade_direction) = if buy {
+                (swap_reserve_amount, swap_supply_amount, TradeDirection::OneForZero)
+            } else {
+                (swap_supply_amount, swap_reserve_amount, TradeDirection::ZeroForOne)
+            };
+            if curve
+                .swap_base_input(
+                    source_token_amount as u128,
+                    swap_source_amount,
+                    swap_destination_amount,
+                    trade_direction,
+                )
+                .is_some()
+            {
+                check_curve_value_from_swap(
+                    &curve,
+                    source_token_amount as u128,
+                    swap_source_amount,
+                    swap_destination_amount,
+                    trade_direction,
+                );
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn curve_value_does_not_decrease_from_swap_base_output(
+            destination_token_amount in 1..u64::MAX,
+            sold in 0..=TARGET_SOLD,
+            buy in proptest::bool::ANY,
+        ) {
+            let curve = curve();
+            let swap_supply_amount = u128::from(INITIAL_SUPPLY) - sold;
+            let swap_reserve_amount = swap_reserve_amount(sold);
+            let (swap_source_amount, swap_destination_amount, trade_direction) = if buy {
+                (swap_reserve_amount, swap_supply_amount, TradeDirection::OneForZero)
+            } else {
+                (swap_supply_amount, swap_reserve_amount, TradeDirection::ZeroForOne)
+            };
+            check_curve_value_from_swap_base_output(
+                &curve,
+                destination_token_amount as u128,
+                swap_source_amount,
+                swap_destination_amount,
+                trade_direction,
+            );
+        }
+    }
+}
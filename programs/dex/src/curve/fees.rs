@@ -22,6 +22,24 @@ impl Fees {
         )
     }
 
+    /// Calculate the pool creator share of the protocol fee
+    pub fn creator_fee(protocol_fee: u128, creator_fee_rate: u64) -> Option<u128> {
+        floor_div(
+            protocol_fee,
+            u128::from(creator_fee_rate),
+            u128::from(FEE_RATE_DENOMINATOR_VALUE),
+        )
+    }
+
+    /// Calculate the referrer share of the protocol fee
+    pub fn referral_fee(protocol_fee: u128, referral_fee_rate: u64) -> Option<u128> {
+        floor_div(
+            protocol_fee,
+            u128::from(referral_fee_rate),
+            u128::from(FEE_RATE_DENOMINATOR_VALUE),
+        )
+    }
+
     pub fn calculate_pre_fee_amount(post_fee_amount: u128, trade_fee_rate: u64) -> Option<u128> {
         if trade_fee_rate == 0 {
             Some(post_fee_amount)
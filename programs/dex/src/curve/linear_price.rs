@@ -0,0 +1,236 @@
+//! Supply token price growing linearly with the amount sold
+
+use crate::{
+    curve::supply::{PriceFunction, SupplyCurve},
+    utils::U256,
+};
+
+/// Price when the vault reserve bound is reached relative to the starting price
+pub const LINEAR_PRICE_GROWTH: u128 = 5;
+
+/// LinearPrice struct implementing PriceFunction
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinearPrice;
+
+pub type LinearPriceCurve = SupplyCurve<LinearPrice>;
+
+impl PriceFunction for LinearPrice {
+    /// price(s) = p0 * (1 + (g - 1) * s / S)
+    /// reserve(s) = p0 * (s + (g - 1) * s^2 / (2 * S))
+    /// with p0 chosen so that reserve(S) = R:
+    /// reserve(s) = R * (2 * S * s + (g - 1) * s^2) / ((g + 1) * S^2)
+    fn reserve_for_sold(
+        &self,
+        sold: u128,
+        target_sold: u128,
+        target_reserve: u128,
+    ) -> Option<u128> {
+        let sold = U256::from(sold);
+        let target_sold = U256::from(target_sold);
+
+        let numerator = U256::from(target_reserve).checked_mul(
+            U256::from(2)
+                .checked_mul(target_sold)?
+                .checked_mul(sold)?
+                .checked_add(
+                    U256::from(LINEAR_PRICE_GROWTH - 1)
+                        .checked_mul(sold)?
+                        .checked_mul(sold)?,
+                )?,
+        )?;
+        let denominator = U256::from(LINEAR_PRICE_GROWTH + 1)
+            .checked_mul(target_sold)?
+            .checked_mul(target_sold)?;
+
+        u128::try_from(numerator.checked_div(denominator)?).ok()
+    }
+
+    /// Positive root of the reserve polynomial:
+    /// s = S * (sqrt(1 + (g^2 - 1) * r / R) - 1) / (g - 1)
+    fn sold_for_reserve(
+        &self,
+        reserve: u128,
+        target_sold: u128,
+        target_reserve: u128,
+    ) -> Option<u128> {
+        let target_sold = U256::from(target_sold);
+        let target_reserve = U256::from(target_reserve);
+
+        // S * sqrt((R + (g^2 - 1) * r) / R) = sqrt(S^2 * (R + (g^2 - 1) * r) * R) / R
+        let radicand = target_sold
+            .checked_mul(target_sold)?
+            .checked_mul(
+                target_reserve.checked_add(
+                    U256::from(LINEAR_PRICE_GROWTH * LINEAR_PRICE_GROWTH - 1)
+                        .checked_mul(U256::from(reserve))?,
+                )?,
+            )?
+            .checked_mul(target_reserve)?;
+        let root = radicand.integer_sqrt().checked_div(target_reserve)?;
+
+        u128::try_from(
+            root.checked_sub(target_sold)?
+                .checked_div(U256::from(LINEAR_PRICE_GROWTH - 1))?,
+        )
+        .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::curve::calculator::{
+            test::{check_curve_value_from_swap, check_curve_value_from_swap_base_output},
+            Curve, TradeDirection,
+        },
+        proptest::prelude::*,
+    };
+
+    const INITIAL_SUPPLY: u64 = 800_000_000 * 10u64.pow(9);
+    const INITIAL_RESERVE: u64 = 2 * 10u64.pow(9);
+    const VAULT_RESERVE_BOUND: u64 = 205_000_000 * 10u64.pow(9);
+    const TARGET_RESERVE: u128 = (VAULT_RESERVE_BOUND - INITIAL_RESERVE) as u128;
+    const TARGET_SOLD: u128 = INITIAL_SUPPLY as u128 * TARGET_RESERVE / VAULT_RESERVE_BOUND as u128;
+
+    fn curve() -> LinearPriceCurve {
+        LinearPriceCurve::new(true, INITIAL_SUPPLY, INITIAL_RESERVE, VAULT_RESERVE_BOUND)
+    }
+
+    /// Reserve vault amount once the curve sold `sold`, the dex stops at the vault reserve bound
+    fn swap_reserve_amount(sold: u128) -> u128 {
+        u128::from(INITIAL_RESERVE)
+            + LinearPrice
+                .reserve_for_sold(sold, TARGET_SOLD, TARGET_RESERVE)
+                .unwrap()
+    }
+
+    #[test]
+    fn linear_price_reaches_reserve_bound_with_constant_product() {
+        let curve = curve();
+        let target_reserve = u128::from(VAULT_RESERVE_BOUND - INITIAL_RESERVE);
+        let target_sold =
+            u128::from(INITIAL_SUPPLY) * target_reserve / u128::from(VAULT_RESERVE_BOUND);
+
+        assert_eq!(
+            LinearPrice.reserve_for_sold(0, target_sold, target_reserve),
+            Some(0)
+        );
+        assert_eq!(
+            LinearPrice.reserve_for_sold(target_sold, target_sold, target_reserve),
+            Some(target_reserve)
+        );
+
+        // buy everything up to the bound in one swap, the last price is
+        // below one so a few extra units may fit into the rounding
+        let bought = curve
+            .swap_base_input(
+                target_reserve,
+                u128::from(INITIAL_RESERVE),
+                u128::from(INITIAL_SUPPLY),
+                TradeDirection::OneForZero,
+            )
+            .unwrap();
+        assert!(bought >= target_sold);
+        assert!(bought - target_sold <= LINEAR_PRICE_GROWTH);
+    }
+
+    #[test]
+    fn linear_price_sold_for_reserve_is_close() {
+        let target_reserve = u128::from(VAULT_RESERVE_BOUND - INITIAL_RESERVE);
+        let target_sold =
+            u128::from(INITIAL_SUPPLY) * target_reserve / u128::from(VAULT_RESERVE_BOUND);
+
+        for sold in [1, 1_000, 10u128.pow(12), target_sold / 3, target_sold] {
+            let reserve = LinearPrice
+                .reserve_for_sold(sold, target_sold, target_reserve)
+                .unwrap();
+            let estimate = LinearPrice
+                .sold_for_reserve(reserve, target_sold, target_reserve)
+                .unwrap();
+            assert!(estimate.abs_diff(sold) <= 2 * 10u128.pow(9));
+        }
+    }
+
+    #[test]
+    fn linear_price_round_trip_does_not_profit() {
+        let curve = curve();
+        let bought = curve
+            .swap_base_input(
+                10u128.pow(12),
+                u128::from(INITIAL_RESERVE),
+                u128::from(INITIAL_SUPPLY),
+                TradeDirection::OneForZero,
+            )
+            .unwrap();
+        let sold = curve
+            .swap_base_input(
+                bought,
+                u128::from(INITIAL_SUPPLY) - bought,
+                u128::from(INITIAL_RESERVE) + 10u128.pow(12),
+                TradeDirection::ZeroForOne,
+            )
+            .unwrap();
+        assert!(sold <= 10u128.pow(12));
+    }
+
+    proptest! {
+        #[test]
+        fn curve_value_does_not_decrease_from_swap(
+            source_token_amount in 1..u64::MAX,
+            sold in 0..=TARGET_SOLD,
+            buy in proptest::bool::ANY,
+        ) {
+            let curve = curve();
+            let swap_supply_amount = u128::from(INITIAL_SUPPLY) - sold;
+            let swap_reserve_amount = swap_reserve_amount(sold);
+            let (swap_source_amount, swap_destination_amount, trade_direction) = if buy {
+                (swap_reserve_amount, swap_supply_amount, TradeDirection::OneForZero)
+            } else {
+                (swap_supply_amount, swap_reserve_amount, TradeDirection::ZeroForOne)
+            };
+            if curve
+                .swap_base_input(
+                    source_token_amount as u128,
+                    swap_source_amount,
+                    swap_destination_amount,
+                    trade_direction,
+                )
+                .is_some()
+            {
+                check_curve_value_from_swap(
+                    &curve,
+                    source_token_amount as u128,
+                    swap_source_amount,
+                    swap_destination_amount,
+                    trade_direction,
+                );
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn curve_value_does_not_decrease_from_swap_base_output(
+            destination_token_amount in 1..u64::MAX,
+            sold in 0..=TARGET_SOLD,
+            buy in proptest::bool::ANY,
+        ) {
+            let curve = curve();
+            let swap_supply_amount = u128::from(INITIAL_SUPPLY) - sold;
+            let swap_reserve_amount = swap_reserve_amount(sold);
+            let (swap_source_amount, swap_destination_amount, trade_direction) = if buy {
+                (swap_reserve_amount, swap_supply_amount, TradeDirection::OneForZero)
+            } else {
+                (swap_supply_amount, swap_reserve_amount, TradeDirection::ZeroForOne)
+            };
+            check_curve_value_from_swap_base_output(
+                &curve,
+                destination_token_amount as u128,
+                swap_source_amount,
+                swap_destination_amount,
+                trade_direction,
+            );
+        }
+    }
+}
@@ -2,8 +2,14 @@
 
 pub mod calculator;
 pub mod constant_product;
+pub mod exponential_price;
 pub mod fees;
+pub mod linear_price;
+pub mod supply;
 
 pub use calculator::*;
 pub use constant_product::*;
+pub use exponential_price::*;
 pub use fees::*;
+pub use linear_price::*;
+pub use supply::*;
@@ -0,0 +1,292 @@
+//! Curves pricing the supply token by the amount sold from the dex
+
+use crate::curve::calculator::{Curve, TradeDirection};
+use std::fmt::Debug;
+
+/// Evaluations of `reserve_for_sold` allowed to refine the closed form
+/// `sold_for_reserve` estimate in a swap, bounds the swap compute units.
+/// The estimates are off by a few tokens, leaving room to spare.
+pub const MAX_SOLD_SEARCH_STEPS: u32 = 24;
+
+/// Shape of the supply token price, as a function of the amount sold.
+///
+/// Implementations are normalized so that `target_reserve` reserve tokens
+/// are collected once `target_sold` supply tokens are sold.
+pub trait PriceFunction: Debug + Default {
+    /// Reserve tokens collected after selling `sold` supply tokens, rounded
+    /// down. Must be non decreasing and zero for nothing sold.
+    fn reserve_for_sold(&self, sold: u128, target_sold: u128, target_reserve: u128)
+        -> Option<u128>;
+
+    /// Closed form estimate of the supply tokens sold once `reserve` reserve
+    /// tokens are collected. Only used as a starting point, the result is always
+    /// checked against `reserve_for_sold` and must be close enough to be refined
+    /// within `MAX_SOLD_SEARCH_STEPS` evaluations.
+    fn sold_for_reserve(
+        &self,
+        reserve: u128,
+        target_sold: u128,
+        target_reserve: u128,
+    ) -> Option<u128>;
+}
+
+/// Curve anchored to the dex vault amounts at initialization.
+///
+/// The supply vault starts with `initial_supply` tokens and the reserve vault
+/// with `initial_reserve` tokens. The curve reaches `vault_reserve_bound` at the
+/// same amount sold as the constant product curve would, only the price path
+/// between both points differs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SupplyCurve<P: PriceFunction> {
+    pub price: P,
+    /// if false the reserve is token_0 , true for token_1
+    pub vault_for_reserve_bound: bool,
+    pub initial_supply: u128,
+    pub initial_reserve: u128,
+    pub vault_reserve_bound: u128,
+}
+
+impl<P: PriceFunction> SupplyCurve<P> {
+    pub fn new(
+        vault_for_reserve_bound: bool,
+        initial_supply: u64,
+        initial_reserve: u64,
+        vault_reserve_bound: u64,
+    ) -> Self {
+        Self {
+            price: P::default(),
+            vault_for_reserve_bound,
+            initial_supply: u128::from(initial_supply),
+            initial_reserve: u128::from(initial_reserve),
+            vault_reserve_bound: u128::from(vault_reserve_bound),
+        }
+    }
+
+    /// Reserve tokens collected when the vault reserve bound is reached
+    fn target_reserve(&self) -> Option<u128> {
+        self.vault_reserve_bound
+            .checked_sub(self.initial_reserve)
+            .filter(|amount| *amount > 0)
+    }
+
+    /// Supply tokens sold when the vault reserve bound is reached.
+    /// For constant product the supply vault holds
+    /// `initial_supply * initial_reserve / vault_reserve_bound` at that point.
+    fn target_sold(&self) -> Option<u128> {
+        self.initial_supply
+            .checked_mul(self.target_reserve()?)?
+            .checked_div(self.vault_reserve_bound)
+            .filter(|amount| *amount > 0)
+    }
+
+    fn sold(&self, swap_supply_amount: u128) -> u128 {
+        self.initial_supply.saturating_sub(swap_supply_amount)
+    }
+
+    fn reserve_for_sold(&self, sold: u128) -> Option<u128> {
+        self.price
+            .reserve_for_sold(sold, self.target_sold()?, self.target_reserve()?)
+    }
+
+    /// Largest amount sold in `[0, max_sold]` collecting at most `reserve`,
+    /// none when the estimate can not be refined within `MAX_SOLD_SEARCH_STEPS`
+    fn max_sold_for_reserve(&self, reserve: u128, max_sold: u128) -> Option<u128> {
+        let estimate = self
+            .price
+            .sold_for_reserve(reserve, self.target_sold()?, self.target_reserve()?)
+            .unwrap_or(max_sold)
+            .min(max_sold);
+
+        let mut steps = 0;
+        let mut collects_at_most_reserve = |sold: u128| -> Option<bool> {
+            steps += 1;
+            if steps > MAX_SOLD_SEARCH_STEPS {
+                return None;
+            }
+            Some(self.reserve_for_sold(sold)? <= reserve)
+        };
+
+        // bracket the result, `low` always collects at most `reserve`
+        // and `high` is the first known amount collecting more
+        let (mut low, mut high);
+        let mut step = 1u128;
+        if collects_at_most_reserve(estimate)? {
+            low = estimate;
+            loop {
+                let next = low.saturating_add(step);
+                if next > max_sold {
+                    high = max_sold.checked_add(1)?;
+                    break;
+                }
+                if collects_at_most_reserve(next)? {
+                    low = next;
+                    step = step.checked_mul(2)?;
+                } else {
+                    high = next;
+                    break;
+                }
+            }
+        } else {
+            high = estimate;
+            loop {
+                if step >= high {
+                    // nothing sold collects nothing
+                    low = 0;
+                    break;
+                }
+                let next = high - step;
+                if collects_at_most_reserve(next)? {
+                    low = next;
+                    break;
+                }
+                high = next;
+                step = step.checked_mul(2)?;
+            }
+        }
+
+        while high - low > 1 {
+            let middle = low + (high - low) / 2;
+            if collects_at_most_reserve(middle)? {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        Some(low)
+    }
+
+    /// Is the source token of the trade the reserve token
+    fn is_reserve_source(&self, trade_direction: TradeDirection) -> bool {
+        match trade_direction {
+            TradeDirection::ZeroForOne => !self.vault_for_reserve_bound,
+            TradeDirection::OneForZero => self.vault_for_reserve_bound,
+        }
+    }
+}
+
+impl<P: PriceFunction> Curve for SupplyCurve<P> {
+    fn swap_base_input(
+        &self,
+        source_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> Option<u128> {
+        if self.is_reserve_source(trade_direction) {
+            // buy supply tokens, never empty the supply vault
+            let sold_before = self.sold(swap_destination_amount);
+            let reserve_after = self
+                .reserve_for_sold(sold_before)?
+                .checked_add(source_amount)?;
+            let max_sold = self
+                .initial_supply
+                .min(sold_before.checked_add(swap_destination_amount)?)
+                .checked_sub(1)?;
+            let sold_after = self.max_sold_for_reserve(reserve_after, max_sold)?;
+            sold_after.checked_sub(sold_before)
+        } else {
+            // sell supply tokens back to the curve
+            let sold_before = self.sold(swap_source_amount);
+            let sold_after = sold_before.saturating_sub(source_amount);
+            self.reserve_for_sold(sold_before)?
+                .checked_sub(self.reserve_for_sold(sold_after)?)
+        }
+    }
+
+    fn swap_base_output(
+        &self,
+        destination_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        trade_direction: TradeDirection,
+    ) -> Option<u128> {
+        if destination_amount >= swap_destination_amount {
+            return None;
+        }
+
+        if self.is_reserve_source(trade_direction) {
+            // buy supply tokens
+            let sold_before = self.sold(swap_destination_amount);
+            let sold_after = sold_before.checked_add(destination_amount)?;
+            if sold_after > self.initial_supply {
+                return None;
+            }
+            self.reserve_for_sold(sold_after)?
+                .checked_sub(self.reserve_for_sold(sold_before)?)
+        } else {
+            // sell supply tokens back to the curve
+            let sold_before = self.sold(swap_source_amount);
+            let reserve_after = self
+                .reserve_for_sold(sold_before)?
+                .checked_sub(destination_amount)?;
+            let sold_after = self.max_sold_for_reserve(reserve_after, sold_before)?;
+            sold_before.checked_sub(sold_after)
+        }
+    }
+
+    /// Reserve vault amount not backed by the curve, shifted by the reserve
+    /// collected when the whole initial supply is sold to stay positive.
+    fn normalized_value(
+        &self,
+        swap_token_0_amount: u128,
+        swap_token_1_amount: u128,
+    ) -> Option<u128> {
+        let (swap_reserve_amount, swap_supply_amount) = if self.vault_for_reserve_bound {
+            (swap_token_1_amount, swap_token_0_amount)
+        } else {
+            (swap_token_0_amount, swap_token_1_amount)
+        };
+
+        swap_reserve_amount
+            .checked_add(self.reserve_for_sold(self.initial_supply)?)?
+            .checked_sub(self.reserve_for_sold(self.sold(swap_supply_amount))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::curve::{ExponentialPriceCurve, LinearPriceCurve},
+        proptest::prelude::*,
+    };
+
+    const INITIAL_SUPPLY: u64 = 800_000_000 * 10u64.pow(9);
+    const INITIAL_RESERVE: u64 = 2 * 10u64.pow(9);
+    const VAULT_RESERVE_BOUND: u64 = 205_000_000 * 10u64.pow(9);
+    const TARGET_RESERVE: u128 = (VAULT_RESERVE_BOUND - INITIAL_RESERVE) as u128;
+
+    /// The refined estimate is the exact inverse of `reserve_for_sold`
+    fn check_max_sold_for_reserve<P: PriceFunction>(curve: &SupplyCurve<P>, reserve: u128) {
+        let max_sold = curve.initial_supply - 1;
+        let sold = curve.max_sold_for_reserve(reserve, max_sold).unwrap();
+        assert!(curve.reserve_for_sold(sold).unwrap() <= reserve);
+        assert!(sold == max_sold || curve.reserve_for_sold(sold + 1).unwrap() > reserve);
+    }
+
+    fn curves() -> (LinearPriceCurve, ExponentialPriceCurve) {
+        (
+            LinearPriceCurve::new(true, INITIAL_SUPPLY, INITIAL_RESERVE, VAULT_RESERVE_BOUND),
+            ExponentialPriceCurve::new(true, INITIAL_SUPPLY, INITIAL_RESERVE, VAULT_RESERVE_BOUND),
+        )
+    }
+
+    #[test]
+    fn max_sold_for_reserve_bounds() {
+        let (linear, exponential) = curves();
+        for reserve in [0, 1, 1_000, TARGET_RESERVE - 1, TARGET_RESERVE] {
+            check_max_sold_for_reserve(&linear, reserve);
+            check_max_sold_for_reserve(&exponential, reserve);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn max_sold_for_reserve_within_search_steps(reserve in 0..=TARGET_RESERVE) {
+            let (linear, exponential) = curves();
+            check_max_sold_for_reserve(&linear, reserve);
+            check_max_sold_for_reserve(&exponential, reserve);
+        }
+    }
+}
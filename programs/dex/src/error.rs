@@ -36,4 +36,46 @@ pub enum ErrorCode {
     DexReadyToLaunch,
     #[msg("Dex not ready to launch")]
     DexNotReadyToLaunch,
+    #[msg("Invalid curve type")]
+    InvalidCurveType,
+    #[msg("Not enough real liquidity in vault")]
+    InsufficientLiquidity,
+    #[msg("Invalid config")]
+    InvalidConfig,
+    #[msg("Config disabled")]
+    ConfigDisabled,
+    #[msg("Invalid pool creator")]
+    InvalidPoolCreator,
+    #[msg("Invalid referrer")]
+    InvalidReferrer,
+    #[msg("Exceeds swap input limit of the anti-sniper window")]
+    ExceededSwapInputLimit,
+    #[msg("Exceeds wallet input limit of the anti-sniper window")]
+    ExceededWalletInputLimit,
+    #[msg("Dex refunding")]
+    DexRefunding,
+    #[msg("Dex not refunding")]
+    DexNotRefunding,
+    #[msg("Dex has no launch lamports")]
+    NoLaunchLamports,
+    #[msg("Invalid mint")]
+    InvalidMint,
+    #[msg("Invalid launch target")]
+    InvalidLaunchTarget,
+    #[msg("Invalid lp mint")]
+    InvalidLpMint,
+    #[msg("Invalid fee recipients")]
+    InvalidFeeRecipients,
+    #[msg("Invalid swap route")]
+    InvalidRoute,
+    #[msg("No native mint in the swap")]
+    InvalidNativeMint,
+    #[msg("Invalid limit order")]
+    InvalidLimitOrder,
+    #[msg("Invalid fee rate")]
+    InvalidFeeRate,
+    #[msg("Wallet limit account required during the anti-sniper window")]
+    MissingWalletLimit,
+    #[msg("Invalid anti-sniper window limits")]
+    InvalidSniperProtection,
 }
@@ -1,31 +1,71 @@
 use super::*;
 
-use crate::curve::MAX_FEE_RATE_VALUE;
+use crate::curve::{CurveType, FEE_RATE_DENOMINATOR_VALUE, MAX_FEE_RATE_VALUE};
 use crate::error::ErrorCode;
 use crate::states::*;
 
-pub fn initialize_config(ctx: Context<InitializeConfigState>) -> Result<()> {
+pub fn initialize_config(ctx: Context<InitializeConfigState>, index: u16) -> Result<()> {
     let config_id = ctx.accounts.config.key();
     let config = &mut ctx.accounts.config;
     config.bump = ctx.bumps.config;
+    config.index = index;
+    config.disabled = false;
     config.swap_fee_rate = 10_000;
     config.launch_fee_rate = 10_000;
     config.initial_reserve = 2 * 10u64.pow(9);
     config.vault_reserve_bound = 205_000_000 * 10u64.pow(9);
+    config.curve_type = CurveType::ConstantProduct.into();
+    config.virtual_reserve = 0;
+    config.virtual_supply = 0;
+    config.creator_fee_rate = 0;
+    config.referral_fee_rate = 0;
+    config.sniper_window = 0;
+    config.sniper_max_swap_input = 0;
+    config.sniper_max_wallet_input = 0;
+    config.launch_window = 0;
+    config.launch_lamports = 0;
+    config.keeper_reward = 0;
+    config.launch_target = LaunchTarget::RaydiumCpSwap.into();
+    config.lp_burn_rate = FEE_RATE_DENOMINATOR_VALUE;
+    config.lp_lock_cliff = 0;
+    config.lp_vesting_period = 0;
+    config.sniper_fee_rate = 0;
+    config.sniper_fee_period = 0;
+    config.sniper_fee_steps = 0;
 
     emit!(InitializeConfigEvent {
         admin_id: ctx.accounts.admin.key(),
         config_id,
+        index,
         swap_fee_rate: config.swap_fee_rate,
         launch_fee_rate: config.launch_fee_rate,
         initial_reserve: config.initial_reserve,
         vault_reserve_bound: config.vault_reserve_bound,
+        curve_type: config.curve_type,
+        virtual_reserve: config.virtual_reserve,
+        virtual_supply: config.virtual_supply,
+        creator_fee_rate: config.creator_fee_rate,
+        referral_fee_rate: config.referral_fee_rate,
+        sniper_window: config.sniper_window,
+        sniper_max_swap_input: config.sniper_max_swap_input,
+        sniper_max_wallet_input: config.sniper_max_wallet_input,
+        launch_window: config.launch_window,
+        launch_lamports: config.launch_lamports,
+        keeper_reward: config.keeper_reward,
+        launch_target: config.launch_target,
+        lp_burn_rate: config.lp_burn_rate,
+        lp_lock_cliff: config.lp_lock_cliff,
+        lp_vesting_period: config.lp_vesting_period,
+        sniper_fee_rate: config.sniper_fee_rate,
+        sniper_fee_period: config.sniper_fee_period,
+        sniper_fee_steps: config.sniper_fee_steps,
     });
 
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(index: u16)]
 pub struct InitializeConfigState<'info> {
     #[account(mut, address = authority_manager.admin @ ErrorCode::InvalidAdmin)]
     pub admin: Signer<'info>,
@@ -39,7 +79,8 @@ pub struct InitializeConfigState<'info> {
     #[account(
         init,
         seeds = [
-            DEX_CONFIG_SEED.as_bytes()
+            DEX_CONFIG_SEED.as_bytes(),
+            &ConfigState::index_seed(index)
         ],
         bump,
         payer = admin,
@@ -61,6 +102,7 @@ pub fn update_config_swap_fee_rate(
 
     emit!(UpdateConfigSwapFeeRateEvent {
         admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
         old_swap_fee_rate,
         new_swap_fee_rate: swap_fee_rate,
     });
@@ -80,6 +122,7 @@ pub fn update_config_launch_fee_rate(
 
     emit!(UpdateConfigLaunchFeeRateEvent {
         admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
         old_launch_fee_rate,
         new_launch_fee_rate: launch_fee_rate,
     });
@@ -97,6 +140,7 @@ pub fn update_config_vault_reserve_bound(
 
     emit!(UpdateConfigVaultReserveBoundEvent {
         admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
         old_vault_reserve_bound,
         new_vault_reserve_bound: vault_reserve_bound,
     });
@@ -114,6 +158,7 @@ pub fn update_config_initial_reserve(
 
     emit!(UpdateConfigInitialReserveEvent {
         admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
         old_initial_reserve,
         new_initial_reserve: initial_reserve,
     });
@@ -121,13 +166,294 @@ pub fn update_config_initial_reserve(
     Ok(())
 }
 
+pub fn update_config_curve_type(ctx: Context<UpdateConfigState>, curve_type: u8) -> Result<()> {
+    CurveType::try_from(curve_type)?;
+
+    let config = &mut ctx.accounts.config;
+    let old_curve_type = config.curve_type;
+    config.curve_type = curve_type;
+
+    emit!(UpdateConfigCurveTypeEvent {
+        admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
+        old_curve_type,
+        new_curve_type: curve_type,
+    });
+
+    Ok(())
+}
+
+pub fn update_config_virtual_reserve(
+    ctx: Context<UpdateConfigState>,
+    virtual_reserve: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_virtual_reserve = config.virtual_reserve;
+    config.virtual_reserve = virtual_reserve;
+
+    emit!(UpdateConfigVirtualReserveEvent {
+        admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
+        old_virtual_reserve,
+        new_virtual_reserve: virtual_reserve,
+    });
+
+    Ok(())
+}
+
+pub fn update_config_virtual_supply(
+    ctx: Context<UpdateConfigState>,
+    virtual_supply: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_virtual_supply = config.virtual_supply;
+    config.virtual_supply = virtual_supply;
+
+    emit!(UpdateConfigVirtualSupplyEvent {
+        admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
+        old_virtual_supply,
+        new_virtual_supply: virtual_supply,
+    });
+
+    Ok(())
+}
+
+pub fn update_config_creator_fee_rate(
+    ctx: Context<UpdateConfigState>,
+    creator_fee_rate: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require_gte!(
+        FEE_RATE_DENOMINATOR_VALUE,
+        creator_fee_rate
+            .checked_add(config.referral_fee_rate)
+            .ok_or(ErrorCode::Overflow)?,
+        ErrorCode::InvalidFeeRate
+    );
+
+    let old_creator_fee_rate = config.creator_fee_rate;
+    config.creator_fee_rate = creator_fee_rate;
+
+    emit!(UpdateConfigCreatorFeeRateEvent {
+        admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
+        old_creator_fee_rate,
+        new_creator_fee_rate: creator_fee_rate,
+    });
+
+    Ok(())
+}
+
+pub fn update_config_referral_fee_rate(
+    ctx: Context<UpdateConfigState>,
+    referral_fee_rate: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require_gte!(
+        FEE_RATE_DENOMINATOR_VALUE,
+        config
+            .creator_fee_rate
+            .checked_add(referral_fee_rate)
+            .ok_or(ErrorCode::Overflow)?,
+        ErrorCode::InvalidFeeRate
+    );
+
+    let old_referral_fee_rate = config.referral_fee_rate;
+    config.referral_fee_rate = referral_fee_rate;
+
+    emit!(UpdateConfigReferralFeeRateEvent {
+        admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
+        old_referral_fee_rate,
+        new_referral_fee_rate: referral_fee_rate,
+    });
+
+    Ok(())
+}
+
+pub fn update_config_disabled(ctx: Context<UpdateConfigState>, disabled: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_disabled = config.disabled;
+    config.disabled = disabled;
+
+    emit!(UpdateConfigDisabledEvent {
+        admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
+        old_disabled,
+        new_disabled: disabled,
+    });
+
+    Ok(())
+}
+
+pub fn update_config_sniper_protection(
+    ctx: Context<UpdateConfigState>,
+    sniper_window: u64,
+    sniper_max_swap_input: u64,
+    sniper_max_wallet_input: u64,
+) -> Result<()> {
+    // a zero swap input limit would block every buy of the window
+    require!(
+        sniper_window == 0
+            || (sniper_max_swap_input > 0 && sniper_max_swap_input <= sniper_max_wallet_input),
+        ErrorCode::InvalidSniperProtection
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_sniper_window = config.sniper_window;
+    let old_sniper_max_swap_input = config.sniper_max_swap_input;
+    let old_sniper_max_wallet_input = config.sniper_max_wallet_input;
+    config.sniper_window = sniper_window;
+    config.sniper_max_swap_input = sniper_max_swap_input;
+    config.sniper_max_wallet_input = sniper_max_wallet_input;
+
+    emit!(UpdateConfigSniperProtectionEvent {
+        admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
+        old_sniper_window,
+        new_sniper_window: sniper_window,
+        old_sniper_max_swap_input,
+        new_sniper_max_swap_input: sniper_max_swap_input,
+        old_sniper_max_wallet_input,
+        new_sniper_max_wallet_input: sniper_max_wallet_input,
+    });
+
+    Ok(())
+}
+
+pub fn update_config_launch_window(
+    ctx: Context<UpdateConfigState>,
+    launch_window: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_launch_window = config.launch_window;
+    config.launch_window = launch_window;
+
+    emit!(UpdateConfigLaunchWindowEvent {
+        admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
+        old_launch_window,
+        new_launch_window: launch_window,
+    });
+
+    Ok(())
+}
+
+pub fn update_config_launch_lamports(
+    ctx: Context<UpdateConfigState>,
+    launch_lamports: u64,
+    keeper_reward: u64,
+) -> Result<()> {
+    assert!(keeper_reward <= launch_lamports);
+
+    let config = &mut ctx.accounts.config;
+    let old_launch_lamports = config.launch_lamports;
+    let old_keeper_reward = config.keeper_reward;
+    config.launch_lamports = launch_lamports;
+    config.keeper_reward = keeper_reward;
+
+    emit!(UpdateConfigLaunchLamportsEvent {
+        admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
+        old_launch_lamports,
+        new_launch_lamports: launch_lamports,
+        old_keeper_reward,
+        new_keeper_reward: keeper_reward,
+    });
+
+    Ok(())
+}
+
+pub fn update_config_launch_target(
+    ctx: Context<UpdateConfigState>,
+    launch_target: u8,
+) -> Result<()> {
+    LaunchTarget::try_from(launch_target)?;
+
+    let config = &mut ctx.accounts.config;
+    let old_launch_target = config.launch_target;
+    config.launch_target = launch_target;
+
+    emit!(UpdateConfigLaunchTargetEvent {
+        admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
+        old_launch_target,
+        new_launch_target: launch_target,
+    });
+
+    Ok(())
+}
+
+pub fn update_config_lp_policy(
+    ctx: Context<UpdateConfigState>,
+    lp_burn_rate: u64,
+    lp_lock_cliff: u64,
+    lp_vesting_period: u64,
+) -> Result<()> {
+    assert!(lp_burn_rate <= FEE_RATE_DENOMINATOR_VALUE);
+
+    let config = &mut ctx.accounts.config;
+    let old_lp_burn_rate = config.lp_burn_rate;
+    let old_lp_lock_cliff = config.lp_lock_cliff;
+    let old_lp_vesting_period = config.lp_vesting_period;
+    config.lp_burn_rate = lp_burn_rate;
+    config.lp_lock_cliff = lp_lock_cliff;
+    config.lp_vesting_period = lp_vesting_period;
+
+    emit!(UpdateConfigLpPolicyEvent {
+        admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
+        old_lp_burn_rate,
+        new_lp_burn_rate: lp_burn_rate,
+        old_lp_lock_cliff,
+        new_lp_lock_cliff: lp_lock_cliff,
+        old_lp_vesting_period,
+        new_lp_vesting_period: lp_vesting_period,
+    });
+
+    Ok(())
+}
+
+pub fn update_config_sniper_fee(
+    ctx: Context<UpdateConfigState>,
+    sniper_fee_rate: u64,
+    sniper_fee_period: u64,
+    sniper_fee_steps: u64,
+) -> Result<()> {
+    assert!(sniper_fee_rate <= MAX_FEE_RATE_VALUE);
+    assert!(sniper_fee_rate == 0 || sniper_fee_period > 0);
+    assert!(sniper_fee_steps <= sniper_fee_period);
+
+    let config = &mut ctx.accounts.config;
+    let old_sniper_fee_rate = config.sniper_fee_rate;
+    let old_sniper_fee_period = config.sniper_fee_period;
+    let old_sniper_fee_steps = config.sniper_fee_steps;
+    config.sniper_fee_rate = sniper_fee_rate;
+    config.sniper_fee_period = sniper_fee_period;
+    config.sniper_fee_steps = sniper_fee_steps;
+
+    emit!(UpdateConfigSniperFeeEvent {
+        admin_id: ctx.accounts.admin.key(),
+        config_id: ctx.accounts.config.key(),
+        old_sniper_fee_rate,
+        new_sniper_fee_rate: sniper_fee_rate,
+        old_sniper_fee_period,
+        new_sniper_fee_period: sniper_fee_period,
+        old_sniper_fee_steps,
+        new_sniper_fee_steps: sniper_fee_steps,
+    });
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct UpdateConfigState<'info> {
     #[account(mut, address = authority_manager.admin @ ErrorCode::InvalidAdmin)]
     pub admin: Signer<'info>,
     #[account(
         mut,
-        seeds = [DEX_CONFIG_SEED.as_bytes(),],
+        seeds = [DEX_CONFIG_SEED.as_bytes(), &ConfigState::index_seed(config.index)],
         bump = config.bump
     )]
     pub config: Box<Account<'info, ConfigState>>,
@@ -1,5 +1,6 @@
 use crate::curve::CurveCalculator;
 use crate::curve::Fees;
+use crate::curve::FEE_RATE_DENOMINATOR_VALUE;
 use crate::error::ErrorCode;
 use crate::states::*;
 use crate::utils::*;
@@ -32,8 +33,8 @@ mod raydium {
     };
 }
 
-pub fn initialize_dex(
-    ctx: Context<InitializeDex>,
+pub fn initialize_dex<'info>(
+    ctx: Context<'_, '_, '_, 'info, InitializeDex<'info>>,
     init_amount: u64,
     vault_for_reserve_bound: bool,
 ) -> Result<()> {
@@ -43,6 +44,21 @@ pub fn initialize_dex(
         return err!(ErrorCode::NotSupportMint);
     }
 
+    // no launch target forwards transfer hook accounts, the dex could never graduate
+    if has_transfer_hook(&ctx.accounts.mint_zero.to_account_info())?
+        || has_transfer_hook(&ctx.accounts.mint_one.to_account_info())?
+    {
+        return err!(ErrorCode::NotSupportMint);
+    }
+
+    // the whirlpool only takes SPL Token mints, the dex could never graduate
+    if LaunchTarget::try_from(ctx.accounts.config.launch_target)? == LaunchTarget::Clmm
+        && (*ctx.accounts.mint_zero.to_account_info().owner != Token::id()
+            || *ctx.accounts.mint_one.to_account_info().owner != Token::id())
+    {
+        return err!(ErrorCode::NotSupportMint);
+    }
+
     // due to stack/heap limitations, we have to create redundant new accounts ourselves.
     create_token_account(
         &ctx.accounts.authority.to_account_info(),
@@ -84,30 +100,49 @@ pub fn initialize_dex(
         &ctx.accounts.system_program.to_account_info(),
     )?;
     let dex_state = &mut dex_state_loader.load_init()?;
-    let (init_amount_zero, init_amount_one) = if vault_for_reserve_bound {
-        (init_amount, ctx.accounts.config.initial_reserve)
+
+    // with a virtual reserve the payer does not seed the reserve vault
+    let config = &ctx.accounts.config;
+    let (initial_reserve, virtual_reserve, virtual_supply) = if config.virtual_reserve > 0 {
+        (0, config.virtual_reserve, config.virtual_supply)
     } else {
-        (ctx.accounts.config.initial_reserve, init_amount)
+        (config.initial_reserve, 0, 0)
     };
+    let (init_amount_zero, init_amount_one, virtual_amount_zero, virtual_amount_one) =
+        if vault_for_reserve_bound {
+            (
+                init_amount,
+                initial_reserve,
+                virtual_supply,
+                virtual_reserve,
+            )
+        } else {
+            (
+                initial_reserve,
+                init_amount,
+                virtual_reserve,
+                virtual_supply,
+            )
+        };
 
     transfer_from_user_to_dex_vault(
         ctx.accounts.payer.to_account_info(),
         ctx.accounts.payer_vault_zero.to_account_info(),
         ctx.accounts.dex_vault_zero.to_account_info(),
-        ctx.accounts.mint_zero.to_account_info(),
+        &ctx.accounts.mint_zero,
         ctx.accounts.token_program_zero.to_account_info(),
         init_amount_zero,
-        ctx.accounts.mint_zero.decimals,
+        ctx.remaining_accounts,
     )?;
 
     transfer_from_user_to_dex_vault(
         ctx.accounts.payer.to_account_info(),
         ctx.accounts.payer_vault_one.to_account_info(),
         ctx.accounts.dex_vault_one.to_account_info(),
-        ctx.accounts.mint_one.to_account_info(),
+        &ctx.accounts.mint_one,
         ctx.accounts.token_program_one.to_account_info(),
         init_amount_one,
-        ctx.accounts.mint_one.decimals,
+        ctx.remaining_accounts,
     )?;
 
     let token_0_vault =
@@ -129,11 +164,27 @@ pub fn initialize_dex(
         )?
         .base;
 
-    CurveCalculator::validate_supply(token_0_vault.amount, token_1_vault.amount)?;
+    let token_0_amount = token_0_vault
+        .amount
+        .checked_add(virtual_amount_zero)
+        .ok_or(ErrorCode::Overflow)?;
+    let token_1_amount = token_1_vault
+        .amount
+        .checked_add(virtual_amount_one)
+        .ok_or(ErrorCode::Overflow)?;
+
+    CurveCalculator::validate_supply(token_0_amount, token_1_amount)?;
 
     let vault_reserve_bound = ctx.accounts.config.vault_reserve_bound;
+    let curve_type = ctx.accounts.config.curve_type;
+    let (initial_reserve, initial_supply) = if vault_for_reserve_bound {
+        (token_1_amount, token_0_amount)
+    } else {
+        (token_0_amount, token_1_amount)
+    };
 
     dex_state.initialize(
+        ctx.accounts.config.key(),
         ctx.accounts.payer.key(),
         ctx.accounts.dex_vault_zero.key(),
         ctx.accounts.dex_vault_one.key(),
@@ -141,10 +192,64 @@ pub fn initialize_dex(
         &ctx.accounts.mint_one,
         vault_for_reserve_bound,
         vault_reserve_bound,
+        curve_type,
+        initial_reserve,
+        initial_supply,
+        virtual_amount_zero,
+        virtual_amount_one,
     );
 
+    if config.sniper_window > 0 {
+        dex_state.sniper_window_end = u64::try_from(Clock::get()?.unix_timestamp)
+            .unwrap()
+            .checked_add(config.sniper_window)
+            .ok_or(ErrorCode::Overflow)?;
+        dex_state.sniper_max_swap_input = config.sniper_max_swap_input;
+        dex_state.sniper_max_wallet_input = config.sniper_max_wallet_input;
+    }
+
+    if config.sniper_fee_rate > 0 {
+        dex_state.sniper_fee_rate = config.sniper_fee_rate;
+        dex_state.sniper_fee_start = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+        dex_state.sniper_fee_period = config.sniper_fee_period;
+        dex_state.sniper_fee_steps = config.sniper_fee_steps;
+    }
+
+    if config.launch_window > 0 {
+        dex_state.launch_deadline = u64::try_from(Clock::get()?.unix_timestamp)
+            .unwrap()
+            .checked_add(config.launch_window)
+            .ok_or(ErrorCode::Overflow)?;
+    }
+
+    // the seed reserve goes back to the pool creator, holders are refunded what the curve collected
+    dex_state.seed_reserve = if vault_for_reserve_bound {
+        token_1_vault.amount
+    } else {
+        token_0_vault.amount
+    };
+
+    dex_state.launch_target = config.launch_target;
+
+    if config.launch_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(ctx.accounts.payer.key, &dex_id, config.launch_lamports),
+            &[
+                ctx.accounts.payer.to_account_info(),
+                ctx.accounts.dex_state.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+        dex_state.launch_lamports = config.launch_lamports;
+        dex_state.keeper_reward = config.keeper_reward;
+    }
+
+    let observation_state = &mut ctx.accounts.observation_state.load_init()?;
+    observation_state.initialize(dex_id);
+
     emit!(InitializeDexEvent {
         dex_id,
+        config_id: ctx.accounts.config.key(),
         payer_id: ctx.accounts.payer.key(),
         mint_zero: ctx.accounts.mint_zero.key(),
         mint_one: ctx.accounts.mint_one.key(),
@@ -152,6 +257,14 @@ pub fn initialize_dex(
         token_one_amount: token_1_vault.amount,
         reserve_bound: vault_reserve_bound,
         vault_for_reserve_bound,
+        curve_type,
+        virtual_token_zero_amount: virtual_amount_zero,
+        virtual_token_one_amount: virtual_amount_one,
+        sniper_window_end: dex_state.sniper_window_end,
+        sniper_fee_rate: dex_state.sniper_fee_rate,
+        launch_deadline: dex_state.launch_deadline,
+        launch_lamports: dex_state.launch_lamports,
+        launch_target: dex_state.launch_target,
     });
 
     Ok(())
@@ -211,6 +324,11 @@ pub struct InitializeDex<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     /// Which config the dex belongs to.
+    #[account(
+        seeds = [DEX_CONFIG_SEED.as_bytes(), &ConfigState::index_seed(config.index)],
+        bump = config.bump,
+        constraint = !config.disabled @ ErrorCode::ConfigDisabled,
+    )]
     pub config: Box<Account<'info, ConfigState>>,
     #[account(
         seeds = [DEX_AUTHORITY_MANAGER_SEED.as_bytes()],
@@ -275,6 +393,18 @@ pub struct InitializeDex<'info> {
         bump,
     )]
     pub dex_vault_one: UncheckedAccount<'info>,
+    /// Price observations of the dex, updated on every swap
+    #[account(
+        init,
+        seeds = [
+            DEX_OBSERVATION_SEED.as_bytes(),
+            dex_state.key().as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = ObservationState::LEN
+    )]
+    pub observation_state: AccountLoader<'info, ObservationState>,
     /// Program to create mint account and mint tokens
     pub token_program: Program<'info, Token>,
     /// Spl token program or token program 2022
@@ -289,7 +419,9 @@ pub struct InitializeDex<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn withdraw_dex_fee(ctx: Context<WithdrawDexFee>) -> Result<()> {
+pub fn withdraw_dex_fee<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawDexFee<'info>>,
+) -> Result<()> {
     let dex_id = ctx.accounts.dex_state.key();
     let mut dex_state = ctx.accounts.dex_state.load_mut()?;
     let mut amount_0 = dex_state.swap_fees_token_0;
@@ -316,6 +448,195 @@ pub fn withdraw_dex_fee(ctx: Context<WithdrawDexFee>) -> Result<()> {
         dex_state.launch_fees_token_1 = 0;
     }
 
+    let fee_distribution = if *ctx.accounts.fee_distribution.owner == system_program::ID {
+        // until the fee recipients are set, the owner of the first token account receives all
+        let recipient_account = ctx
+            .remaining_accounts
+            .first()
+            .ok_or(ErrorCode::InvalidFeeRecipients)?;
+        let recipient_token_account =
+            TokenAccount::try_deserialize(&mut &recipient_account.data.borrow()[..])?;
+        FeeDistributionState::single_recipient(recipient_token_account.owner)
+    } else {
+        // only the program can assign its distribution pda
+        FeeDistributionState::try_deserialize(
+            &mut &ctx.accounts.fee_distribution.data.borrow()[..],
+        )?
+    };
+
+    // recipient token accounts come first, the transfer hook accounts after them
+    let recipients = fee_distribution.get_recipients();
+    if ctx.remaining_accounts.len() < 2 * recipients.len() {
+        return err!(ErrorCode::InvalidFeeRecipients);
+    }
+    let (recipient_accounts, hook_accounts) = ctx.remaining_accounts.split_at(2 * recipients.len());
+
+    let shares_0 = fee_distribution.split(amount_0);
+    let shares_1 = fee_distribution.split(amount_1);
+
+    let token_0_program =
+        if ctx.accounts.vault_0_mint.to_account_info().owner == ctx.accounts.token_program.key {
+            ctx.accounts.token_program.to_account_info()
+        } else {
+            ctx.accounts.token_program_2022.to_account_info()
+        };
+    let token_1_program =
+        if ctx.accounts.vault_1_mint.to_account_info().owner == ctx.accounts.token_program.key {
+            ctx.accounts.token_program.to_account_info()
+        } else {
+            ctx.accounts.token_program_2022.to_account_info()
+        };
+
+    // dex authority pda signer seeds
+    let seeds = [
+        DEX_AUTHORITY_SEED.as_bytes(),
+        &[ctx.accounts.authority_manager.authority_bump],
+    ];
+    let signer_seeds = &[seeds.as_slice()];
+
+    // each recipient passes its token_0 and token_1 accounts in the order of the fee distribution
+    for (index, recipient) in recipients.iter().enumerate() {
+        let recipient_token_0_account = load_fee_recipient_account(
+            &recipient_accounts[2 * index],
+            recipient,
+            &ctx.accounts.vault_0_mint.key(),
+        )?;
+        let recipient_token_1_account = load_fee_recipient_account(
+            &recipient_accounts[2 * index + 1],
+            recipient,
+            &ctx.accounts.vault_1_mint.key(),
+        )?;
+
+        transfer_from_dex_vault_to_user(
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.token_0_vault.to_account_info(),
+            recipient_token_0_account,
+            &ctx.accounts.vault_0_mint,
+            token_0_program.clone(),
+            shares_0[index],
+            signer_seeds,
+            hook_accounts,
+        )?;
+
+        transfer_from_dex_vault_to_user(
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.token_1_vault.to_account_info(),
+            recipient_token_1_account,
+            &ctx.accounts.vault_1_mint,
+            token_1_program.clone(),
+            shares_1[index],
+            signer_seeds,
+            hook_accounts,
+        )?;
+
+        emit!(DistributeDexFeeEvent {
+            dex_id,
+            recipient_id: recipient.wallet,
+            token_zero_amount: shares_0[index],
+            token_one_amount: shares_1[index],
+        });
+    }
+
+    emit!(WithdrawDexFeeEvent {
+        admin_id: ctx.accounts.admin.key(),
+        dex_id,
+        token_zero_amount: amount_0,
+        token_one_amount: amount_1
+    });
+
+    Ok(())
+}
+
+/// Token account of the mint owned by the fee recipient wallet
+fn load_fee_recipient_account<'info>(
+    account: &AccountInfo<'info>,
+    recipient: &FeeRecipient,
+    mint: &Pubkey,
+) -> Result<AccountInfo<'info>> {
+    let token_account = TokenAccount::try_deserialize(&mut &account.data.borrow()[..])?;
+    if token_account.owner != recipient.wallet || token_account.mint != *mint {
+        return err!(ErrorCode::InvalidFeeRecipients);
+    }
+
+    Ok(account.clone())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawDexFee<'info> {
+    /// Only admin can collect fee now
+    #[account(address = authority_manager.admin @ ErrorCode::InvalidAdmin)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [DEX_AUTHORITY_MANAGER_SEED.as_bytes()],
+        bump = authority_manager.bump
+    )]
+    pub authority_manager: Box<Account<'info, AuthorityManager>>,
+    /// CHECK: dex vault mint authority
+    #[account(
+        seeds = [
+            DEX_AUTHORITY_SEED.as_bytes(),
+        ],
+        bump = authority_manager.authority_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    /// Dex state stores accumulated protocol fee amount
+    #[account(mut)]
+    pub dex_state: AccountLoader<'info, DexState>,
+    /// The address that holds dex tokens for token_0
+    #[account(
+        mut,
+        constraint = token_0_vault.key() == dex_state.load()?.token_0_vault
+    )]
+    pub token_0_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The address that holds dex tokens for token_1
+    #[account(
+        mut,
+        constraint = token_1_vault.key() == dex_state.load()?.token_1_vault
+    )]
+    pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The mint of token_0 vault
+    #[account(
+        address = token_0_vault.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The mint of token_1 vault
+    #[account(
+        address = token_1_vault.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: Recipients of the collected protocol fees, their token accounts are remaining accounts.
+    /// A single recipient is passed while it is not initialized.
+    #[account(
+        seeds = [DEX_FEE_DISTRIBUTION_SEED.as_bytes()],
+        bump,
+    )]
+    pub fee_distribution: UncheckedAccount<'info>,
+    /// The SPL program to perform token transfers
+    pub token_program: Program<'info, Token>,
+    /// The SPL program 2022 to perform token transfers
+    pub token_program_2022: Program<'info, Token2022>,
+}
+
+pub fn withdraw_creator_fee<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawCreatorFee<'info>>,
+) -> Result<()> {
+    let dex_id = ctx.accounts.dex_state.key();
+    let mut dex_state = ctx.accounts.dex_state.load_mut()?;
+    let amount_0 = dex_state.creator_fees_token_0;
+    let amount_1 = dex_state.creator_fees_token_1;
+
+    dex_state.creator_fees_token_0 = 0;
+    dex_state.creator_fees_token_1 = 0;
+
+    // lamports above rent not reserved for the launch belong to the creator
+    let dex_state_info = ctx.accounts.dex_state.to_account_info();
+    let lamports = dex_state.get_creator_lamports(
+        dex_state_info.lamports(),
+        Rent::get()?.minimum_balance(dex_state_info.data_len()),
+    );
+    **dex_state_info.try_borrow_mut_lamports()? -= lamports;
+    **ctx.accounts.pool_creator.try_borrow_mut_lamports()? += lamports;
+
     // dex authority pda signer seeds
     let seeds = [
         DEX_AUTHORITY_SEED.as_bytes(),
@@ -327,47 +648,48 @@ pub fn withdraw_dex_fee(ctx: Context<WithdrawDexFee>) -> Result<()> {
         ctx.accounts.authority.to_account_info(),
         ctx.accounts.token_0_vault.to_account_info(),
         ctx.accounts.recipient_token_0_account.to_account_info(),
-        ctx.accounts.vault_0_mint.to_account_info(),
+        &ctx.accounts.vault_0_mint,
         if ctx.accounts.vault_0_mint.to_account_info().owner == ctx.accounts.token_program.key {
             ctx.accounts.token_program.to_account_info()
         } else {
             ctx.accounts.token_program_2022.to_account_info()
         },
         amount_0,
-        ctx.accounts.vault_0_mint.decimals,
         signer_seeds,
+        ctx.remaining_accounts,
     )?;
 
     transfer_from_dex_vault_to_user(
         ctx.accounts.authority.to_account_info(),
         ctx.accounts.token_1_vault.to_account_info(),
         ctx.accounts.recipient_token_1_account.to_account_info(),
-        ctx.accounts.vault_1_mint.to_account_info(),
+        &ctx.accounts.vault_1_mint,
         if ctx.accounts.vault_1_mint.to_account_info().owner == ctx.accounts.token_program.key {
             ctx.accounts.token_program.to_account_info()
         } else {
             ctx.accounts.token_program_2022.to_account_info()
         },
         amount_1,
-        ctx.accounts.vault_1_mint.decimals,
         signer_seeds,
+        ctx.remaining_accounts,
     )?;
 
-    emit!(WithdrawDexFeeEvent {
-        admin_id: ctx.accounts.admin.key(),
+    emit!(WithdrawCreatorFeeEvent {
+        creator_id: ctx.accounts.pool_creator.key(),
         dex_id,
         token_zero_amount: amount_0,
-        token_one_amount: amount_1
+        token_one_amount: amount_1,
+        lamports,
     });
 
     Ok(())
 }
 
 #[derive(Accounts)]
-pub struct WithdrawDexFee<'info> {
-    /// Only admin can collect fee now
-    #[account(address = authority_manager.admin @ ErrorCode::InvalidAdmin)]
-    pub admin: Signer<'info>,
+pub struct WithdrawCreatorFee<'info> {
+    /// Only pool creator can collect creator fee
+    #[account(mut, address = dex_state.load()?.pool_creator @ ErrorCode::InvalidPoolCreator)]
+    pub pool_creator: Signer<'info>,
     #[account(
         seeds = [DEX_AUTHORITY_MANAGER_SEED.as_bytes()],
         bump = authority_manager.bump
@@ -381,7 +703,7 @@ pub struct WithdrawDexFee<'info> {
         bump = authority_manager.authority_bump,
     )]
     pub authority: UncheckedAccount<'info>,
-    /// Dex state stores accumulated protocol fee amount
+    /// Dex state stores accumulated creator fee amount
     #[account(mut)]
     pub dex_state: AccountLoader<'info, DexState>,
     /// The address that holds dex tokens for token_0
@@ -406,10 +728,10 @@ pub struct WithdrawDexFee<'info> {
         address = token_1_vault.mint
     )]
     pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
-    /// The address that receives the collected token_0 protocol fees
+    /// The address that receives the collected token_0 creator fees
     #[account(mut)]
     pub recipient_token_0_account: Box<InterfaceAccount<'info, TokenAccount>>,
-    /// The address that receives the collected token_1 protocol fees
+    /// The address that receives the collected token_1 creator fees
     #[account(mut)]
     pub recipient_token_1_account: Box<InterfaceAccount<'info, TokenAccount>>,
     /// The SPL program to perform token transfers
@@ -418,60 +740,137 @@ pub struct WithdrawDexFee<'info> {
     pub token_program_2022: Program<'info, Token2022>,
 }
 
+pub fn claim_locked_lp(ctx: Context<ClaimLockedLp>) -> Result<()> {
+    let dex_id = ctx.accounts.dex_state.key();
+    let mut dex_state = ctx.accounts.dex_state.load_mut()?;
+    let amount = dex_state.get_claimable_lp(u64::try_from(Clock::get()?.unix_timestamp).unwrap());
+
+    dex_state.lp_claimed_amount = dex_state
+        .lp_claimed_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+
+    // dex authority pda signer seeds
+    let seeds = [
+        DEX_AUTHORITY_SEED.as_bytes(),
+        &[ctx.accounts.authority_manager.authority_bump],
+    ];
+    let signer_seeds = &[seeds.as_slice()];
+
+    transfer_from_dex_vault_to_user(
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.lp_vault.to_account_info(),
+        ctx.accounts.recipient_lp_token.to_account_info(),
+        &ctx.accounts.lp_mint,
+        ctx.accounts.token_program.to_account_info(),
+        amount,
+        signer_seeds,
+        &[],
+    )?;
+
+    emit!(ClaimLockedLpEvent {
+        creator_id: ctx.accounts.pool_creator.key(),
+        dex_id,
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimLockedLp<'info> {
+    /// Only pool creator can claim the locked LP tokens
+    #[account(address = dex_state.load()?.pool_creator @ ErrorCode::InvalidPoolCreator)]
+    pub pool_creator: Signer<'info>,
+    #[account(
+        seeds = [DEX_AUTHORITY_MANAGER_SEED.as_bytes()],
+        bump = authority_manager.bump
+    )]
+    pub authority_manager: Box<Account<'info, AuthorityManager>>,
+    /// CHECK: dex vault authority
+    #[account(
+        seeds = [
+            DEX_AUTHORITY_SEED.as_bytes(),
+        ],
+        bump = authority_manager.authority_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    /// Dex state stores the locked LP amount and its release schedule
+    #[account(mut)]
+    pub dex_state: AccountLoader<'info, DexState>,
+    /// LP mint of the launched pool
+    #[account(address = dex_state.load()?.lp_mint @ ErrorCode::InvalidLpMint)]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The dex authority LP account holding the locked LP tokens
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = authority,
+    )]
+    pub lp_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The address that receives the claimed LP tokens
+    #[account(
+        mut,
+        token::mint = lp_mint,
+    )]
+    pub recipient_lp_token: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The SPL program to perform token transfers
+    pub token_program: Program<'info, Token>,
+}
+
 pub fn launch_dex(ctx: Context<LaunchDex>, shared_lamports: u64) -> Result<()> {
+    fund_launch_by_admin(
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.dex_authority_manager.admin,
+        ctx.accounts.dex_authority.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        shared_lamports,
+    )?;
+
+    // unused launch lamports are released to the pool creator
+    launch_raydium(&ctx, ctx.accounts.payer.to_account_info(), 0)
+}
+
+pub fn crank_launch_dex(ctx: Context<LaunchDex>) -> Result<()> {
+    let keeper_reward = fund_launch_by_keeper(
+        &ctx.accounts.payer,
+        &ctx.accounts.dex_state,
+        &ctx.accounts.dex_authority,
+    )?;
+
+    // lamports left after the pool creation go back to the dex for the pool creator
+    launch_raydium(
+        &ctx,
+        ctx.accounts.dex_state.to_account_info(),
+        keeper_reward,
+    )
+}
+
+fn launch_raydium<'info>(
+    ctx: &Context<LaunchDex<'info>>,
+    lamports_recipient: AccountInfo<'info>,
+    keeper_reward: u64,
+) -> Result<()> {
     let dex_id = ctx.accounts.dex_state.key();
     let raydium_id = ctx.accounts.pool_state.key();
     let dex_state = &mut ctx.accounts.dex_state.load_mut()?;
 
-    if !dex_state.is_ready_to_launch {
-        return err!(ErrorCode::DexNotReadyToLaunch);
-    }
-
-    if dex_state.is_launched {
-        return err!(ErrorCode::DexLaunched);
-    }
-
-    let (taxed_amount_0, launch_fees_0, transfer_fee_0) = get_taxed_amount_before_launch(
-        ctx.accounts.creator_token_0.amount,
-        dex_state.swap_fees_token_0,
+    let launch_amounts = prepare_launch(
+        dex_state,
+        LaunchTarget::RaydiumCpSwap,
         ctx.accounts.dex_config.launch_fee_rate,
-        &ctx.accounts.token_0_mint.to_account_info(),
-    )?;
-    let (taxed_amount_1, launch_fees_1, transfer_fee_1) = get_taxed_amount_before_launch(
+        ctx.accounts.creator_token_0.amount,
         ctx.accounts.creator_token_1.amount,
-        dex_state.swap_fees_token_1,
-        ctx.accounts.dex_config.launch_fee_rate,
+        &ctx.accounts.token_0_mint.to_account_info(),
         &ctx.accounts.token_1_mint.to_account_info(),
     )?;
 
-    dex_state.launch_fees_token_0 = launch_fees_0;
-    dex_state.launch_fees_token_1 = launch_fees_1;
-
     #[cfg(feature = "enable-log")]
     msg!(
-        "launch_dex taxed_amount_0:{},launch_fees_0:{},transfer_fee_0:{},taxed_amount_1:{},launch_fees_1:{},transfer_fee_1:{},raydium_create_pool_fee:{}",
-        taxed_amount_0,
-        launch_fees_0,
-        transfer_fee_0,
-        taxed_amount_1,
-        launch_fees_1,
-        transfer_fee_1,
+        "launch_dex raydium_create_pool_fee:{}",
         ctx.accounts.amm_config.create_pool_fee,
     );
 
-    invoke(
-        &system_instruction::transfer(
-            ctx.accounts.payer.key,
-            &ctx.accounts.dex_authority.key(),
-            shared_lamports,
-        ),
-        &[
-            ctx.accounts.payer.to_account_info(),
-            ctx.accounts.dex_authority.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-    )?;
-
     let cpi_accounts = raydium_cp_swap::cpi::accounts::Initialize {
         creator: ctx.accounts.dex_authority.to_account_info(),
         amm_config: ctx.accounts.amm_config.to_account_info(),
@@ -508,24 +907,38 @@ pub fn launch_dex(ctx: Context<LaunchDex>, shared_lamports: u64) -> Result<()> {
         signer_seeds,
     );
 
-    raydium_cp_swap::cpi::initialize(cpi_context, taxed_amount_0, taxed_amount_1, 0)?;
+    raydium_cp_swap::cpi::initialize(
+        cpi_context,
+        launch_amounts.amount_0,
+        launch_amounts.amount_1,
+        0,
+    )?;
 
-    invoke_signed(
-        &system_instruction::transfer(
-            &ctx.accounts.dex_authority.key(),
-            ctx.accounts.payer.key,
-            ctx.accounts.dex_authority.lamports(),
-        ),
-        &[
-            ctx.accounts.dex_authority.to_account_info(),
-            ctx.accounts.payer.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
+    return_launch_lamports(
+        ctx.accounts.dex_authority.to_account_info(),
+        lamports_recipient,
+        ctx.accounts.system_program.to_account_info(),
         signer_seeds,
     )?;
 
-    let lp_amount_to_burn =
+    let lp_amount =
         spl_token::state::Account::unpack(&ctx.accounts.creator_lp_token.data.borrow())?.amount;
+    let lp_amount_to_burn = u64::try_from(
+        u128::from(lp_amount) * u128::from(ctx.accounts.dex_config.lp_burn_rate)
+            / u128::from(FEE_RATE_DENOMINATOR_VALUE),
+    )
+    .map_err(|_| ErrorCode::InvalidU64Cast)?;
+    let lp_amount_to_lock = lp_amount - lp_amount_to_burn;
+
+    // locked LP tokens stay in the dex authority LP account until the pool creator claims them
+    dex_state.lp_mint = ctx.accounts.lp_mint.key();
+    dex_state.lp_locked_amount = lp_amount_to_lock;
+    dex_state.lp_unlock_start = u64::try_from(Clock::get()?.unix_timestamp)
+        .unwrap()
+        .checked_add(ctx.accounts.dex_config.lp_lock_cliff)
+        .ok_or(ErrorCode::Overflow)?;
+    dex_state.lp_vesting_period = ctx.accounts.dex_config.lp_vesting_period;
+
     token_burn(
         ctx.accounts.dex_authority.to_account_info(),
         ctx.accounts.token_program.to_account_info(),
@@ -538,17 +951,162 @@ pub fn launch_dex(ctx: Context<LaunchDex>, shared_lamports: u64) -> Result<()> {
     emit!(DexLaunchedEvent {
         dex_id,
         raydium_id,
+        pool_id: raydium_id,
         admin_id: ctx.accounts.payer.key(),
-        amount_0: taxed_amount_0,
-        amount_1: taxed_amount_1,
+        amount_0: launch_amounts.amount_0,
+        amount_1: launch_amounts.amount_1,
+        launch_fees_0: launch_amounts.launch_fees_0,
+        launch_fees_1: launch_amounts.launch_fees_1,
+        transfer_fee_0: launch_amounts.transfer_fee_0,
+        transfer_fee_1: launch_amounts.transfer_fee_1,
+        lp_burned: lp_amount_to_burn,
+        lp_locked: lp_amount_to_lock,
+        keeper_reward,
+        launch_target: LaunchTarget::RaydiumCpSwap.into(),
+    });
+
+    dex_state.is_launched = true;
+    dex_state.launch_lamports = 0;
+
+    Ok(())
+}
+
+/// Real vault amounts migrated to the launch target after the launch fees
+pub struct LaunchAmounts {
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub launch_fees_0: u64,
+    pub launch_fees_1: u64,
+    pub transfer_fee_0: u64,
+    pub transfer_fee_1: u64,
+}
+
+/// Check the dex can launch to the target and book the launch fees
+pub(crate) fn prepare_launch(
+    dex_state: &mut DexState,
+    launch_target: LaunchTarget,
+    launch_fee_rate: u64,
+    vault_0_amount: u64,
+    vault_1_amount: u64,
+    token_0_mint: &AccountInfo,
+    token_1_mint: &AccountInfo,
+) -> Result<LaunchAmounts> {
+    if !dex_state.is_ready_to_launch {
+        return err!(ErrorCode::DexNotReadyToLaunch);
+    }
+
+    if dex_state.is_launched {
+        return err!(ErrorCode::DexLaunched);
+    }
+
+    if LaunchTarget::try_from(dex_state.launch_target)? != launch_target {
+        return err!(ErrorCode::InvalidLaunchTarget);
+    }
+
+    // only real vault balances are migrated, virtual amounts are dropped
+    let (amount_0, launch_fees_0, transfer_fee_0) = get_taxed_amount_before_launch(
+        vault_0_amount,
+        dex_state
+            .swap_fees_token_0
+            .checked_add(dex_state.creator_fees_token_0)
+            .ok_or(ErrorCode::Overflow)?,
+        launch_fee_rate,
+        token_0_mint,
+    )?;
+    let (amount_1, launch_fees_1, transfer_fee_1) = get_taxed_amount_before_launch(
+        vault_1_amount,
+        dex_state
+            .swap_fees_token_1
+            .checked_add(dex_state.creator_fees_token_1)
+            .ok_or(ErrorCode::Overflow)?,
+        launch_fee_rate,
+        token_1_mint,
+    )?;
+
+    dex_state.launch_fees_token_0 = launch_fees_0;
+    dex_state.launch_fees_token_1 = launch_fees_1;
+
+    #[cfg(feature = "enable-log")]
+    msg!(
+        "launch_dex taxed_amount_0:{},launch_fees_0:{},transfer_fee_0:{},taxed_amount_1:{},launch_fees_1:{},transfer_fee_1:{}",
+        amount_0,
+        launch_fees_0,
+        transfer_fee_0,
+        amount_1,
+        launch_fees_1,
+        transfer_fee_1,
+    );
+
+    Ok(LaunchAmounts {
+        amount_0,
+        amount_1,
         launch_fees_0,
         launch_fees_1,
         transfer_fee_0,
         transfer_fee_1,
-        lp_burned: lp_amount_to_burn,
-    });
+    })
+}
 
-    dex_state.is_launched = true;
+/// Admin sends the lamports paying for the pool creation to the dex authority
+pub(crate) fn fund_launch_by_admin<'info>(
+    payer: AccountInfo<'info>,
+    admin: Pubkey,
+    dex_authority: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    shared_lamports: u64,
+) -> Result<()> {
+    if payer.key() != admin {
+        return err!(ErrorCode::InvalidAdmin);
+    }
+
+    invoke(
+        &system_instruction::transfer(payer.key, dex_authority.key, shared_lamports),
+        &[payer, dex_authority, system_program],
+    )?;
+
+    Ok(())
+}
+
+/// Launch lamports of the dex pay for the pool creation and the keeper reward
+pub(crate) fn fund_launch_by_keeper<'info>(
+    payer: &AccountInfo<'info>,
+    dex_state: &AccountLoader<'info, DexState>,
+    dex_authority: &AccountInfo<'info>,
+) -> Result<u64> {
+    let (launch_lamports, (shared_lamports, keeper_reward)) = {
+        let dex_state = dex_state.load()?;
+        (
+            dex_state.launch_lamports,
+            dex_state
+                .split_launch_lamports()
+                .ok_or(ErrorCode::Underflow)?,
+        )
+    };
+
+    if launch_lamports == 0 {
+        return err!(ErrorCode::NoLaunchLamports);
+    }
+
+    // the dex account is owned by the program, its lamports are moved directly
+    **dex_state.to_account_info().try_borrow_mut_lamports()? -= launch_lamports;
+    **dex_authority.try_borrow_mut_lamports()? += shared_lamports;
+    **payer.try_borrow_mut_lamports()? += keeper_reward;
+
+    Ok(keeper_reward)
+}
+
+/// Send the lamports left after the pool creation out of the dex authority
+pub(crate) fn return_launch_lamports<'info>(
+    dex_authority: AccountInfo<'info>,
+    recipient: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_signed(
+        &system_instruction::transfer(dex_authority.key, recipient.key, dex_authority.lamports()),
+        &[dex_authority, recipient, system_program],
+        signer_seeds,
+    )?;
 
     Ok(())
 }
@@ -578,8 +1136,8 @@ fn get_taxed_amount_before_launch(
 
 #[derive(Accounts)]
 pub struct LaunchDex<'info> {
-    /// Address paying to create the pool. Can be anyone
-    #[account(mut, address = dex_authority_manager.admin @ ErrorCode::InvalidAdmin)]
+    /// Address paying to create the pool, the admin or anyone through the crank
+    #[account(mut)]
     pub payer: Signer<'info>,
     /// CHECK: dex vault authority
     #[account(
@@ -594,8 +1152,7 @@ pub struct LaunchDex<'info> {
     )]
     pub dex_authority_manager: Box<Account<'info, AuthorityManager>>,
     #[account(
-        seeds = [DEX_CONFIG_SEED.as_bytes()],
-        bump = dex_config.bump
+        address = dex_state.load()?.config @ ErrorCode::InvalidConfig,
     )]
     pub dex_config: Box<Account<'info, ConfigState>>,
     /// The program account of the dex in which the swap will be performed
@@ -628,12 +1185,14 @@ pub struct LaunchDex<'info> {
     pub pool_state: UncheckedAccount<'info>,
     /// Token_0 mint, the key must smaller then token_1 mint.
     #[account(
+        address = dex_state.load()?.token_0_mint @ ErrorCode::InvalidMint,
         constraint = token_0_mint.key() < token_1_mint.key(),
         mint::token_program = token_0_program,
     )]
     pub token_0_mint: Box<InterfaceAccount<'info, Mint>>,
     /// Token_1 mint, the key must grater then token_0 mint.
     #[account(
+        address = dex_state.load()?.token_1_mint @ ErrorCode::InvalidMint,
         mint::token_program = token_1_program,
     )]
     pub token_1_mint: Box<InterfaceAccount<'info, Mint>>,
@@ -651,15 +1210,13 @@ pub struct LaunchDex<'info> {
     /// dex vault0 account
     #[account(
         mut,
-        token::mint = token_0_mint,
-        token::authority = dex_authority,
+        address = dex_state.load()?.token_0_vault @ ErrorCode::InvalidVault,
     )]
     pub creator_token_0: Box<InterfaceAccount<'info, TokenAccount>>,
-    /// dex vault0 account
+    /// dex vault1 account
     #[account(
         mut,
-        token::mint = token_1_mint,
-        token::authority = dex_authority,
+        address = dex_state.load()?.token_1_vault @ ErrorCode::InvalidVault,
     )]
     pub creator_token_1: Box<InterfaceAccount<'info, TokenAccount>>,
     /// CHECK: dex lp ATA token account, init by cp-swap
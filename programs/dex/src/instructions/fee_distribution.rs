@@ -0,0 +1,61 @@
+use super::*;
+
+use crate::error::ErrorCode;
+use crate::states::*;
+
+pub fn update_fee_recipients(
+    ctx: Context<UpdateFeeRecipients>,
+    recipients: Vec<FeeRecipient>,
+) -> Result<()> {
+    if recipients.is_empty()
+        || recipients.len() > MAX_FEE_RECIPIENTS
+        || recipients
+            .iter()
+            .any(|recipient| recipient.wallet == Pubkey::default())
+        || recipients
+            .iter()
+            .map(|recipient| u32::from(recipient.weight_bps))
+            .sum::<u32>()
+            != u32::from(FEE_WEIGHT_DENOMINATOR)
+    {
+        return err!(ErrorCode::InvalidFeeRecipients);
+    }
+
+    let fee_distribution_id = ctx.accounts.fee_distribution.key();
+    let fee_distribution = &mut ctx.accounts.fee_distribution;
+    let old_recipients = fee_distribution.get_recipients().to_vec();
+
+    fee_distribution.bump = ctx.bumps.fee_distribution;
+    fee_distribution.recipients = Default::default();
+    fee_distribution.recipients[..recipients.len()].copy_from_slice(&recipients);
+    fee_distribution.recipients_count = recipients.len() as u8;
+
+    emit!(UpdateFeeRecipientsEvent {
+        admin_id: ctx.accounts.admin.key(),
+        fee_distribution_id,
+        old_recipients,
+        new_recipients: recipients,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeeRecipients<'info> {
+    #[account(mut, address = authority_manager.admin @ ErrorCode::InvalidAdmin)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [DEX_AUTHORITY_MANAGER_SEED.as_bytes()],
+        bump = authority_manager.bump
+    )]
+    pub authority_manager: Box<Account<'info, AuthorityManager>>,
+    #[account(
+        init_if_needed,
+        seeds = [DEX_FEE_DISTRIBUTION_SEED.as_bytes()],
+        bump,
+        payer = admin,
+        space = FeeDistributionState::LEN
+    )]
+    pub fee_distribution: Box<Account<'info, FeeDistributionState>>,
+    pub system_program: Program<'info, System>,
+}
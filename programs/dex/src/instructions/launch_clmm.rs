@@ -0,0 +1,509 @@
+use super::dex::{
+    fund_launch_by_admin, fund_launch_by_keeper, prepare_launch, return_launch_lamports,
+};
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::U256;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+use anchor_spl::{
+    associated_token::{get_associated_token_address, AssociatedToken},
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use whirlpool_cpi::{
+    program::Whirlpool,
+    state::{
+        OpenPositionBumps, WhirlpoolBumps, FEE_TIER_SEED, POSITION_SEED, TICK_ARRAY_SEED,
+        TICK_ARRAY_SIZE, WHIRLPOOL_SEED,
+    },
+};
+
+/// Orca Whirlpools config the dex launches to
+pub const WHIRLPOOLS_CONFIG: Pubkey = pubkey!("2LecshUwdy9xi7meFgHtFJQNSKk4KdTrcpvaB56dP2NQ");
+/// Tick spacing of the config fee tier the whirlpools are created with
+pub const WHIRLPOOL_TICK_SPACING: u16 = 128;
+/// Largest tick index of the tick spacing below the whirlpool max tick 443636
+pub const FULL_RANGE_TICK_INDEX: i32 = 443_520;
+/// Whirlpool sqrt prices of the full range tick indexes as a Q64.64
+pub const FULL_RANGE_MIN_SQRT_PRICE_X64: u128 = 4_320_030_424;
+pub const FULL_RANGE_MAX_SQRT_PRICE_X64: u128 = 78_768_511_670_388_395_576_650_426_747;
+
+/// Start tick index of the tick array holding the tick index
+pub const fn tick_array_start_index(tick_index: i32) -> i32 {
+    let ticks = TICK_ARRAY_SIZE * WHIRLPOOL_TICK_SPACING as i32;
+    tick_index.div_euclid(ticks) * ticks
+}
+
+pub fn launch_dex_clmm<'info>(
+    ctx: Context<'_, '_, '_, 'info, LaunchDexClmm<'info>>,
+    shared_lamports: u64,
+) -> Result<()> {
+    fund_launch_by_admin(
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.dex_authority_manager.admin,
+        ctx.accounts.dex_authority.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        shared_lamports,
+    )?;
+
+    // unused launch lamports are released to the pool creator
+    launch_clmm(&ctx, ctx.accounts.payer.to_account_info(), 0)
+}
+
+pub fn crank_launch_dex_clmm<'info>(
+    ctx: Context<'_, '_, '_, 'info, LaunchDexClmm<'info>>,
+) -> Result<()> {
+    let keeper_reward = fund_launch_by_keeper(
+        &ctx.accounts.payer,
+        &ctx.accounts.dex_state,
+        &ctx.accounts.dex_authority,
+    )?;
+
+    // lamports left after the pool creation go back to the dex for the pool creator
+    launch_clmm(
+        &ctx,
+        ctx.accounts.dex_state.to_account_info(),
+        keeper_reward,
+    )
+}
+
+fn launch_clmm<'info>(
+    ctx: &Context<'_, '_, '_, 'info, LaunchDexClmm<'info>>,
+    lamports_recipient: AccountInfo<'info>,
+    keeper_reward: u64,
+) -> Result<()> {
+    let dex_id = ctx.accounts.dex_state.key();
+    let pool_id = ctx.accounts.pool_state.key();
+    let dex_state = &mut ctx.accounts.dex_state.load_mut()?;
+
+    let launch_amounts = prepare_launch(
+        dex_state,
+        LaunchTarget::Clmm,
+        ctx.accounts.dex_config.launch_fee_rate,
+        ctx.accounts.creator_token_0.amount,
+        ctx.accounts.creator_token_1.amount,
+        &ctx.accounts.token_0_mint.to_account_info(),
+        &ctx.accounts.token_1_mint.to_account_info(),
+    )?;
+
+    let sqrt_price = sqrt_price_x64(launch_amounts.amount_0, launch_amounts.amount_1)?;
+    let (liquidity, amount_0, amount_1) =
+        full_range_liquidity(sqrt_price, launch_amounts.amount_0, launch_amounts.amount_1)?;
+
+    // the rounding left by the full range position goes to the launch fees
+    let launch_fees_0 = launch_amounts.launch_fees_0 + (launch_amounts.amount_0 - amount_0);
+    let launch_fees_1 = launch_amounts.launch_fees_1 + (launch_amounts.amount_1 - amount_1);
+    dex_state.launch_fees_token_0 = launch_fees_0;
+    dex_state.launch_fees_token_1 = launch_fees_1;
+
+    // dex authority pda signer seeds
+    let seeds = [
+        DEX_AUTHORITY_SEED.as_bytes(),
+        &[ctx.accounts.dex_authority_manager.authority_bump],
+    ];
+    let signer_seeds = &[seeds.as_slice()];
+
+    // the whirlpool vaults and the position mint are created at dex pdas signed by the dex
+    let token_0_mint = ctx.accounts.token_0_mint.key();
+    let token_1_mint = ctx.accounts.token_1_mint.key();
+    let vault_0_seeds = [
+        DEX_CLMM_VAULT_SEED.as_bytes(),
+        dex_id.as_ref(),
+        token_0_mint.as_ref(),
+        &[ctx.bumps.token_0_vault],
+    ];
+    let vault_1_seeds = [
+        DEX_CLMM_VAULT_SEED.as_bytes(),
+        dex_id.as_ref(),
+        token_1_mint.as_ref(),
+        &[ctx.bumps.token_1_vault],
+    ];
+    let position_mint_seeds = [
+        DEX_CLMM_POSITION_MINT_SEED.as_bytes(),
+        dex_id.as_ref(),
+        &[ctx.bumps.position_mint],
+    ];
+
+    whirlpool_cpi::cpi::initialize_pool(
+        CpiContext::new_with_signer(
+            ctx.accounts.whirlpool_program.to_account_info(),
+            whirlpool_cpi::cpi::accounts::InitializePool {
+                whirlpools_config: ctx.accounts.whirlpools_config.to_account_info(),
+                token_mint_a: ctx.accounts.token_0_mint.to_account_info(),
+                token_mint_b: ctx.accounts.token_1_mint.to_account_info(),
+                funder: ctx.accounts.dex_authority.to_account_info(),
+                whirlpool: ctx.accounts.pool_state.to_account_info(),
+                token_vault_a: ctx.accounts.token_0_vault.to_account_info(),
+                token_vault_b: ctx.accounts.token_1_vault.to_account_info(),
+                fee_tier: ctx.accounts.fee_tier.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            &[
+                seeds.as_slice(),
+                vault_0_seeds.as_slice(),
+                vault_1_seeds.as_slice(),
+            ],
+        ),
+        WhirlpoolBumps {
+            whirlpool_bump: ctx.bumps.pool_state,
+        },
+        WHIRLPOOL_TICK_SPACING,
+        sqrt_price,
+    )?;
+
+    for (tick_array, start_tick_index) in [
+        (
+            &ctx.accounts.tick_array_lower,
+            tick_array_start_index(-FULL_RANGE_TICK_INDEX),
+        ),
+        (
+            &ctx.accounts.tick_array_upper,
+            tick_array_start_index(FULL_RANGE_TICK_INDEX),
+        ),
+    ] {
+        whirlpool_cpi::cpi::initialize_tick_array(
+            CpiContext::new_with_signer(
+                ctx.accounts.whirlpool_program.to_account_info(),
+                whirlpool_cpi::cpi::accounts::InitializeTickArray {
+                    whirlpool: ctx.accounts.pool_state.to_account_info(),
+                    funder: ctx.accounts.dex_authority.to_account_info(),
+                    tick_array: tick_array.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            start_tick_index,
+        )?;
+    }
+
+    whirlpool_cpi::cpi::open_position(
+        CpiContext::new_with_signer(
+            ctx.accounts.whirlpool_program.to_account_info(),
+            whirlpool_cpi::cpi::accounts::OpenPosition {
+                funder: ctx.accounts.dex_authority.to_account_info(),
+                owner: ctx.accounts.dex_authority.to_account_info(),
+                position: ctx.accounts.position.to_account_info(),
+                position_mint: ctx.accounts.position_mint.to_account_info(),
+                position_token_account: ctx.accounts.position_token_account.to_account_info(),
+                whirlpool: ctx.accounts.pool_state.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+                associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            },
+            &[seeds.as_slice(), position_mint_seeds.as_slice()],
+        ),
+        OpenPositionBumps {
+            position_bump: ctx.bumps.position,
+        },
+        -FULL_RANGE_TICK_INDEX,
+        FULL_RANGE_TICK_INDEX,
+    )?;
+
+    whirlpool_cpi::cpi::increase_liquidity(
+        CpiContext::new_with_signer(
+            ctx.accounts.whirlpool_program.to_account_info(),
+            whirlpool_cpi::cpi::accounts::ModifyLiquidity {
+                whirlpool: ctx.accounts.pool_state.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                position_authority: ctx.accounts.dex_authority.to_account_info(),
+                position: ctx.accounts.position.to_account_info(),
+                position_token_account: ctx.accounts.position_token_account.to_account_info(),
+                token_owner_account_a: ctx.accounts.creator_token_0.to_account_info(),
+                token_owner_account_b: ctx.accounts.creator_token_1.to_account_info(),
+                token_vault_a: ctx.accounts.token_0_vault.to_account_info(),
+                token_vault_b: ctx.accounts.token_1_vault.to_account_info(),
+                tick_array_lower: ctx.accounts.tick_array_lower.to_account_info(),
+                tick_array_upper: ctx.accounts.tick_array_upper.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        liquidity,
+        amount_0,
+        amount_1,
+    )?;
+
+    return_launch_lamports(
+        ctx.accounts.dex_authority.to_account_info(),
+        lamports_recipient,
+        ctx.accounts.system_program.to_account_info(),
+        signer_seeds,
+    )?;
+
+    emit!(DexLaunchedEvent {
+        dex_id,
+        raydium_id: Pubkey::default(),
+        pool_id,
+        admin_id: ctx.accounts.payer.key(),
+        amount_0,
+        amount_1,
+        launch_fees_0,
+        launch_fees_1,
+        transfer_fee_0: launch_amounts.transfer_fee_0,
+        transfer_fee_1: launch_amounts.transfer_fee_1,
+        lp_burned: 0,
+        lp_locked: 0,
+        keeper_reward,
+        launch_target: LaunchTarget::Clmm.into(),
+    });
+
+    dex_state.is_launched = true;
+    dex_state.launch_lamports = 0;
+
+    Ok(())
+}
+
+/// Sqrt price (token_1/token_0) of the migrated amounts as a Q64.64
+pub fn sqrt_price_x64(amount_0: u64, amount_1: u64) -> Result<u128> {
+    let price_x128 = (U256::from(amount_1) << 128)
+        .checked_div(U256::from(amount_0))
+        .ok_or(ErrorCode::DivZero)?;
+    Ok(price_x128.integer_sqrt().as_u128())
+}
+
+/// Full range liquidity of the amounts at the sqrt price and the amounts it
+/// takes, rounded up like the whirlpool does
+pub fn full_range_liquidity(
+    sqrt_price_x64: u128,
+    amount_0: u64,
+    amount_1: u64,
+) -> Result<(u128, u64, u64)> {
+    require!(
+        sqrt_price_x64 > FULL_RANGE_MIN_SQRT_PRICE_X64
+            && sqrt_price_x64 < FULL_RANGE_MAX_SQRT_PRICE_X64,
+        ErrorCode::InvalidInput
+    );
+
+    let sqrt_price = U256::from(sqrt_price_x64);
+    let sqrt_price_lower = U256::from(FULL_RANGE_MIN_SQRT_PRICE_X64);
+    let sqrt_price_upper = U256::from(FULL_RANGE_MAX_SQRT_PRICE_X64);
+
+    // token_0 is deposited between the price and the upper bound, token_1 below the price
+    let liquidity_0 = U256::from(amount_0) * sqrt_price * sqrt_price_upper
+        / ((sqrt_price_upper - sqrt_price) << 64);
+    let liquidity_1 = (U256::from(amount_1) << 64) / (sqrt_price - sqrt_price_lower);
+    let liquidity = liquidity_0.min(liquidity_1);
+
+    let numerator_0 = (liquidity * (sqrt_price_upper - sqrt_price)) << 64;
+    let denominator_0 = sqrt_price * sqrt_price_upper;
+    let taken_0 = (numerator_0 + denominator_0 - 1) / denominator_0;
+    let taken_1 = (liquidity * (sqrt_price - sqrt_price_lower) + U256::from(u64::MAX)) >> 64;
+
+    Ok((
+        u128::try_from(liquidity).map_err(|_| ErrorCode::Overflow)?,
+        u64::try_from(taken_0).map_err(|_| ErrorCode::InvalidU64Cast)?,
+        u64::try_from(taken_1).map_err(|_| ErrorCode::InvalidU64Cast)?,
+    ))
+}
+
+#[derive(Accounts)]
+pub struct LaunchDexClmm<'info> {
+    /// Address paying to create the pool, the admin or anyone through the crank
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: dex vault authority, funds the whirlpool accounts and owns the position
+    #[account(
+        mut,
+        seeds = [DEX_AUTHORITY_SEED.as_bytes(),],
+        bump = dex_authority_manager.authority_bump,
+    )]
+    pub dex_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [DEX_AUTHORITY_MANAGER_SEED.as_bytes()],
+        bump = dex_authority_manager.bump
+    )]
+    pub dex_authority_manager: Box<Account<'info, AuthorityManager>>,
+    #[account(
+        address = dex_state.load()?.config @ ErrorCode::InvalidConfig,
+    )]
+    pub dex_config: Box<Account<'info, ConfigState>>,
+    /// The program account of the dex to launch
+    #[account(mut)]
+    pub dex_state: AccountLoader<'info, DexState>,
+    pub whirlpool_program: Program<'info, Whirlpool>,
+    /// CHECK: Whirlpools config the pool is created in
+    #[account(address = WHIRLPOOLS_CONFIG)]
+    pub whirlpools_config: UncheckedAccount<'info>,
+    /// CHECK: Config fee tier of the tick spacing, checked by the whirlpool
+    #[account(
+        seeds = [
+            FEE_TIER_SEED.as_bytes(),
+            whirlpools_config.key().as_ref(),
+            WHIRLPOOL_TICK_SPACING.to_le_bytes().as_ref(),
+        ],
+        seeds::program = whirlpool_program,
+        bump,
+    )]
+    pub fee_tier: UncheckedAccount<'info>,
+    /// CHECK: Initialize an account to store the pool state, init by the whirlpool
+    #[account(
+        mut,
+        seeds = [
+            WHIRLPOOL_SEED.as_bytes(),
+            whirlpools_config.key().as_ref(),
+            token_0_mint.key().as_ref(),
+            token_1_mint.key().as_ref(),
+            WHIRLPOOL_TICK_SPACING.to_le_bytes().as_ref(),
+        ],
+        seeds::program = whirlpool_program,
+        bump,
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+    /// Token_0 mint, the key must smaller then token_1 mint.
+    #[account(
+        address = dex_state.load()?.token_0_mint @ ErrorCode::InvalidMint,
+        constraint = token_0_mint.key() < token_1_mint.key(),
+        mint::token_program = token_program,
+    )]
+    pub token_0_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// Token_1 mint, the key must grater then token_0 mint.
+    #[account(
+        address = dex_state.load()?.token_1_mint @ ErrorCode::InvalidMint,
+        mint::token_program = token_program,
+    )]
+    pub token_1_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// dex vault0 account
+    #[account(
+        mut,
+        address = dex_state.load()?.token_0_vault @ ErrorCode::InvalidVault,
+    )]
+    pub creator_token_0: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// dex vault1 account
+    #[account(
+        mut,
+        address = dex_state.load()?.token_1_vault @ ErrorCode::InvalidVault,
+    )]
+    pub creator_token_1: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: Token_0 vault for the pool, init by the whirlpool at a dex pda
+    #[account(
+        mut,
+        seeds = [
+            DEX_CLMM_VAULT_SEED.as_bytes(),
+            dex_state.key().as_ref(),
+            token_0_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub token_0_vault: UncheckedAccount<'info>,
+    /// CHECK: Token_1 vault for the pool, init by the whirlpool at a dex pda
+    #[account(
+        mut,
+        seeds = [
+            DEX_CLMM_VAULT_SEED.as_bytes(),
+            dex_state.key().as_ref(),
+            token_1_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub token_1_vault: UncheckedAccount<'info>,
+    /// CHECK: Tick array of the full range lower tick, init by the whirlpool
+    #[account(
+        mut,
+        seeds = [
+            TICK_ARRAY_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+            tick_array_start_index(-FULL_RANGE_TICK_INDEX).to_string().as_bytes(),
+        ],
+        seeds::program = whirlpool_program,
+        bump,
+    )]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: Tick array of the full range upper tick, init by the whirlpool
+    #[account(
+        mut,
+        seeds = [
+            TICK_ARRAY_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+            tick_array_start_index(FULL_RANGE_TICK_INDEX).to_string().as_bytes(),
+        ],
+        seeds::program = whirlpool_program,
+        bump,
+    )]
+    pub tick_array_upper: UncheckedAccount<'info>,
+    /// CHECK: Mint of the full range position, init by the whirlpool at a dex pda
+    #[account(
+        mut,
+        seeds = [
+            DEX_CLMM_POSITION_MINT_SEED.as_bytes(),
+            dex_state.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub position_mint: UncheckedAccount<'info>,
+    /// CHECK: full range position of the dex authority, init by the whirlpool
+    #[account(
+        mut,
+        seeds = [
+            POSITION_SEED.as_bytes(),
+            position_mint.key().as_ref(),
+        ],
+        seeds::program = whirlpool_program,
+        bump,
+    )]
+    pub position: UncheckedAccount<'info>,
+    /// CHECK: dex authority account of the position mint, init by the whirlpool
+    #[account(
+        mut,
+        address = get_associated_token_address(&dex_authority.key(), &position_mint.key()),
+    )]
+    pub position_token_account: UncheckedAccount<'info>,
+    /// The whirlpool only takes SPL Token mints
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// To create new program accounts
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sqrt_price_x64_test() {
+        assert_eq!(sqrt_price_x64(1_000, 1_000).unwrap(), 1 << 64);
+        assert_eq!(sqrt_price_x64(1_000, 4_000).unwrap(), 2 << 64);
+        assert_eq!(sqrt_price_x64(4_000, 1_000).unwrap(), 1 << 63);
+        assert!(sqrt_price_x64(0, 1_000).is_err());
+    }
+
+    #[test]
+    fn tick_array_start_index_test() {
+        assert_eq!(tick_array_start_index(-FULL_RANGE_TICK_INDEX), -450_560);
+        assert_eq!(tick_array_start_index(FULL_RANGE_TICK_INDEX), 439_296);
+        assert_eq!(tick_array_start_index(0), 0);
+        assert_eq!(tick_array_start_index(-1), -11_264);
+    }
+
+    #[test]
+    fn full_range_liquidity_test() {
+        for (amount_0, amount_1) in [
+            (1_000_000_000u64, 1_000_000_000u64),
+            (793_100_000 * 10u64.pow(9), 85 * 10u64.pow(9)),
+            (85 * 10u64.pow(9), 793_100_000 * 10u64.pow(9)),
+            (1_000, u64::MAX),
+        ] {
+            let sqrt_price = sqrt_price_x64(amount_0, amount_1).unwrap();
+            let (liquidity, taken_0, taken_1) =
+                full_range_liquidity(sqrt_price, amount_0, amount_1).unwrap();
+            assert!(liquidity > 0);
+            assert!(taken_0 <= amount_0 && taken_1 <= amount_1);
+            // the position takes all but the rounding of one of the amounts, the
+            // other one is left over when the price is close to the range bounds
+            assert!(
+                amount_0 - taken_0 <= amount_0 / 1_000_000 + 1
+                    || amount_1 - taken_1 <= amount_1 / 1_000_000 + 1
+            );
+        }
+
+        assert!(full_range_liquidity(FULL_RANGE_MIN_SQRT_PRICE_X64, 1, 1).is_err());
+        assert!(full_range_liquidity(FULL_RANGE_MAX_SQRT_PRICE_X64, 1, 1).is_err());
+        // a price above the full range can't be launched
+        assert!(full_range_liquidity(sqrt_price_x64(1, u64::MAX).unwrap(), 1, u64::MAX).is_err());
+    }
+}
@@ -0,0 +1,462 @@
+use crate::curve::TradeDirection;
+use crate::error::ErrorCode;
+use crate::instructions::Swapper;
+use crate::states::*;
+use crate::utils::token::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Order, escrow, owner and owner output token account of each order filled by the crank
+pub const FILL_ORDER_ACCOUNTS: usize = 4;
+
+/// Escrow input tokens of the owner until the token_0 price of the dex crosses the trigger price
+pub fn place_limit_order<'info>(
+    ctx: Context<'_, '_, '_, 'info, PlaceLimitOrder<'info>>,
+    order_id: u64,
+    amount_in: u64,
+    minimum_amount_out: u64,
+    trigger_price_x32: u128,
+    trigger_above: bool,
+) -> Result<()> {
+    let dex_id = ctx.accounts.dex_state.key();
+    let timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+    let zero_for_one = {
+        let dex_state = ctx.accounts.dex_state.load()?;
+        if dex_state.is_launched {
+            return err!(ErrorCode::DexLaunched);
+        }
+        if dex_state.is_refunding(timestamp) {
+            return err!(ErrorCode::DexRefunding);
+        }
+        ctx.accounts.input_token_mint.key() == dex_state.token_0_mint
+    };
+    require_gt!(amount_in, 0, ErrorCode::InvalidInput);
+    require_gt!(trigger_price_x32, 0, ErrorCode::InvalidLimitOrder);
+
+    let order_key = ctx.accounts.order.key();
+    let escrow_seeds = [
+        DEX_LIMIT_ORDER_ESCROW_SEED.as_bytes(),
+        order_key.as_ref(),
+        &[ctx.bumps.escrow],
+    ];
+    // the order owns its escrow, only its own seeds move the escrowed tokens
+    create_token_account(
+        &ctx.accounts.order.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.escrow.to_account_info(),
+        &ctx.accounts.input_token_mint.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.input_token_program.to_account_info(),
+        &[escrow_seeds.as_slice()],
+    )?;
+
+    transfer_from_user_to_dex_vault(
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.input_token_account.to_account_info(),
+        ctx.accounts.escrow.to_account_info(),
+        &ctx.accounts.input_token_mint,
+        ctx.accounts.input_token_program.to_account_info(),
+        amount_in,
+        ctx.remaining_accounts,
+    )?;
+
+    let transfer_fee =
+        get_transfer_fee(&ctx.accounts.input_token_mint.to_account_info(), amount_in)?;
+    let escrow_amount = amount_in
+        .checked_sub(transfer_fee)
+        .ok_or(ErrorCode::Underflow)?;
+    require_gt!(escrow_amount, 0, ErrorCode::InvalidInput);
+
+    let owner = ctx.accounts.owner.key();
+    ctx.accounts.order.set_inner(LimitOrderState {
+        bump: ctx.bumps.order,
+        escrow_bump: ctx.bumps.escrow,
+        dex_id,
+        owner,
+        order_id,
+        zero_for_one,
+        amount_in: escrow_amount,
+        minimum_amount_out,
+        trigger_price_x32,
+        trigger_above,
+        created_at: timestamp,
+    });
+
+    emit!(PlaceLimitOrderEvent {
+        dex_id,
+        order_id: order_key,
+        owner,
+        zero_for_one,
+        amount_in: escrow_amount,
+        minimum_amount_out,
+        trigger_price_x32,
+        trigger_above,
+    });
+
+    Ok(())
+}
+
+/// Return the escrowed tokens to the owner and close the order
+pub fn cancel_limit_order<'info>(
+    ctx: Context<'_, '_, '_, 'info, CancelLimitOrder<'info>>,
+) -> Result<()> {
+    let amount_in = ctx.accounts.escrow.amount;
+
+    // order pda signer seeds
+    let order = &ctx.accounts.order;
+    let order_id = order.order_id.to_be_bytes();
+    let seeds = [
+        DEX_LIMIT_ORDER_SEED.as_bytes(),
+        order.dex_id.as_ref(),
+        order.owner.as_ref(),
+        order_id.as_ref(),
+        &[order.bump],
+    ];
+    let signer_seeds = &[seeds.as_slice()];
+
+    transfer_from_dex_vault_to_user(
+        ctx.accounts.order.to_account_info(),
+        ctx.accounts.escrow.to_account_info(),
+        ctx.accounts.input_token_account.to_account_info(),
+        &ctx.accounts.input_token_mint,
+        ctx.accounts.input_token_program.to_account_info(),
+        amount_in,
+        signer_seeds,
+        ctx.remaining_accounts,
+    )?;
+
+    close_escrow(
+        ctx.accounts.order.to_account_info(),
+        ctx.accounts.input_token_mint.to_account_info(),
+        ctx.accounts.escrow.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.input_token_program.to_account_info(),
+        signer_seeds,
+    )?;
+
+    emit!(CancelLimitOrderEvent {
+        dex_id: ctx.accounts.order.dex_id,
+        order_id: ctx.accounts.order.key(),
+        owner: ctx.accounts.owner.key(),
+        amount_in,
+    });
+
+    Ok(())
+}
+
+/// Fill the limit orders triggered at the current price through the swap, the orders not triggered are skipped.
+/// Remaining accounts are the `FILL_ORDER_ACCOUNTS` of each order followed by the extra accounts of the mint transfer hooks.
+pub fn fill_orders<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FillOrders<'info>>,
+    order_count: u8,
+) -> Result<()> {
+    let order_accounts_len = usize::from(order_count) * FILL_ORDER_ACCOUNTS;
+    require_gte!(
+        ctx.remaining_accounts.len(),
+        order_accounts_len,
+        ErrorCode::InvalidLimitOrder
+    );
+    let (order_accounts, hook_accounts) = ctx.remaining_accounts.split_at(order_accounts_len);
+
+    for accounts in order_accounts.chunks(FILL_ORDER_ACCOUNTS) {
+        ctx.accounts.fill_order(
+            &accounts[0],
+            &accounts[1],
+            &accounts[2],
+            &accounts[3],
+            hook_accounts,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct PlaceLimitOrder<'info> {
+    /// The user placing the order
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// The dex the order swaps against
+    pub dex_state: AccountLoader<'info, DexState>,
+    #[account(
+        init,
+        seeds = [
+            DEX_LIMIT_ORDER_SEED.as_bytes(),
+            dex_state.key().as_ref(),
+            owner.key().as_ref(),
+            order_id.to_be_bytes().as_ref(),
+        ],
+        bump,
+        payer = owner,
+        space = LimitOrderState::LEN,
+    )]
+    pub order: Box<Account<'info, LimitOrderState>>,
+    /// CHECK: Input token account owned by the order, created by the instruction
+    #[account(
+        mut,
+        seeds = [
+            DEX_LIMIT_ORDER_ESCROW_SEED.as_bytes(),
+            order.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub escrow: UncheckedAccount<'info>,
+    /// The user token account for input token
+    #[account(mut)]
+    pub input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The mint of input token
+    #[account(
+        constraint = input_token_mint.key() == dex_state.load()?.token_0_mint
+            || input_token_mint.key() == dex_state.load()?.token_1_mint @ ErrorCode::InvalidLimitOrder
+    )]
+    pub input_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// SPL program for input token transfers
+    pub input_token_program: Interface<'info, TokenInterface>,
+    /// To create the order and the escrow
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelLimitOrder<'info> {
+    /// The owner of the order
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            DEX_LIMIT_ORDER_SEED.as_bytes(),
+            order.dex_id.as_ref(),
+            owner.key().as_ref(),
+            order.order_id.to_be_bytes().as_ref(),
+        ],
+        bump = order.bump,
+        has_one = owner @ ErrorCode::InvalidLimitOrder,
+        close = owner,
+    )]
+    pub order: Box<Account<'info, LimitOrderState>>,
+    #[account(
+        mut,
+        seeds = [
+            DEX_LIMIT_ORDER_ESCROW_SEED.as_bytes(),
+            order.key().as_ref(),
+        ],
+        bump = order.escrow_bump,
+    )]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The user token account for input token
+    #[account(mut)]
+    pub input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The mint of input token, withheld transfer fees of the escrow are harvested to it
+    #[account(
+        mut,
+        address = escrow.mint
+    )]
+    pub input_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// SPL program for input token transfers
+    pub input_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct FillOrders<'info> {
+    /// The caller of the permissionless crank
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    #[account(
+        address = dex_state.load()?.config @ ErrorCode::InvalidConfig,
+    )]
+    pub config: Box<Account<'info, ConfigState>>,
+    #[account(
+        seeds = [DEX_AUTHORITY_MANAGER_SEED.as_bytes()],
+        bump = authority_manager.bump
+    )]
+    pub authority_manager: Box<Account<'info, AuthorityManager>>,
+    /// CHECK: dex vault authority
+    #[account(
+        seeds = [
+            DEX_AUTHORITY_SEED.as_bytes(),
+        ],
+        bump = authority_manager.authority_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    /// The dex of the orders
+    #[account(mut)]
+    pub dex_state: AccountLoader<'info, DexState>,
+    /// The price observations of the dex
+    #[account(
+        mut,
+        seeds = [
+            DEX_OBSERVATION_SEED.as_bytes(),
+            dex_state.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+    /// The vault token account for input token
+    #[account(
+        mut,
+        constraint = input_vault.key() == dex_state.load()?.token_0_vault || input_vault.key() == dex_state.load()?.token_1_vault
+    )]
+    pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The vault token account for output token
+    #[account(
+        mut,
+        constraint = output_vault.key() == dex_state.load()?.token_0_vault || output_vault.key() == dex_state.load()?.token_1_vault
+    )]
+    pub output_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// SPL program for input token transfers
+    pub input_token_program: Interface<'info, TokenInterface>,
+    /// SPL program for output token transfers
+    pub output_token_program: Interface<'info, TokenInterface>,
+    /// The mint of input token, withheld transfer fees of the filled escrows are harvested to it
+    #[account(
+        mut,
+        address = input_vault.mint
+    )]
+    pub input_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The mint of output token
+    #[account(
+        address = output_vault.mint
+    )]
+    pub output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FillOrders<'info> {
+    fn fill_order(
+        &mut self,
+        order_info: &'info AccountInfo<'info>,
+        escrow_info: &'info AccountInfo<'info>,
+        owner_info: &'info AccountInfo<'info>,
+        output_token_account_info: &'info AccountInfo<'info>,
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let dex_id = self.dex_state.key();
+        let mut order = Account::<LimitOrderState>::try_from(order_info)?;
+        let mut escrow = InterfaceAccount::<TokenAccount>::try_from(escrow_info)?;
+        let mut output_token_account =
+            InterfaceAccount::<TokenAccount>::try_from(output_token_account_info)?;
+        let escrow_key = Pubkey::create_program_address(
+            &[
+                DEX_LIMIT_ORDER_ESCROW_SEED.as_bytes(),
+                order_info.key.as_ref(),
+                &[order.escrow_bump],
+            ],
+            &crate::id(),
+        )
+        .map_err(|_| ErrorCode::InvalidLimitOrder)?;
+
+        if order.dex_id != dex_id
+            || order.owner != owner_info.key()
+            || escrow.key() != escrow_key
+            || output_token_account.owner != order.owner
+            || output_token_account.mint != self.output_token_mint.key()
+        {
+            return err!(ErrorCode::InvalidLimitOrder);
+        }
+
+        let token_0_price_x32 = {
+            let dex_state = self.dex_state.load()?;
+            let (trade_direction, vault_0, vault_1) =
+                if self.input_vault.key() == dex_state.token_0_vault {
+                    (
+                        TradeDirection::ZeroForOne,
+                        self.input_vault.amount,
+                        self.output_vault.amount,
+                    )
+                } else {
+                    (
+                        TradeDirection::OneForZero,
+                        self.output_vault.amount,
+                        self.input_vault.amount,
+                    )
+                };
+            if order.zero_for_one != (trade_direction == TradeDirection::ZeroForOne) {
+                return err!(ErrorCode::InvalidLimitOrder);
+            }
+
+            let (token_0_price_x32, _) = dex_state.token_price_x32(vault_0, vault_1);
+            // buys of the anti-sniper window are left to the wallet limited swaps
+            let timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+            if !order.is_triggered(token_0_price_x32)
+                || (dex_state.is_buy(trade_direction) && dex_state.is_sniper_window(timestamp))
+            {
+                return Ok(());
+            }
+            token_0_price_x32
+        };
+
+        let output_amount_before = output_token_account.amount;
+        Swapper::from_fill(
+            self,
+            order.clone(),
+            escrow_info.clone(),
+            output_token_account_info.clone(),
+            hook_accounts.to_vec(),
+        )
+        .try_swap_base_input(order.amount_in, order.minimum_amount_out)?;
+
+        escrow.reload()?;
+        output_token_account.reload()?;
+        let amount_left = escrow.amount;
+        let amount_out = output_token_account
+            .amount
+            .checked_sub(output_amount_before)
+            .ok_or(ErrorCode::Underflow)?;
+
+        emit!(FillLimitOrderEvent {
+            dex_id,
+            order_id: order.key(),
+            owner: order.owner,
+            keeper: self.keeper.key(),
+            amount_in: order
+                .amount_in
+                .checked_sub(amount_left)
+                .ok_or(ErrorCode::Underflow)?,
+            amount_out,
+            amount_left,
+            token_0_price_x32,
+        });
+
+        if amount_left == 0 {
+            // order pda signer seeds
+            let order_id = order.order_id.to_be_bytes();
+            let seeds = [
+                DEX_LIMIT_ORDER_SEED.as_bytes(),
+                order.dex_id.as_ref(),
+                order.owner.as_ref(),
+                order_id.as_ref(),
+                &[order.bump],
+            ];
+            close_escrow(
+                order_info.clone(),
+                self.input_token_mint.to_account_info(),
+                escrow_info.clone(),
+                owner_info.clone(),
+                self.input_token_program.to_account_info(),
+                &[seeds.as_slice()],
+            )?;
+            order.close(owner_info.clone())?;
+        } else {
+            order.fill(amount_left);
+            order.exit(&crate::id())?;
+        }
+
+        // the next order is priced after this fill
+        self.input_vault.reload()?;
+        self.output_vault.reload()
+    }
+}
+
+/// Close an emptied escrow signed by its order, its rent goes back to the order owner
+fn close_escrow<'info>(
+    order: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    escrow: AccountInfo<'info>,
+    owner: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    harvest_withheld_tokens(mint, escrow.clone(), token_program.clone())?;
+    close_token_account(order, escrow, owner, token_program, signer_seeds)
+}
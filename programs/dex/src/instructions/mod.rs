@@ -7,7 +7,22 @@ pub use authority_manager::*;
 pub mod config;
 pub use config::*;
 
+pub mod fee_distribution;
+pub use fee_distribution::*;
+
 pub mod swap;
 pub use swap::*;
 
+pub mod limit_order;
+pub use limit_order::*;
+
+pub mod quote_swap;
+pub use quote_swap::*;
+
+pub mod refund;
+pub use refund::*;
+
+pub mod launch_clmm;
+pub use launch_clmm::*;
+
 use anchor_lang::prelude::*;
@@ -0,0 +1,109 @@
+use crate::curve::TradeDirection;
+use crate::error::ErrorCode;
+use crate::quote::{MintData, SwapQuote, SwapQuoter};
+use crate::states::*;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+pub fn quote_base_input(
+    ctx: Context<QuoteSwap>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+    with_referrer: bool,
+) -> Result<SwapQuote> {
+    ctx.accounts.quote(with_referrer, |quoter| {
+        quoter.quote_base_input(amount_in, minimum_amount_out)
+    })
+}
+
+pub fn quote_base_output(
+    ctx: Context<QuoteSwap>,
+    max_amount_in: u64,
+    amount_out_less_fee: u64,
+    with_referrer: bool,
+) -> Result<SwapQuote> {
+    ctx.accounts.quote(with_referrer, |quoter| {
+        quoter.quote_base_output(max_amount_in, amount_out_less_fee)
+    })
+}
+
+/// Accounts read by a swap quote, nothing is written
+#[derive(Accounts)]
+pub struct QuoteSwap<'info> {
+    #[account(
+        address = dex_state.load()?.config @ ErrorCode::InvalidConfig,
+    )]
+    pub config: Box<Account<'info, ConfigState>>,
+    /// The program account of the dex to quote
+    pub dex_state: AccountLoader<'info, DexState>,
+    /// The vault token account for input token
+    #[account(
+        constraint = input_vault.key() == dex_state.load()?.token_0_vault || input_vault.key() == dex_state.load()?.token_1_vault
+    )]
+    pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The vault token account for output token
+    #[account(
+        constraint = output_vault.key() == dex_state.load()?.token_0_vault || output_vault.key() == dex_state.load()?.token_1_vault
+    )]
+    pub output_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The mint of input token
+    #[account(
+        address = input_vault.mint
+    )]
+    pub input_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The mint of output token
+    #[account(
+        address = output_vault.mint
+    )]
+    pub output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+impl<'info> QuoteSwap<'info> {
+    /// Quote with the same quoter as the swap instructions against the current accounts
+    fn quote(
+        &self,
+        with_referrer: bool,
+        quote: impl FnOnce(&SwapQuoter) -> Result<SwapQuote>,
+    ) -> Result<SwapQuote> {
+        let dex_state = &self.dex_state.load()?;
+        let trade_direction = match (self.input_vault.key(), self.output_vault.key()) {
+            (input, output)
+                if input == dex_state.token_0_vault && output == dex_state.token_1_vault =>
+            {
+                TradeDirection::ZeroForOne
+            }
+            (input, output)
+                if input == dex_state.token_1_vault && output == dex_state.token_0_vault =>
+            {
+                TradeDirection::OneForZero
+            }
+            _ => return err!(ErrorCode::InvalidVault),
+        };
+
+        let input_mint_info = self.input_token_mint.to_account_info();
+        let output_mint_info = self.output_token_mint.to_account_info();
+        let input_mint_data = input_mint_info.try_borrow_data()?;
+        let output_mint_data = output_mint_info.try_borrow_data()?;
+        let clock = Clock::get()?;
+
+        quote(&SwapQuoter {
+            dex_state,
+            config: &self.config,
+            trade_direction,
+            input_vault_amount: self.input_vault.amount,
+            output_vault_amount: self.output_vault.amount,
+            input_mint: MintData {
+                program: *input_mint_info.owner,
+                data: &input_mint_data,
+            },
+            output_mint: MintData {
+                program: *output_mint_info.owner,
+                data: &output_mint_data,
+            },
+            epoch: clock.epoch,
+            timestamp: u64::try_from(clock.unix_timestamp).unwrap(),
+            with_referrer,
+        })
+    }
+}
@@ -0,0 +1,208 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::token::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Redeem supply tokens sold by the curve for a pro-rata share of the reserve it collected
+/// once the launch deadline passed
+pub fn refund<'info>(ctx: Context<'_, '_, '_, 'info, Refund<'info>>, amount: u64) -> Result<()> {
+    let dex_id = ctx.accounts.dex_state.key();
+    let dex_state = &ctx.accounts.dex_state.load()?;
+
+    if !dex_state.is_refunding(u64::try_from(Clock::get()?.unix_timestamp).unwrap()) {
+        return err!(ErrorCode::DexNotRefunding);
+    }
+
+    let supply_transfer_fee =
+        get_transfer_fee(&ctx.accounts.supply_token_mint.to_account_info(), amount)?;
+    let supply_amount = amount.saturating_sub(supply_transfer_fee);
+    require_gt!(supply_amount, 0);
+
+    // every supply token sold by the curve redeems the same share of the reserve it collected
+    let (sold_amount, refundable_reserve) = dex_state
+        .get_refund_amounts(
+            ctx.accounts.supply_vault.amount,
+            ctx.accounts.reserve_vault.amount,
+        )
+        .ok_or(ErrorCode::Underflow)?;
+    require_gte!(sold_amount, supply_amount, ErrorCode::InvalidInput);
+
+    let reserve_amount = u64::try_from(
+        u128::from(refundable_reserve) * u128::from(supply_amount) / u128::from(sold_amount),
+    )
+    .map_err(|_| ErrorCode::InvalidU64Cast)?;
+    require_gt!(reserve_amount, 0, ErrorCode::ZeroTradingTokens);
+
+    transfer_from_user_to_dex_vault(
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.supply_token_account.to_account_info(),
+        ctx.accounts.supply_vault.to_account_info(),
+        &ctx.accounts.supply_token_mint,
+        ctx.accounts.supply_token_program.to_account_info(),
+        amount,
+        ctx.remaining_accounts,
+    )?;
+
+    // dex authority pda signer seeds
+    let seeds = [
+        DEX_AUTHORITY_SEED.as_bytes(),
+        &[ctx.accounts.authority_manager.authority_bump],
+    ];
+    let signer_seeds = &[seeds.as_slice()];
+
+    transfer_from_dex_vault_to_user(
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.reserve_vault.to_account_info(),
+        ctx.accounts.reserve_token_account.to_account_info(),
+        &ctx.accounts.reserve_token_mint,
+        ctx.accounts.reserve_token_program.to_account_info(),
+        reserve_amount,
+        signer_seeds,
+        ctx.remaining_accounts,
+    )?;
+
+    emit!(RefundEvent {
+        dex_id,
+        payer_id: ctx.accounts.payer.key(),
+        supply_amount,
+        supply_transfer_fee,
+        reserve_amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Refund<'info> {
+    /// The holder redeeming supply tokens
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [DEX_AUTHORITY_MANAGER_SEED.as_bytes()],
+        bump = authority_manager.bump
+    )]
+    pub authority_manager: Box<Account<'info, AuthorityManager>>,
+    /// CHECK: dex vault authority
+    #[account(
+        seeds = [
+            DEX_AUTHORITY_SEED.as_bytes(),
+        ],
+        bump = authority_manager.authority_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    /// The dex past its launch deadline
+    pub dex_state: AccountLoader<'info, DexState>,
+    /// The user token account for supply token
+    #[account(mut)]
+    pub supply_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The user token account for reserve token
+    #[account(mut)]
+    pub reserve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The vault token account for supply token
+    #[account(
+        mut,
+        address = dex_state.load()?.get_supply_vault() @ ErrorCode::InvalidVault
+    )]
+    pub supply_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The vault token account for reserve token
+    #[account(
+        mut,
+        address = dex_state.load()?.get_reserve_vault() @ ErrorCode::InvalidVault
+    )]
+    pub reserve_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// SPL program for supply token transfers
+    pub supply_token_program: Interface<'info, TokenInterface>,
+    /// SPL program for reserve token transfers
+    pub reserve_token_program: Interface<'info, TokenInterface>,
+    /// The mint of supply token
+    #[account(
+        address = supply_vault.mint
+    )]
+    pub supply_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The mint of reserve token
+    #[account(
+        address = reserve_vault.mint
+    )]
+    pub reserve_token_mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+/// Return the seed reserve to the pool creator once the launch deadline passed
+pub fn withdraw_seed_reserve<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawSeedReserve<'info>>,
+) -> Result<()> {
+    let dex_id = ctx.accounts.dex_state.key();
+    let mut dex_state = ctx.accounts.dex_state.load_mut()?;
+
+    if !dex_state.is_refunding(u64::try_from(Clock::get()?.unix_timestamp).unwrap()) {
+        return err!(ErrorCode::DexNotRefunding);
+    }
+
+    let amount = dex_state.seed_reserve;
+    require_gt!(amount, 0, ErrorCode::ZeroTradingTokens);
+    dex_state.seed_reserve = 0;
+
+    // dex authority pda signer seeds
+    let seeds = [
+        DEX_AUTHORITY_SEED.as_bytes(),
+        &[ctx.accounts.authority_manager.authority_bump],
+    ];
+    let signer_seeds = &[seeds.as_slice()];
+
+    transfer_from_dex_vault_to_user(
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.reserve_vault.to_account_info(),
+        ctx.accounts.reserve_token_account.to_account_info(),
+        &ctx.accounts.reserve_token_mint,
+        ctx.accounts.reserve_token_program.to_account_info(),
+        amount,
+        signer_seeds,
+        ctx.remaining_accounts,
+    )?;
+
+    emit!(WithdrawSeedReserveEvent {
+        creator_id: ctx.accounts.pool_creator.key(),
+        dex_id,
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSeedReserve<'info> {
+    /// Only pool creator can withdraw the seed reserve
+    #[account(address = dex_state.load()?.pool_creator @ ErrorCode::InvalidPoolCreator)]
+    pub pool_creator: Signer<'info>,
+    #[account(
+        seeds = [DEX_AUTHORITY_MANAGER_SEED.as_bytes()],
+        bump = authority_manager.bump
+    )]
+    pub authority_manager: Box<Account<'info, AuthorityManager>>,
+    /// CHECK: dex vault authority
+    #[account(
+        seeds = [
+            DEX_AUTHORITY_SEED.as_bytes(),
+        ],
+        bump = authority_manager.authority_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    /// The dex past its launch deadline, stores the seed reserve
+    #[account(mut)]
+    pub dex_state: AccountLoader<'info, DexState>,
+    /// The pool creator token account for reserve token
+    #[account(mut)]
+    pub reserve_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The vault token account for reserve token
+    #[account(
+        mut,
+        address = dex_state.load()?.get_reserve_vault() @ ErrorCode::InvalidVault
+    )]
+    pub reserve_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// SPL program for reserve token transfers
+    pub reserve_token_program: Interface<'info, TokenInterface>,
+    /// The mint of reserve token
+    #[account(
+        address = reserve_vault.mint
+    )]
+    pub reserve_token_mint: Box<InterfaceAccount<'info, Mint>>,
+}
@@ -1,10 +1,15 @@
-use crate::curve::calculator::CurveCalculator;
 use crate::curve::TradeDirection;
 use crate::error::ErrorCode;
+use crate::instructions::FillOrders;
+use crate::quote::{MintData, SwapQuote, SwapQuoter};
 use crate::states::*;
+use crate::utils::create_pda_account;
 use crate::utils::token::*;
-use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    token::spl_token::native_mint,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
 use std::cell::RefMut;
 
 pub fn swap_base_input<'info>(
@@ -25,16 +30,112 @@ pub fn swap_base_output<'info>(
     swapper.try_swap_base_output(max_amount_in, amount_out_less_fee)
 }
 
+pub fn swap_base_input_native<'info>(
+    ctx: Context<'_, '_, '_, 'info, SwapNative<'info>>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    let native_input = ctx
+        .accounts
+        .open_native_account(ctx.bumps.native_token_account, amount_in)?;
+
+    Swapper::from_native_ctx(&ctx, native_input)
+        .try_swap_base_input(amount_in, minimum_amount_out)?;
+
+    ctx.accounts.close_native_account(native_input)
+}
+
+pub fn swap_base_output_native<'info>(
+    ctx: Context<'_, '_, '_, 'info, SwapNative<'info>>,
+    max_amount_in: u64,
+    amount_out_less_fee: u64,
+) -> Result<()> {
+    let native_input = ctx
+        .accounts
+        .open_native_account(ctx.bumps.native_token_account, max_amount_in)?;
+
+    Swapper::from_native_ctx(&ctx, native_input)
+        .try_swap_base_output(max_amount_in, amount_out_less_fee)?;
+
+    // the unused lamports of the max input go back to the payer
+    ctx.accounts.close_native_account(native_input)
+}
+
+pub fn swap_route<'info>(
+    ctx: Context<'_, '_, '_, 'info, SwapRoute<'info>>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+    first_hop_accounts: u8,
+) -> Result<()> {
+    let first = &ctx.accounts.first;
+    let second = &ctx.accounts.second;
+    if first.payer.key() != second.payer.key()
+        || first.output_token_account.key() != second.input_token_account.key()
+        || first.dex_state.key() == second.dex_state.key()
+    {
+        return err!(ErrorCode::InvalidRoute);
+    }
+
+    let mut intermediate_token_account = second.input_token_account.clone();
+    let mut output_token_account = second.output_token_account.clone();
+    let intermediate_amount_before = intermediate_token_account.amount;
+    let output_amount_before = output_token_account.amount;
+
+    // the remaining accounts of each hop are laid out like the ones of a single swap
+    let first_hop_accounts = usize::from(first_hop_accounts);
+    require_gte!(
+        ctx.remaining_accounts.len(),
+        first_hop_accounts,
+        ErrorCode::InvalidRoute
+    );
+    let (first_remaining_accounts, second_remaining_accounts) =
+        ctx.remaining_accounts.split_at(first_hop_accounts);
+    let (first_referrer, first_hook_accounts) = split_remaining_accounts(first_remaining_accounts);
+    let (second_referrer, second_hook_accounts) =
+        split_remaining_accounts(second_remaining_accounts);
+
+    // slippage is only checked once on the route output
+    Swapper::from_accounts(first, &ctx.bumps.first, first_referrer, first_hook_accounts)
+        .try_swap_base_input(amount_in, 0)?;
+
+    intermediate_token_account.reload()?;
+    let intermediate_amount = intermediate_token_account
+        .amount
+        .checked_sub(intermediate_amount_before)
+        .ok_or(ErrorCode::Underflow)?;
+
+    Swapper::from_accounts(
+        second,
+        &ctx.bumps.second,
+        second_referrer,
+        second_hook_accounts,
+    )
+    .try_swap_base_input(intermediate_amount, minimum_amount_out)?;
+
+    output_token_account.reload()?;
+    let amount_out = output_token_account
+        .amount
+        .checked_sub(output_amount_before)
+        .ok_or(ErrorCode::Underflow)?;
+
+    emit!(SwapRouteEvent {
+        first_dex_id: first.dex_state.key(),
+        second_dex_id: second.dex_state.key(),
+        amount_in,
+        intermediate_amount,
+        amount_out,
+    });
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct Swap<'info> {
     /// The user performing the swap
+    #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
-        mut,
-        seeds = [
-            DEX_CONFIG_SEED.as_bytes(),
-        ],
-        bump = config.bump
+        address = dex_state.load()?.config @ ErrorCode::InvalidConfig,
     )]
     pub config: Box<Account<'info, ConfigState>>,
     #[account(
@@ -53,6 +154,16 @@ pub struct Swap<'info> {
     /// The program account of the dex in which the swap will be performed
     #[account(mut)]
     pub dex_state: AccountLoader<'info, DexState>,
+    /// The price observations of the dex
+    #[account(
+        mut,
+        seeds = [
+            DEX_OBSERVATION_SEED.as_bytes(),
+            dex_state.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub observation_state: AccountLoader<'info, ObservationState>,
     /// The user token account for input token
     #[account(mut)]
     pub input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
@@ -85,6 +196,204 @@ pub struct Swap<'info> {
         address = output_vault.mint
     )]
     pub output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: The user buys during the anti-sniper window, created by the swap when needed.
+    /// Only required for buys while the window is active
+    #[account(
+        mut,
+        seeds = [
+            DEX_WALLET_LIMIT_SEED.as_bytes(),
+            dex_state.key().as_ref(),
+            payer.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub wallet_limit: Option<UncheckedAccount<'info>>,
+    /// CHECK: Optional swap statistics of the user, created by the swap when empty
+    #[account(
+        mut,
+        seeds = [
+            DEX_TRADER_STATS_SEED.as_bytes(),
+            dex_state.key().as_ref(),
+            payer.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub trader_stats: Option<UncheckedAccount<'info>>,
+    /// To create the wallet limit and trader stats accounts
+    pub system_program: Program<'info, System>,
+}
+
+/// Swap accounts where the payer pays or receives lamports for the native mint side.
+/// The native token account only lives for the duration of the swap.
+#[derive(Accounts)]
+pub struct SwapNative<'info> {
+    /// The user performing the swap
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        address = dex_state.load()?.config @ ErrorCode::InvalidConfig,
+    )]
+    pub config: Box<Account<'info, ConfigState>>,
+    #[account(
+        seeds = [DEX_AUTHORITY_MANAGER_SEED.as_bytes()],
+        bump = authority_manager.bump
+    )]
+    pub authority_manager: Box<Account<'info, AuthorityManager>>,
+    /// CHECK: dex vault authority
+    #[account(
+        seeds = [
+            DEX_AUTHORITY_SEED.as_bytes(),
+        ],
+        bump = authority_manager.authority_bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+    /// The program account of the dex in which the swap will be performed
+    #[account(mut)]
+    pub dex_state: AccountLoader<'info, DexState>,
+    /// The price observations of the dex
+    #[account(
+        mut,
+        seeds = [
+            DEX_OBSERVATION_SEED.as_bytes(),
+            dex_state.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+    /// The user token account for the other token of the swap
+    #[account(mut)]
+    pub token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: Native mint token account of the payer, created and closed by the swap
+    #[account(
+        mut,
+        seeds = [
+            DEX_NATIVE_ACCOUNT_SEED.as_bytes(),
+            payer.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub native_token_account: UncheckedAccount<'info>,
+    /// The vault token account for input token
+    #[account(
+        mut,
+        constraint = input_vault.key() == dex_state.load()?.token_0_vault || input_vault.key() == dex_state.load()?.token_1_vault
+    )]
+    pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// The vault token account for output token
+    #[account(
+        mut,
+        constraint = output_vault.key() == dex_state.load()?.token_0_vault || output_vault.key() == dex_state.load()?.token_1_vault
+    )]
+    pub output_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// SPL program for input token transfers
+    pub input_token_program: Interface<'info, TokenInterface>,
+    /// SPL program for output token transfers
+    pub output_token_program: Interface<'info, TokenInterface>,
+    /// The mint of input token
+    #[account(
+        address = input_vault.mint
+    )]
+    pub input_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// The mint of output token
+    #[account(
+        address = output_vault.mint
+    )]
+    pub output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: The user buys during the anti-sniper window, created by the swap when needed.
+    /// Only required for buys while the window is active
+    #[account(
+        mut,
+        seeds = [
+            DEX_WALLET_LIMIT_SEED.as_bytes(),
+            dex_state.key().as_ref(),
+            payer.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub wallet_limit: Option<UncheckedAccount<'info>>,
+    /// CHECK: Optional swap statistics of the user, created by the swap when empty
+    #[account(
+        mut,
+        seeds = [
+            DEX_TRADER_STATS_SEED.as_bytes(),
+            dex_state.key().as_ref(),
+            payer.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub trader_stats: Option<UncheckedAccount<'info>>,
+    /// To create the wallet limit, trader stats and native token accounts
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SwapNative<'info> {
+    /// Create the native token account and wrap the input lamports, returns if the native mint is the input
+    fn open_native_account(&self, bump: u8, wrap_amount: u64) -> Result<bool> {
+        let native_input = if self.input_token_mint.key() == native_mint::ID {
+            true
+        } else if self.output_token_mint.key() == native_mint::ID {
+            false
+        } else {
+            return err!(ErrorCode::InvalidNativeMint);
+        };
+        let (native_mint, token_program) = self.native_side(native_input);
+
+        let payer = self.payer.key();
+        let seeds = [DEX_NATIVE_ACCOUNT_SEED.as_bytes(), payer.as_ref(), &[bump]];
+        create_token_account(
+            &self.payer.to_account_info(),
+            &self.payer.to_account_info(),
+            &self.native_token_account.to_account_info(),
+            &native_mint,
+            &self.system_program.to_account_info(),
+            &token_program,
+            &[seeds.as_slice()],
+        )?;
+
+        if native_input {
+            wrap_native(
+                self.payer.to_account_info(),
+                self.native_token_account.to_account_info(),
+                self.system_program.to_account_info(),
+                token_program,
+                wrap_amount,
+            )?;
+        }
+
+        Ok(native_input)
+    }
+    /// Close the native token account, its balance and rent go back to the payer as lamports
+    fn close_native_account(&self, native_input: bool) -> Result<()> {
+        let (_, token_program) = self.native_side(native_input);
+        close_token_account(
+            self.payer.to_account_info(),
+            self.native_token_account.to_account_info(),
+            self.payer.to_account_info(),
+            token_program,
+            &[],
+        )
+    }
+    fn native_side(&self, native_input: bool) -> (AccountInfo<'info>, AccountInfo<'info>) {
+        if native_input {
+            (
+                self.input_token_mint.to_account_info(),
+                self.input_token_program.to_account_info(),
+            )
+        } else {
+            (
+                self.output_token_mint.to_account_info(),
+                self.output_token_program.to_account_info(),
+            )
+        }
+    }
+}
+
+/// Two swaps through the shared token, the output account of the first swap is the input account of the second one.
+/// The route is limited to two hops.
+#[derive(Accounts)]
+pub struct SwapRoute<'info> {
+    pub first: Swap<'info>,
+    pub second: Swap<'info>,
 }
 
 pub struct Swapper<'info> {
@@ -92,35 +401,158 @@ pub struct Swapper<'info> {
     authority_manager: Box<Account<'info, AuthorityManager>>,
     config: Box<Account<'info, ConfigState>>,
     dex_state: AccountLoader<'info, DexState>,
+    observation_state: AccountLoader<'info, ObservationState>,
     input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     output_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     input_token_program: Interface<'info, TokenInterface>,
     output_token_program: Interface<'info, TokenInterface>,
     input_token_mint: Box<InterfaceAccount<'info, Mint>>,
     output_token_mint: Box<InterfaceAccount<'info, Mint>>,
-    input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
-    output_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    input_token_account: AccountInfo<'info>,
+    output_token_account: AccountInfo<'info>,
     payer: Signer<'info>,
+    referrer: Option<AccountInfo<'info>>,
+    hook_accounts: Vec<AccountInfo<'info>>,
+    /// Limit order owning the escrow input token account of a fill
+    escrow_order: Option<Account<'info, LimitOrderState>>,
+    wallet_limit: Option<UncheckedAccount<'info>>,
+    wallet_limit_bump: u8,
+    trader_stats: Option<UncheckedAccount<'info>>,
+    trader_stats_bump: u8,
+    system_program: Program<'info, System>,
 }
 
 impl<'info> Swapper<'info> {
     pub fn from_ctx(ctx: &Context<'_, '_, '_, 'info, Swap<'info>>) -> Self {
+        let (referrer, hook_accounts) = split_remaining_accounts(ctx.remaining_accounts);
+        Self::from_accounts(ctx.accounts, &ctx.bumps, referrer, hook_accounts)
+    }
+    /// The native token account of the payer replaces its token account of the native mint
+    pub fn from_native_ctx(
+        ctx: &Context<'_, '_, '_, 'info, SwapNative<'info>>,
+        native_input: bool,
+    ) -> Self {
+        let accounts = &ctx.accounts;
+        let (input_token_account, output_token_account) = if native_input {
+            (
+                accounts.native_token_account.to_account_info(),
+                accounts.token_account.to_account_info(),
+            )
+        } else {
+            (
+                accounts.token_account.to_account_info(),
+                accounts.native_token_account.to_account_info(),
+            )
+        };
+        let (referrer, hook_accounts) = split_remaining_accounts(ctx.remaining_accounts);
+
+        Self {
+            authority: accounts.authority.clone(),
+            authority_manager: accounts.authority_manager.clone(),
+            config: accounts.config.clone(),
+            dex_state: accounts.dex_state.clone(),
+            observation_state: accounts.observation_state.clone(),
+            input_vault: accounts.input_vault.clone(),
+            output_vault: accounts.output_vault.clone(),
+            input_token_program: accounts.input_token_program.clone(),
+            output_token_program: accounts.output_token_program.clone(),
+            input_token_mint: accounts.input_token_mint.clone(),
+            output_token_mint: accounts.output_token_mint.clone(),
+            input_token_account,
+            output_token_account,
+            payer: accounts.payer.clone(),
+            referrer,
+            hook_accounts,
+            escrow_order: None,
+            wallet_limit: accounts.wallet_limit.clone(),
+            wallet_limit_bump: ctx.bumps.wallet_limit,
+            trader_stats: accounts.trader_stats.clone(),
+            trader_stats_bump: ctx.bumps.trader_stats,
+            system_program: accounts.system_program.clone(),
+        }
+    }
+    pub fn from_accounts(
+        accounts: &Swap<'info>,
+        bumps: &SwapBumps,
+        referrer: Option<AccountInfo<'info>>,
+        hook_accounts: Vec<AccountInfo<'info>>,
+    ) -> Self {
+        Self {
+            authority: accounts.authority.clone(),
+            authority_manager: accounts.authority_manager.clone(),
+            config: accounts.config.clone(),
+            dex_state: accounts.dex_state.clone(),
+            observation_state: accounts.observation_state.clone(),
+            input_vault: accounts.input_vault.clone(),
+            output_vault: accounts.output_vault.clone(),
+            input_token_program: accounts.input_token_program.clone(),
+            output_token_program: accounts.output_token_program.clone(),
+            input_token_mint: accounts.input_token_mint.clone(),
+            output_token_mint: accounts.output_token_mint.clone(),
+            input_token_account: accounts.input_token_account.to_account_info(),
+            output_token_account: accounts.output_token_account.to_account_info(),
+            payer: accounts.payer.clone(),
+            referrer,
+            hook_accounts,
+            escrow_order: None,
+            wallet_limit: accounts.wallet_limit.clone(),
+            wallet_limit_bump: bumps.wallet_limit,
+            trader_stats: accounts.trader_stats.clone(),
+            trader_stats_bump: bumps.trader_stats,
+            system_program: accounts.system_program.clone(),
+        }
+    }
+    /// The escrow of a limit order is the input, the keeper filling the order pays for the swap
+    pub fn from_fill(
+        accounts: &FillOrders<'info>,
+        order: Account<'info, LimitOrderState>,
+        escrow: AccountInfo<'info>,
+        output_token_account: AccountInfo<'info>,
+        hook_accounts: Vec<AccountInfo<'info>>,
+    ) -> Self {
         Self {
-            authority: ctx.accounts.authority.clone(),
-            authority_manager: ctx.accounts.authority_manager.clone(),
-            config: ctx.accounts.config.clone(),
-            dex_state: ctx.accounts.dex_state.clone(),
-            input_vault: ctx.accounts.input_vault.clone(),
-            output_vault: ctx.accounts.output_vault.clone(),
-            input_token_program: ctx.accounts.input_token_program.clone(),
-            output_token_program: ctx.accounts.output_token_program.clone(),
-            input_token_mint: ctx.accounts.input_token_mint.clone(),
-            output_token_mint: ctx.accounts.output_token_mint.clone(),
-            input_token_account: ctx.accounts.input_token_account.clone(),
-            output_token_account: ctx.accounts.output_token_account.clone(),
-            payer: ctx.accounts.payer.clone(),
+            authority: accounts.authority.clone(),
+            authority_manager: accounts.authority_manager.clone(),
+            config: accounts.config.clone(),
+            dex_state: accounts.dex_state.clone(),
+            observation_state: accounts.observation_state.clone(),
+            input_vault: accounts.input_vault.clone(),
+            output_vault: accounts.output_vault.clone(),
+            input_token_program: accounts.input_token_program.clone(),
+            output_token_program: accounts.output_token_program.clone(),
+            input_token_mint: accounts.input_token_mint.clone(),
+            output_token_mint: accounts.output_token_mint.clone(),
+            input_token_account: escrow,
+            output_token_account,
+            payer: accounts.keeper.clone(),
+            referrer: None,
+            hook_accounts,
+            escrow_order: Some(order),
+            wallet_limit: None,
+            wallet_limit_bump: 0,
+            trader_stats: None,
+            trader_stats_bump: 0,
+            system_program: accounts.system_program.clone(),
         }
     }
+    /// Referrer token account receives its fee share in the input token
+    fn load_referrer(&self) -> Result<Option<AccountInfo<'info>>> {
+        let Some(referrer) = &self.referrer else {
+            return Ok(None);
+        };
+
+        if referrer.owner != self.input_token_program.key
+            || referrer.key() == self.input_vault.key()
+        {
+            return err!(ErrorCode::InvalidReferrer);
+        }
+        let token_account = TokenAccount::try_deserialize(&mut &referrer.data.borrow()[..])?;
+        if token_account.mint != self.input_token_mint.key() {
+            return err!(ErrorCode::InvalidReferrer);
+        }
+
+        Ok(Some(referrer.clone()))
+    }
     fn calculate_trade_amounts_and_price_before_swap(
         &self,
         dex_state: &mut RefMut<'_, DexState>,
@@ -129,39 +561,39 @@ impl<'info> Swapper<'info> {
             trade_direction,
             total_input_token_amount,
             total_output_token_amount,
-            token_0_price_x64,
-            token_1_price_x64,
+            token_0_price_x32,
+            token_1_price_x32,
         ) = match (self.input_vault.key(), self.output_vault.key()) {
             (input, output)
                 if input == dex_state.token_0_vault && output == dex_state.token_1_vault =>
             {
                 let (total_input_token_amount, total_output_token_amount) = dex_state
-                    .vault_amount_without_fee(self.input_vault.amount, self.output_vault.amount);
-                let (token_0_price_x64, token_1_price_x64) =
+                    .vault_amount_with_virtual(self.input_vault.amount, self.output_vault.amount);
+                let (token_0_price_x32, token_1_price_x32) =
                     dex_state.token_price_x32(self.input_vault.amount, self.output_vault.amount);
 
                 (
                     TradeDirection::ZeroForOne,
                     total_input_token_amount,
                     total_output_token_amount,
-                    token_0_price_x64,
-                    token_1_price_x64,
+                    token_0_price_x32,
+                    token_1_price_x32,
                 )
             }
             (input, output)
                 if input == dex_state.token_1_vault && output == dex_state.token_0_vault =>
             {
                 let (total_output_token_amount, total_input_token_amount) = dex_state
-                    .vault_amount_without_fee(self.output_vault.amount, self.input_vault.amount);
-                let (token_0_price_x64, token_1_price_x64) =
+                    .vault_amount_with_virtual(self.output_vault.amount, self.input_vault.amount);
+                let (token_0_price_x32, token_1_price_x32) =
                     dex_state.token_price_x32(self.output_vault.amount, self.input_vault.amount);
 
                 (
                     TradeDirection::OneForZero,
                     total_input_token_amount,
                     total_output_token_amount,
-                    token_0_price_x64,
-                    token_1_price_x64,
+                    token_0_price_x32,
+                    token_1_price_x32,
                 )
             }
             _ => {
@@ -173,238 +605,298 @@ impl<'info> Swapper<'info> {
             trade_direction,
             total_input_token_amount,
             total_output_token_amount,
-            token_0_price_x64,
-            token_1_price_x64,
+            token_0_price_x32,
+            token_1_price_x32,
+        })
+    }
+    /// Quote the swap against the current accounts, mint data is only borrowed during the quote
+    fn quote(
+        &self,
+        dex_state: &DexState,
+        trade_direction: TradeDirection,
+        with_referrer: bool,
+        quote: impl FnOnce(&SwapQuoter) -> Result<SwapQuote>,
+    ) -> Result<SwapQuote> {
+        let input_mint_info = self.input_token_mint.to_account_info();
+        let output_mint_info = self.output_token_mint.to_account_info();
+        let input_mint_data = input_mint_info.try_borrow_data()?;
+        let output_mint_data = output_mint_info.try_borrow_data()?;
+
+        quote(&SwapQuoter {
+            dex_state,
+            config: &self.config,
+            trade_direction,
+            input_vault_amount: self.input_vault.amount,
+            output_vault_amount: self.output_vault.amount,
+            input_mint: MintData {
+                program: *input_mint_info.owner,
+                data: &input_mint_data,
+            },
+            output_mint: MintData {
+                program: *output_mint_info.owner,
+                data: &output_mint_data,
+            },
+            epoch: Clock::get()?.epoch,
+            timestamp: u64::try_from(Clock::get()?.unix_timestamp).unwrap(),
+            with_referrer,
         })
     }
     pub fn try_swap_base_input(&mut self, amount_in: u64, minimum_amount_out: u64) -> Result<()> {
         let dex_id = self.dex_state.key();
-        let dex_state = &mut self.dex_state.load_mut()?;
+        let dex_loader = self.dex_state.clone();
+        let dex_state = &mut dex_loader.load_mut()?;
 
         if dex_state.is_launched {
-            return err!(ErrorCode::DexReadyToLaunch);
+            return err!(ErrorCode::DexLaunched);
         }
 
-        if dex_state.is_launched {
-            return err!(ErrorCode::DexLaunched);
+        if dex_state.is_ready_to_launch {
+            return err!(ErrorCode::DexReadyToLaunch);
         }
 
-        let transfer_fee = get_transfer_fee(&self.input_token_mint.to_account_info(), amount_in)?;
-        // Take transfer fees into account for actual amount transferred in
-        let actual_amount_in = amount_in.saturating_sub(transfer_fee);
-        require_gt!(actual_amount_in, 0);
+        if dex_state.is_refunding(u64::try_from(Clock::get()?.unix_timestamp).unwrap()) {
+            return err!(ErrorCode::DexRefunding);
+        }
 
         let SwapCalculation {
             trade_direction,
-            total_input_token_amount,
-            total_output_token_amount,
+            token_0_price_x32,
+            token_1_price_x32,
             ..
         } = self.calculate_trade_amounts_and_price_before_swap(dex_state)?;
 
-        let result = CurveCalculator::swap_base_input(
-            u128::from(actual_amount_in),
-            u128::from(total_input_token_amount),
-            u128::from(total_output_token_amount),
-            self.config.swap_fee_rate,
-        )
-        .ok_or(ErrorCode::ZeroTradingTokens)?;
+        self.observation_state.load_mut()?.update(
+            u64::try_from(Clock::get()?.unix_timestamp).unwrap(),
+            token_0_price_x32,
+            token_1_price_x32,
+        );
+
+        let referrer = self.load_referrer()?;
+        let quote = self.quote(dex_state, trade_direction, referrer.is_some(), |quoter| {
+            quoter.quote_base_input(amount_in, minimum_amount_out)
+        })?;
 
         #[cfg(feature = "enable-log")]
         msg!(
             "swap source_amount_swapped:{}, destination_amount_swapped:{}, protocol_fee:{}, constant_before:{}, constant_after:{}, base_input:{}",
-            result.source_amount_swapped,
-            result.destination_amount_swapped,
-            result.protocol_fee,
-            result.constant_before,
-            result.constant_after,
+            quote.input_amount,
+            quote.output_amount,
+            quote.protocol_fee,
+            quote.constant_before,
+            quote.constant_after,
             true
         );
 
-        require_eq!(
-            u64::try_from(result.source_amount_swapped).unwrap(),
-            actual_amount_in
+        self.execute(dex_id, dex_state, trade_direction, referrer, &quote, true)
+    }
+    pub fn try_swap_base_output(
+        &mut self,
+        max_amount_in: u64,
+        amount_out_less_fee: u64,
+    ) -> Result<()> {
+        let dex_id = self.dex_state.key();
+        let dex_loader = self.dex_state.clone();
+        let dex_state = &mut dex_loader.load_mut()?;
+
+        if dex_state.is_launched {
+            return err!(ErrorCode::DexLaunched);
+        }
+
+        if dex_state.is_ready_to_launch {
+            return err!(ErrorCode::DexReadyToLaunch);
+        }
+
+        if dex_state.is_refunding(u64::try_from(Clock::get()?.unix_timestamp).unwrap()) {
+            return err!(ErrorCode::DexRefunding);
+        }
+
+        let SwapCalculation {
+            trade_direction,
+            token_0_price_x32,
+            token_1_price_x32,
+            ..
+        } = self.calculate_trade_amounts_and_price_before_swap(dex_state)?;
+
+        self.observation_state.load_mut()?.update(
+            u64::try_from(Clock::get()?.unix_timestamp).unwrap(),
+            token_0_price_x32,
+            token_1_price_x32,
         );
 
-        let (input_transfer_amount, input_transfer_fee) = (amount_in, transfer_fee);
-        let (output_transfer_amount, output_transfer_fee) = {
-            let amount_out = u64::try_from(result.destination_amount_swapped).unwrap();
-            let transfer_fee =
-                get_transfer_fee(&self.output_token_mint.to_account_info(), amount_out)?;
-            let amount_received = amount_out.checked_sub(transfer_fee).unwrap();
-            require_gt!(amount_received, 0);
-            require_gte!(
-                amount_received,
-                minimum_amount_out,
-                ErrorCode::ExceededSlippage
-            );
-            (amount_out, transfer_fee)
-        };
+        let referrer = self.load_referrer()?;
+        let quote = self.quote(dex_state, trade_direction, referrer.is_some(), |quoter| {
+            quoter.quote_base_output(max_amount_in, amount_out_less_fee)
+        })?;
 
-        let protocol_fee = u64::try_from(result.protocol_fee).unwrap();
+        self.execute(dex_id, dex_state, trade_direction, referrer, &quote, false)
+    }
+    /// Buys during the anti-sniper window are limited per swap and per wallet
+    fn check_sniper_limits(
+        &self,
+        dex_id: Pubkey,
+        dex_state: &DexState,
+        amount_in: u64,
+    ) -> Result<()> {
+        require_gte!(
+            dex_state.sniper_max_swap_input,
+            amount_in,
+            ErrorCode::ExceededSwapInputLimit
+        );
 
-        match trade_direction {
-            TradeDirection::ZeroForOne => {
-                dex_state.swap_fees_token_0 = dex_state
-                    .swap_fees_token_0
-                    .checked_add(protocol_fee)
-                    .unwrap();
-            }
-            TradeDirection::OneForZero => {
-                dex_state.swap_fees_token_1 = dex_state
-                    .swap_fees_token_1
-                    .checked_add(protocol_fee)
-                    .unwrap();
-            }
+        // limit order fills skip buys of the anti-sniper window
+        let Some(wallet_limit_info) = &self.wallet_limit else {
+            return err!(ErrorCode::MissingWalletLimit);
         };
 
-        require_gte!(result.constant_after, result.constant_before);
+        if *wallet_limit_info.owner == system_program::ID {
+            self.create_wallet_limit(dex_id, wallet_limit_info)?;
+        }
 
-        transfer_from_user_to_dex_vault(
-            self.payer.to_account_info(),
-            self.input_token_account.to_account_info(),
-            self.input_vault.to_account_info(),
-            self.input_token_mint.to_account_info(),
-            self.input_token_program.to_account_info(),
-            input_transfer_amount,
-            self.input_token_mint.decimals,
-        )?;
+        let mut data = wallet_limit_info.try_borrow_mut_data()?;
+        let mut wallet_limit = WalletLimitState::try_deserialize(&mut &data[..])?;
+        wallet_limit.input_amount = wallet_limit.input_amount.checked_add(amount_in).unwrap();
+        require_gte!(
+            dex_state.sniper_max_wallet_input,
+            wallet_limit.input_amount,
+            ErrorCode::ExceededWalletInputLimit
+        );
 
-        // dex authority pda signer seeds
+        wallet_limit.try_serialize(&mut &mut data[..])
+    }
+    fn create_wallet_limit(
+        &self,
+        dex_id: Pubkey,
+        wallet_limit_info: &UncheckedAccount<'info>,
+    ) -> Result<()> {
+        let payer = self.payer.key();
         let seeds = [
-            DEX_AUTHORITY_SEED.as_bytes(),
-            &[self.authority_manager.authority_bump],
+            DEX_WALLET_LIMIT_SEED.as_bytes(),
+            dex_id.as_ref(),
+            payer.as_ref(),
+            &[self.wallet_limit_bump],
         ];
         let signer_seeds = &[seeds.as_slice()];
 
-        transfer_from_dex_vault_to_user(
-            self.authority.to_account_info(),
-            self.output_vault.to_account_info(),
-            self.output_token_account.to_account_info(),
-            self.output_token_mint.to_account_info(),
-            self.output_token_program.to_account_info(),
-            output_transfer_amount,
-            self.output_token_mint.decimals,
+        create_pda_account(
+            &self.payer.to_account_info(),
+            &wallet_limit_info.to_account_info(),
+            &self.system_program.to_account_info(),
+            WalletLimitState::LEN,
+            &crate::id(),
             signer_seeds,
         )?;
 
-        self.input_vault.reload()?;
-        self.output_vault.reload()?;
-
-        let vault_reserve_amount = dex_state.get_vault_reserve_amount(
-            self.input_vault.amount,
-            self.output_vault.amount,
-            trade_direction,
-        );
-
-        let remaining_tokens = dex_state.get_remaining_tokens(vault_reserve_amount);
-
-        emit!(SwapEvent {
+        let wallet_limit = WalletLimitState {
+            bump: self.wallet_limit_bump,
             dex_id,
-            input_vault_before: total_input_token_amount,
-            output_vault_before: total_output_token_amount,
-            input_amount: u64::try_from(result.source_amount_swapped).unwrap(),
-            output_amount: u64::try_from(result.destination_amount_swapped).unwrap(),
-            input_transfer_fee,
-            output_transfer_fee,
-            remaining_tokens,
-            base_input: true,
-            zero_to_one: trade_direction.into(),
-        });
-
-        if dex_state.is_reached_reserve_bound(vault_reserve_amount) {
-            dex_state.is_ready_to_launch = true;
-            emit!(DexIsReadyToLaunchEvent { dex_id });
-        }
-
-        Ok(())
+            wallet: payer,
+            input_amount: 0,
+        };
+        wallet_limit.try_serialize(&mut &mut wallet_limit_info.try_borrow_mut_data()?[..])
     }
-    pub fn try_swap_base_output(
-        &mut self,
-        max_amount_in: u64,
-        amount_out_less_fee: u64,
+    /// Add the swap to the trader stats when the user passed them, the reserve token amount is the volume
+    fn record_trader_stats(
+        &self,
+        dex_id: Pubkey,
+        dex_state: &DexState,
+        trade_direction: TradeDirection,
+        quote: &SwapQuote,
     ) -> Result<()> {
-        let dex_id = self.dex_state.key();
-        let dex_state = &mut self.dex_state.load_mut()?;
+        let Some(trader_stats_info) = &self.trader_stats else {
+            return Ok(());
+        };
 
-        if dex_state.is_launched {
-            return err!(ErrorCode::DexReadyToLaunch);
+        if *trader_stats_info.owner == system_program::ID {
+            self.create_trader_stats(dex_id, trader_stats_info)?;
         }
 
-        if dex_state.is_launched {
-            return err!(ErrorCode::DexLaunched);
-        }
+        let is_buy = dex_state.is_buy(trade_direction);
+        let volume = if is_buy {
+            quote.input_transfer_amount
+        } else {
+            quote.output_amount
+        };
 
-        let out_transfer_fee = get_transfer_inverse_fee(
-            &self.output_token_mint.to_account_info(),
-            amount_out_less_fee,
-        )?;
-        let actual_amount_out = amount_out_less_fee.checked_add(out_transfer_fee).unwrap();
+        let mut data = trader_stats_info.try_borrow_mut_data()?;
+        let mut trader_stats = TraderStatsState::try_deserialize(&mut &data[..])?;
+        trader_stats.record_trade(
+            is_buy,
+            volume,
+            u64::try_from(Clock::get()?.unix_timestamp).unwrap(),
+        );
 
-        let SwapCalculation {
-            trade_direction,
-            total_input_token_amount,
-            total_output_token_amount,
-            ..
-        } = self.calculate_trade_amounts_and_price_before_swap(dex_state)?;
+        trader_stats.try_serialize(&mut &mut data[..])
+    }
+    fn create_trader_stats(
+        &self,
+        dex_id: Pubkey,
+        trader_stats_info: &UncheckedAccount<'info>,
+    ) -> Result<()> {
+        let payer = self.payer.key();
+        let seeds = [
+            DEX_TRADER_STATS_SEED.as_bytes(),
+            dex_id.as_ref(),
+            payer.as_ref(),
+            &[self.trader_stats_bump],
+        ];
+        let signer_seeds = &[seeds.as_slice()];
 
-        let result = CurveCalculator::swap_base_output(
-            u128::from(actual_amount_out),
-            u128::from(total_input_token_amount),
-            u128::from(total_output_token_amount),
-            self.config.swap_fee_rate,
-        )
-        .ok_or(ErrorCode::ZeroTradingTokens)?;
-
-        // Re-calculate the source amount swapped based on what the curve says
-        let (input_transfer_amount, input_transfer_fee) = {
-            let source_amount_swapped = u64::try_from(result.source_amount_swapped).unwrap();
-            require_gt!(source_amount_swapped, 0);
-            let transfer_fee = get_transfer_inverse_fee(
-                &self.input_token_mint.to_account_info(),
-                source_amount_swapped,
-            )?;
-            let input_transfer_amount = source_amount_swapped.checked_add(transfer_fee).unwrap();
-            require_gte!(
-                max_amount_in,
-                input_transfer_amount,
-                ErrorCode::ExceededSlippage
-            );
-            (input_transfer_amount, transfer_fee)
-        };
-        require_eq!(
-            u64::try_from(result.destination_amount_swapped).unwrap(),
-            actual_amount_out
-        );
-        let (output_transfer_amount, output_transfer_fee) = (actual_amount_out, out_transfer_fee);
+        create_pda_account(
+            &self.payer.to_account_info(),
+            &trader_stats_info.to_account_info(),
+            &self.system_program.to_account_info(),
+            TraderStatsState::LEN,
+            &crate::id(),
+            signer_seeds,
+        )?;
 
-        let protocol_fee = u64::try_from(result.protocol_fee).unwrap();
+        let trader_stats = TraderStatsState {
+            bump: self.trader_stats_bump,
+            dex_id,
+            trader: payer,
+            ..Default::default()
+        };
+        trader_stats.try_serialize(&mut &mut trader_stats_info.try_borrow_mut_data()?[..])
+    }
+    /// Book the fees and transfer the quoted amounts
+    fn execute(
+        &mut self,
+        dex_id: Pubkey,
+        dex_state: &mut RefMut<'_, DexState>,
+        trade_direction: TradeDirection,
+        referrer: Option<AccountInfo<'info>>,
+        quote: &SwapQuote,
+        base_input: bool,
+    ) -> Result<()> {
+        if dex_state.is_buy(trade_direction)
+            && dex_state.is_sniper_window(u64::try_from(Clock::get()?.unix_timestamp).unwrap())
+        {
+            self.check_sniper_limits(dex_id, dex_state, quote.input_transfer_amount)?;
+        }
 
         match trade_direction {
             TradeDirection::ZeroForOne => {
                 dex_state.swap_fees_token_0 = dex_state
                     .swap_fees_token_0
-                    .checked_add(protocol_fee)
+                    .checked_add(quote.protocol_fee)
+                    .unwrap();
+                dex_state.creator_fees_token_0 = dex_state
+                    .creator_fees_token_0
+                    .checked_add(quote.creator_fee)
                     .unwrap();
             }
             TradeDirection::OneForZero => {
                 dex_state.swap_fees_token_1 = dex_state
                     .swap_fees_token_1
-                    .checked_add(protocol_fee)
+                    .checked_add(quote.protocol_fee)
+                    .unwrap();
+                dex_state.creator_fees_token_1 = dex_state
+                    .creator_fees_token_1
+                    .checked_add(quote.creator_fee)
                     .unwrap();
             }
         };
 
-        require_gte!(result.constant_after, result.constant_before);
-        transfer_from_user_to_dex_vault(
-            self.payer.to_account_info(),
-            self.input_token_account.to_account_info(),
-            self.input_vault.to_account_info(),
-            self.input_token_mint.to_account_info(),
-            self.input_token_program.to_account_info(),
-            input_transfer_amount,
-            self.input_token_mint.decimals,
-        )?;
-
         // dex authority pda signer seeds
         let seeds = [
             DEX_AUTHORITY_SEED.as_bytes(),
@@ -412,17 +904,64 @@ impl<'info> Swapper<'info> {
         ];
         let signer_seeds = &[seeds.as_slice()];
 
+        if let Some(order) = &self.escrow_order {
+            // order pda signer seeds
+            let order_id = order.order_id.to_be_bytes();
+            let order_seeds = [
+                DEX_LIMIT_ORDER_SEED.as_bytes(),
+                order.dex_id.as_ref(),
+                order.owner.as_ref(),
+                order_id.as_ref(),
+                &[order.bump],
+            ];
+            transfer_from_dex_vault_to_user(
+                order.to_account_info(),
+                self.input_token_account.to_account_info(),
+                self.input_vault.to_account_info(),
+                &self.input_token_mint,
+                self.input_token_program.to_account_info(),
+                quote.input_transfer_amount,
+                &[order_seeds.as_slice()],
+                &self.hook_accounts,
+            )?;
+        } else {
+            transfer_from_user_to_dex_vault(
+                self.payer.to_account_info(),
+                self.input_token_account.to_account_info(),
+                self.input_vault.to_account_info(),
+                &self.input_token_mint,
+                self.input_token_program.to_account_info(),
+                quote.input_transfer_amount,
+                &self.hook_accounts,
+            )?;
+        }
+
         transfer_from_dex_vault_to_user(
             self.authority.to_account_info(),
             self.output_vault.to_account_info(),
             self.output_token_account.to_account_info(),
-            self.output_token_mint.to_account_info(),
+            &self.output_token_mint,
             self.output_token_program.to_account_info(),
-            output_transfer_amount,
-            self.output_token_mint.decimals,
+            quote.output_amount,
             signer_seeds,
+            &self.hook_accounts,
         )?;
 
+        if let Some(referrer) = &referrer {
+            transfer_from_dex_vault_to_user(
+                self.authority.to_account_info(),
+                self.input_vault.to_account_info(),
+                referrer.to_account_info(),
+                &self.input_token_mint,
+                self.input_token_program.to_account_info(),
+                quote.referral_fee,
+                signer_seeds,
+                &self.hook_accounts,
+            )?;
+        }
+
+        self.record_trader_stats(dex_id, dex_state, trade_direction, quote)?;
+
         self.input_vault.reload()?;
         self.output_vault.reload()?;
 
@@ -436,14 +975,18 @@ impl<'info> Swapper<'info> {
 
         emit!(SwapEvent {
             dex_id,
-            input_vault_before: total_input_token_amount,
-            output_vault_before: total_output_token_amount,
-            input_amount: u64::try_from(result.source_amount_swapped).unwrap(),
-            output_amount: u64::try_from(result.destination_amount_swapped).unwrap(),
-            input_transfer_fee,
-            output_transfer_fee,
+            input_vault_before: quote.total_input_token_amount,
+            output_vault_before: quote.total_output_token_amount,
+            input_amount: quote.input_amount,
+            output_amount: quote.output_amount,
+            input_transfer_fee: quote.input_transfer_fee,
+            output_transfer_fee: quote.output_transfer_fee,
             remaining_tokens,
-            base_input: false,
+            protocol_fee: quote.protocol_fee,
+            creator_fee: quote.creator_fee,
+            referral_fee: quote.referral_fee,
+            unfilled_amount: quote.unfilled_amount,
+            base_input,
             zero_to_one: trade_direction.into(),
         });
 
@@ -456,10 +999,24 @@ impl<'info> Swapper<'info> {
     }
 }
 
+/// Swap remaining accounts are the optional referrer, the dex program id standing for none,
+/// followed by the extra accounts of the mint transfer hooks
+fn split_remaining_accounts<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+) -> (Option<AccountInfo<'info>>, Vec<AccountInfo<'info>>) {
+    match remaining_accounts.split_first() {
+        Some((first, hook_accounts)) => (
+            (first.key() != crate::id()).then(|| first.clone()),
+            hook_accounts.to_vec(),
+        ),
+        None => (None, Vec::new()),
+    }
+}
+
 pub struct SwapCalculation {
     pub trade_direction: TradeDirection,
     pub total_input_token_amount: u64,
     pub total_output_token_amount: u64,
-    pub token_0_price_x64: u128,
-    pub token_1_price_x64: u128,
+    pub token_0_price_x32: u128,
+    pub token_1_price_x32: u128,
 }
@@ -1,11 +1,16 @@
+#[cfg(feature = "client")]
+pub mod client;
 pub mod curve;
 pub mod error;
 pub mod instructions;
+pub mod quote;
 pub mod states;
 pub mod utils;
 
 use anchor_lang::prelude::*;
 use instructions::*;
+use quote::SwapQuote;
+use states::FeeRecipient;
 
 declare_id!("8454oEni7sVVVjS4be7V7d92ShgcjtiRcyDb82vcRmDQ");
 
@@ -35,8 +40,8 @@ pub mod dex {
         instructions::update_authority_manager_cpi_authority(ctx, cpi_authority)
     }
 
-    pub fn initialize_config(ctx: Context<InitializeConfigState>) -> Result<()> {
-        instructions::initialize_config(ctx)
+    pub fn initialize_config(ctx: Context<InitializeConfigState>, index: u16) -> Result<()> {
+        instructions::initialize_config(ctx, index)
     }
 
     pub fn update_config_swap_fee_rate(
@@ -67,12 +72,150 @@ pub mod dex {
         instructions::update_config_initial_reserve(ctx, initial_reserve)
     }
 
-    pub fn withdraw_dex_fee(ctx: Context<WithdrawDexFee>) -> Result<()> {
+    pub fn update_config_curve_type(ctx: Context<UpdateConfigState>, curve_type: u8) -> Result<()> {
+        instructions::update_config_curve_type(ctx, curve_type)
+    }
+
+    pub fn update_config_virtual_reserve(
+        ctx: Context<UpdateConfigState>,
+        virtual_reserve: u64,
+    ) -> Result<()> {
+        instructions::update_config_virtual_reserve(ctx, virtual_reserve)
+    }
+
+    pub fn update_config_virtual_supply(
+        ctx: Context<UpdateConfigState>,
+        virtual_supply: u64,
+    ) -> Result<()> {
+        instructions::update_config_virtual_supply(ctx, virtual_supply)
+    }
+
+    pub fn update_config_creator_fee_rate(
+        ctx: Context<UpdateConfigState>,
+        creator_fee_rate: u64,
+    ) -> Result<()> {
+        instructions::update_config_creator_fee_rate(ctx, creator_fee_rate)
+    }
+
+    pub fn update_config_referral_fee_rate(
+        ctx: Context<UpdateConfigState>,
+        referral_fee_rate: u64,
+    ) -> Result<()> {
+        instructions::update_config_referral_fee_rate(ctx, referral_fee_rate)
+    }
+
+    pub fn update_config_disabled(ctx: Context<UpdateConfigState>, disabled: bool) -> Result<()> {
+        instructions::update_config_disabled(ctx, disabled)
+    }
+
+    pub fn update_config_sniper_protection(
+        ctx: Context<UpdateConfigState>,
+        sniper_window: u64,
+        sniper_max_swap_input: u64,
+        sniper_max_wallet_input: u64,
+    ) -> Result<()> {
+        instructions::update_config_sniper_protection(
+            ctx,
+            sniper_window,
+            sniper_max_swap_input,
+            sniper_max_wallet_input,
+        )
+    }
+
+    pub fn update_config_launch_window(
+        ctx: Context<UpdateConfigState>,
+        launch_window: u64,
+    ) -> Result<()> {
+        instructions::update_config_launch_window(ctx, launch_window)
+    }
+
+    pub fn update_config_launch_lamports(
+        ctx: Context<UpdateConfigState>,
+        launch_lamports: u64,
+        keeper_reward: u64,
+    ) -> Result<()> {
+        instructions::update_config_launch_lamports(ctx, launch_lamports, keeper_reward)
+    }
+
+    pub fn update_config_launch_target(
+        ctx: Context<UpdateConfigState>,
+        launch_target: u8,
+    ) -> Result<()> {
+        instructions::update_config_launch_target(ctx, launch_target)
+    }
+
+    pub fn update_config_lp_policy(
+        ctx: Context<UpdateConfigState>,
+        lp_burn_rate: u64,
+        lp_lock_cliff: u64,
+        lp_vesting_period: u64,
+    ) -> Result<()> {
+        instructions::update_config_lp_policy(ctx, lp_burn_rate, lp_lock_cliff, lp_vesting_period)
+    }
+
+    pub fn update_config_sniper_fee(
+        ctx: Context<UpdateConfigState>,
+        sniper_fee_rate: u64,
+        sniper_fee_period: u64,
+        sniper_fee_steps: u64,
+    ) -> Result<()> {
+        instructions::update_config_sniper_fee(
+            ctx,
+            sniper_fee_rate,
+            sniper_fee_period,
+            sniper_fee_steps,
+        )
+    }
+
+    /// Withdraw the protocol fees of the dex split between the fee recipients
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `remaining_accounts` - token_0 and token_1 accounts of each fee recipient in order,
+    ///   then the extra accounts of the mint transfer hooks
+    ///
+    pub fn withdraw_dex_fee<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawDexFee<'info>>,
+    ) -> Result<()> {
         instructions::withdraw_dex_fee(ctx)
     }
 
-    pub fn initialize_dex(
-        ctx: Context<InitializeDex>,
+    pub fn update_fee_recipients(
+        ctx: Context<UpdateFeeRecipients>,
+        recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        instructions::update_fee_recipients(ctx, recipients)
+    }
+
+    /// Withdraw the pool creator share of the swap fees
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `remaining_accounts` - Extra accounts of the mint transfer hooks
+    ///
+    pub fn withdraw_creator_fee<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawCreatorFee<'info>>,
+    ) -> Result<()> {
+        instructions::withdraw_creator_fee(ctx)
+    }
+
+    pub fn claim_locked_lp(ctx: Context<ClaimLockedLp>) -> Result<()> {
+        instructions::claim_locked_lp(ctx)
+    }
+
+    /// Create a dex seeded from the payer vaults
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `init_amount` - supply tokens of the dex
+    /// * `vault_for_reserve_bound` - whether vault_1 is the reserve vault
+    /// * `remaining_accounts` - Extra accounts of the mint transfer hooks
+    ///
+    pub fn initialize_dex<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeDex<'info>>,
         init_amount: u64,
         vault_for_reserve_bound: bool,
     ) -> Result<()> {
@@ -86,6 +229,8 @@ pub mod dex {
     /// * `ctx`- The context of accounts
     /// * `amount_in` - input amount to transfer, output to DESTINATION is based on the exchange rate
     /// * `minimum_amount_out` - Minimum amount of output token, prevents excessive slippage
+    /// * `remaining_accounts` - Optional referrer token account of the input mint, the dex program id
+    ///   stands for no referrer when the extra accounts of the mint transfer hooks follow
     ///
     pub fn swap_base_input<'info>(
         ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
@@ -102,6 +247,8 @@ pub mod dex {
     /// * `ctx`- The context of accounts
     /// * `max_amount_in` - input amount prevents excessive slippage
     /// * `amount_out` - amount of output token
+    /// * `remaining_accounts` - Optional referrer token account of the input mint, the dex program id
+    ///   stands for no referrer when the extra accounts of the mint transfer hooks follow
     ///
     pub fn swap_base_output<'info>(
         ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
@@ -111,7 +258,227 @@ pub mod dex {
         instructions::swap_base_output(&ctx, max_amount_in, amount_out)
     }
 
+    /// Swap base input with lamports for the native mint side of the dex
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `amount_in` - input amount to transfer, lamports when the input is the native mint
+    /// * `minimum_amount_out` - Minimum amount of output token, prevents excessive slippage
+    /// * `remaining_accounts` - Optional referrer token account of the input mint, the dex program id
+    ///   stands for no referrer when the extra accounts of the mint transfer hooks follow
+    ///
+    pub fn swap_base_input_native<'info>(
+        ctx: Context<'_, '_, '_, 'info, SwapNative<'info>>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
+        instructions::swap_base_input_native(ctx, amount_in, minimum_amount_out)
+    }
+
+    /// Swap base output with lamports for the native mint side of the dex
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `max_amount_in` - input amount prevents excessive slippage, lamports when the input is the native mint
+    /// * `amount_out` - amount of output token
+    /// * `remaining_accounts` - Optional referrer token account of the input mint, the dex program id
+    ///   stands for no referrer when the extra accounts of the mint transfer hooks follow
+    ///
+    pub fn swap_base_output_native<'info>(
+        ctx: Context<'_, '_, '_, 'info, SwapNative<'info>>,
+        max_amount_in: u64,
+        amount_out: u64,
+    ) -> Result<()> {
+        instructions::swap_base_output_native(ctx, max_amount_in, amount_out)
+    }
+
+    /// Quote swap_base_input without moving tokens, the quote is set as return data
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `amount_in` - input amount to transfer
+    /// * `minimum_amount_out` - Minimum amount of output token, the quote fails below it
+    /// * `with_referrer` - quote the swap with a referrer token account
+    ///
+    pub fn quote_base_input(
+        ctx: Context<QuoteSwap>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        with_referrer: bool,
+    ) -> Result<SwapQuote> {
+        instructions::quote_base_input(ctx, amount_in, minimum_amount_out, with_referrer)
+    }
+
+    /// Quote swap_base_output without moving tokens, the quote is set as return data
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `max_amount_in` - input amount prevents excessive slippage, the quote fails above it
+    /// * `amount_out_less_fee` - amount of output token received
+    /// * `with_referrer` - quote the swap with a referrer token account
+    ///
+    pub fn quote_base_output(
+        ctx: Context<QuoteSwap>,
+        max_amount_in: u64,
+        amount_out_less_fee: u64,
+        with_referrer: bool,
+    ) -> Result<SwapQuote> {
+        instructions::quote_base_output(ctx, max_amount_in, amount_out_less_fee, with_referrer)
+    }
+
+    /// Swap the tokens through two dex sharing a token in one transaction.
+    /// A route has exactly two hops, the swap accounts of a third dex don't fit in a transaction
+    /// without lookup tables. Longer routes add swap_base_input instructions to the transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `amount_in` - input amount of the first dex
+    /// * `minimum_amount_out` - Minimum amount of output token of the second dex, prevents excessive slippage of the whole route
+    /// * `first_hop_accounts` - Number of remaining accounts of the first hop, the rest belong to the second hop
+    /// * `remaining_accounts` - Per hop, the optional referrer token account of the hop input mint or the dex program id
+    ///   when hook accounts follow, then the extra accounts of the mint transfer hooks
+    ///
+    pub fn swap_route<'info>(
+        ctx: Context<'_, '_, '_, 'info, SwapRoute<'info>>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        first_hop_accounts: u8,
+    ) -> Result<()> {
+        instructions::swap_route(ctx, amount_in, minimum_amount_out, first_hop_accounts)
+    }
+
+    /// Escrow input tokens in a limit order filled by `fill_orders` once the dex price crosses the trigger price
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `order_id` - id of the order chosen by the owner, unique per dex and owner while the order is open
+    /// * `amount_in` - input amount to escrow
+    /// * `minimum_amount_out` - Minimum amount of output token of the whole order, prevents excessive slippage
+    /// * `trigger_price_x32` - token_0 price triggering the order, Q32.32 in the format of the dex observations
+    /// * `trigger_above` - fill at or above the trigger price, at or below otherwise
+    /// * `remaining_accounts` - Extra accounts of the mint transfer hooks
+    ///
+    pub fn place_limit_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlaceLimitOrder<'info>>,
+        order_id: u64,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        trigger_price_x32: u128,
+        trigger_above: bool,
+    ) -> Result<()> {
+        instructions::place_limit_order(
+            ctx,
+            order_id,
+            amount_in,
+            minimum_amount_out,
+            trigger_price_x32,
+            trigger_above,
+        )
+    }
+
+    /// Return the escrowed tokens of a limit order to its owner and close the order
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `remaining_accounts` - Extra accounts of the mint transfer hooks
+    ///
+    pub fn cancel_limit_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelLimitOrder<'info>>,
+    ) -> Result<()> {
+        instructions::cancel_limit_order(ctx)
+    }
+
+    /// Swap the escrow of the limit orders triggered at the current dex price, callable by anyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `order_count` - number of orders in the remaining accounts
+    /// * `remaining_accounts` - Order, escrow, owner and owner output token account of each order,
+    ///   followed by the extra accounts of the mint transfer hooks
+    ///
+    pub fn fill_orders<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FillOrders<'info>>,
+        order_count: u8,
+    ) -> Result<()> {
+        instructions::fill_orders(ctx, order_count)
+    }
+
+    /// Redeem supply tokens sold by the curve for a pro-rata share of the reserve
+    /// it collected once the dex missed its launch deadline
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `amount` - supply tokens to transfer to the dex
+    /// * `remaining_accounts` - Extra accounts of the mint transfer hooks
+    ///
+    pub fn refund<'info>(
+        ctx: Context<'_, '_, '_, 'info, Refund<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::refund(ctx, amount)
+    }
+
+    /// Withdraw the reserve the pool creator seeded the dex with
+    /// once the dex missed its launch deadline
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `remaining_accounts` - Extra accounts of the mint transfer hooks
+    ///
+    pub fn withdraw_seed_reserve<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawSeedReserve<'info>>,
+    ) -> Result<()> {
+        instructions::withdraw_seed_reserve(ctx)
+    }
+
     pub fn launch_dex(ctx: Context<LaunchDex>, shared_lamports: u64) -> Result<()> {
         instructions::launch_dex(ctx, shared_lamports)
     }
+
+    /// Launch a dex ready to launch paying the pool creation with the launch lamports
+    /// collected at the dex creation, callable by anyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts, the payer receives the keeper reward
+    ///
+    pub fn crank_launch_dex(ctx: Context<LaunchDex>) -> Result<()> {
+        instructions::crank_launch_dex(ctx)
+    }
+
+    /// Launch a dex ready to launch to an Orca Whirlpool with a full range position
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `shared_lamports` - lamports sent to the dex authority to pay for the pool creation
+    ///
+    pub fn launch_dex_clmm<'info>(
+        ctx: Context<'_, '_, '_, 'info, LaunchDexClmm<'info>>,
+        shared_lamports: u64,
+    ) -> Result<()> {
+        instructions::launch_dex_clmm(ctx, shared_lamports)
+    }
+
+    /// Same as `launch_dex_clmm` paid with the launch lamports, callable by anyone
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts, the payer receives the keeper reward
+    ///
+    pub fn crank_launch_dex_clmm<'info>(
+        ctx: Context<'_, '_, '_, 'info, LaunchDexClmm<'info>>,
+    ) -> Result<()> {
+        instructions::crank_launch_dex_clmm(ctx)
+    }
 }
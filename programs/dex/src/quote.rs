@@ -0,0 +1,636 @@
+//! Swap quotes shared by the swap instructions and off-chain clients
+//!
+//! Given deserialized `DexState`, `ConfigState`, vault amounts and mint
+//! accounts, the quoter reproduces the amounts, fees and launch state
+//! transitions of `swap_base_input` and `swap_base_output` exactly.
+
+use crate::curve::{Fees, SwapResult, TradeDirection};
+use crate::error::ErrorCode;
+use crate::states::{ConfigState, DexState};
+use crate::utils::token::{transfer_fee, transfer_inverse_fee};
+use anchor_lang::prelude::*;
+
+/// Basis points denominator of the price impact
+pub const PRICE_IMPACT_DENOMINATOR: u64 = 10_000;
+
+/// Mint account as needed to compute Token-2022 transfer fees
+#[derive(Clone, Copy, Debug)]
+pub struct MintData<'a> {
+    /// Owner of the mint account, spl token or token 2022 program
+    pub program: Pubkey,
+    /// Raw mint account data
+    pub data: &'a [u8],
+}
+
+/// Everything a swap depends on
+#[derive(Clone, Copy)]
+pub struct SwapQuoter<'a> {
+    pub dex_state: &'a DexState,
+    pub config: &'a ConfigState,
+    pub trade_direction: TradeDirection,
+    /// Input vault token amount, including fees
+    pub input_vault_amount: u64,
+    /// Output vault token amount, including fees
+    pub output_vault_amount: u64,
+    pub input_mint: MintData<'a>,
+    pub output_mint: MintData<'a>,
+    /// Epoch used for the Token-2022 transfer fees
+    pub epoch: u64,
+    /// Timestamp used for the sniper fee rate decay
+    pub timestamp: u64,
+    /// Is a referrer token account passed to the swap
+    pub with_referrer: bool,
+}
+
+/// Outcome of a swap, the return data of the quote instructions
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct SwapQuote {
+    /// Input vault amount without fees plus virtual amount before the swap
+    pub total_input_token_amount: u64,
+    /// Output vault amount without fees plus virtual amount before the swap
+    pub total_output_token_amount: u64,
+    /// Amount transferred from the user, including the transfer fee
+    pub input_transfer_amount: u64,
+    pub input_transfer_fee: u64,
+    /// Amount swapped by the curve, including the swap fee
+    pub input_amount: u64,
+    /// Amount transferred from the output vault, including the transfer fee
+    pub output_amount: u64,
+    pub output_transfer_fee: u64,
+    /// Amount received by the user
+    pub amount_received: u64,
+    /// Swap fee kept by the protocol
+    pub protocol_fee: u64,
+    /// Swap fee share of the pool creator
+    pub creator_fee: u64,
+    /// Swap fee share of the referrer
+    pub referral_fee: u64,
+    pub constant_before: u128,
+    pub constant_after: u128,
+    /// Execution price relative to the vault amounts ratio, in basis points
+    pub price_impact_bps: u64,
+    /// Reserve tokens left until the vault reserve bound after the swap
+    pub remaining_tokens: u64,
+    /// Is the dex ready to launch after the swap
+    pub is_ready_to_launch: bool,
+    /// Part of the request not filled because the swap stops at the vault reserve bound,
+    /// input left with the user for base input and output not received for base output
+    pub unfilled_amount: u64,
+}
+
+impl<'a> SwapQuoter<'a> {
+    /// Quote `swap_base_input` transferring `amount_in` from the user
+    pub fn quote_base_input(&self, amount_in: u64, minimum_amount_out: u64) -> Result<SwapQuote> {
+        self.check_dex_state()?;
+
+        let input_transfer_fee = self.input_transfer_fee(amount_in)?;
+        // Take transfer fees into account for actual amount transferred in
+        let actual_amount_in = amount_in.saturating_sub(input_transfer_fee);
+        require_gt!(actual_amount_in, 0);
+
+        let filled_amount_in = self.reserve_bound_amount_in(actual_amount_in);
+        if filled_amount_in < actual_amount_in {
+            let input_transfer_fee = self.input_inverse_transfer_fee(filled_amount_in)?;
+            let input_transfer_amount = filled_amount_in.checked_add(input_transfer_fee).unwrap();
+            require_gte!(amount_in, input_transfer_amount);
+            // the minimum output shrinks with the filled input
+            let minimum_amount_out = u64::try_from(
+                u128::from(minimum_amount_out) * u128::from(input_transfer_amount)
+                    / u128::from(amount_in),
+            )
+            .unwrap();
+
+            let mut quote = self.swap_actual_amount_in(
+                input_transfer_amount,
+                input_transfer_fee,
+                filled_amount_in,
+                minimum_amount_out,
+            )?;
+            quote.unfilled_amount = amount_in - input_transfer_amount;
+            return Ok(quote);
+        }
+
+        self.swap_actual_amount_in(
+            amount_in,
+            input_transfer_fee,
+            actual_amount_in,
+            minimum_amount_out,
+        )
+    }
+
+    /// Swap `actual_amount_in` received by the input vault out of `input_transfer_amount`
+    fn swap_actual_amount_in(
+        &self,
+        input_transfer_amount: u64,
+        input_transfer_fee: u64,
+        actual_amount_in: u64,
+        minimum_amount_out: u64,
+    ) -> Result<SwapQuote> {
+        let (total_input_token_amount, total_output_token_amount) = self.total_token_amounts();
+        let result = self
+            .dex_state
+            .curve_calculator()?
+            .swap_base_input(
+                u128::from(actual_amount_in),
+                u128::from(total_input_token_amount),
+                u128::from(total_output_token_amount),
+                self.swap_fee_rate(),
+                self.config.creator_fee_rate,
+                self.referral_fee_rate(),
+                self.trade_direction,
+            )
+            .ok_or(ErrorCode::ZeroTradingTokens)?;
+
+        require_eq!(
+            u64::try_from(result.source_amount_swapped).unwrap(),
+            actual_amount_in
+        );
+
+        let output_amount = u64::try_from(result.destination_amount_swapped).unwrap();
+        require_gte!(
+            self.output_vault_amount_without_fee(),
+            output_amount,
+            ErrorCode::InsufficientLiquidity
+        );
+        let output_transfer_fee = self.output_transfer_fee(output_amount)?;
+        let amount_received = output_amount.checked_sub(output_transfer_fee).unwrap();
+        require_gt!(amount_received, 0);
+        require_gte!(
+            amount_received,
+            minimum_amount_out,
+            ErrorCode::ExceededSlippage
+        );
+
+        self.build_quote(
+            &result,
+            input_transfer_amount,
+            input_transfer_fee,
+            output_amount,
+            output_transfer_fee,
+        )
+    }
+
+    /// Quote `swap_base_output` receiving `amount_out_less_fee` by the user
+    pub fn quote_base_output(
+        &self,
+        max_amount_in: u64,
+        amount_out_less_fee: u64,
+    ) -> Result<SwapQuote> {
+        self.check_dex_state()?;
+
+        let output_transfer_fee = self.output_inverse_transfer_fee(amount_out_less_fee)?;
+        let actual_amount_out = amount_out_less_fee
+            .checked_add(output_transfer_fee)
+            .unwrap();
+
+        let (total_input_token_amount, total_output_token_amount) = self.total_token_amounts();
+        let result = self
+            .dex_state
+            .curve_calculator()?
+            .swap_base_output(
+                u128::from(actual_amount_out),
+                u128::from(total_input_token_amount),
+                u128::from(total_output_token_amount),
+                self.swap_fee_rate(),
+                self.config.creator_fee_rate,
+                self.referral_fee_rate(),
+                self.trade_direction,
+            )
+            .ok_or(ErrorCode::ZeroTradingTokens)?;
+
+        // Re-calculate the source amount swapped based on what the curve says
+        let source_amount_swapped = u64::try_from(result.source_amount_swapped).unwrap();
+        require_gt!(source_amount_swapped, 0);
+
+        let filled_amount_in = self.reserve_bound_amount_in(source_amount_swapped);
+        if filled_amount_in < source_amount_swapped {
+            let input_transfer_fee = self.input_inverse_transfer_fee(filled_amount_in)?;
+            let input_transfer_amount = filled_amount_in.checked_add(input_transfer_fee).unwrap();
+            require_gte!(
+                max_amount_in,
+                input_transfer_amount,
+                ErrorCode::ExceededSlippage
+            );
+
+            let mut quote = self.swap_actual_amount_in(
+                input_transfer_amount,
+                input_transfer_fee,
+                filled_amount_in,
+                0,
+            )?;
+            // the partial fill may not be priced worse than the request
+            require_gte!(
+                u128::from(quote.amount_received) * u128::from(max_amount_in),
+                u128::from(amount_out_less_fee) * u128::from(input_transfer_amount),
+                ErrorCode::ExceededSlippage
+            );
+            quote.unfilled_amount = amount_out_less_fee.saturating_sub(quote.amount_received);
+            return Ok(quote);
+        }
+
+        let input_transfer_fee = self.input_inverse_transfer_fee(source_amount_swapped)?;
+        let input_transfer_amount = source_amount_swapped
+            .checked_add(input_transfer_fee)
+            .unwrap();
+        require_gte!(
+            max_amount_in,
+            input_transfer_amount,
+            ErrorCode::ExceededSlippage
+        );
+        require_eq!(
+            u64::try_from(result.destination_amount_swapped).unwrap(),
+            actual_amount_out
+        );
+        require_gte!(
+            self.output_vault_amount_without_fee(),
+            actual_amount_out,
+            ErrorCode::InsufficientLiquidity
+        );
+
+        self.build_quote(
+            &result,
+            input_transfer_amount,
+            input_transfer_fee,
+            actual_amount_out,
+            output_transfer_fee,
+        )
+    }
+
+    fn check_dex_state(&self) -> Result<()> {
+        if self.dex_state.is_launched {
+            return err!(ErrorCode::DexLaunched);
+        }
+        if self.dex_state.is_ready_to_launch {
+            return err!(ErrorCode::DexReadyToLaunch);
+        }
+        if self.dex_state.is_refunding(self.timestamp) {
+            return err!(ErrorCode::DexRefunding);
+        }
+        Ok(())
+    }
+
+    fn swap_fee_rate(&self) -> u64 {
+        self.dex_state
+            .get_swap_fee_rate(self.config.swap_fee_rate, self.timestamp)
+    }
+
+    /// Swapped input of a buy landing the reserve vault on the vault reserve bound, the whole
+    /// input when the swap does not cross the bound
+    fn reserve_bound_amount_in(&self, actual_amount_in: u64) -> u64 {
+        if !self.dex_state.is_buy(self.trade_direction) {
+            return actual_amount_in;
+        }
+
+        let remaining_tokens = self.dex_state.get_remaining_tokens(self.input_vault_amount);
+        if self.input_vault_increase(actual_amount_in) <= remaining_tokens {
+            return actual_amount_in;
+        }
+
+        // smallest input reaching the bound, the referral fee leaves the input vault
+        let mut filled_amount_in = remaining_tokens;
+        loop {
+            let increase = self.input_vault_increase(filled_amount_in);
+            if increase >= remaining_tokens {
+                return filled_amount_in;
+            }
+            filled_amount_in += remaining_tokens - increase;
+        }
+    }
+
+    /// Input vault amount added by a swapped input, the referral fee is paid out of the vault
+    fn input_vault_increase(&self, actual_amount_in: u64) -> u64 {
+        let referral_fee = Fees::protocol_fee(u128::from(actual_amount_in), self.swap_fee_rate())
+            .and_then(|protocol_fee| Fees::referral_fee(protocol_fee, self.referral_fee_rate()))
+            .unwrap();
+        actual_amount_in - u64::try_from(referral_fee).unwrap()
+    }
+
+    fn referral_fee_rate(&self) -> u64 {
+        if self.with_referrer {
+            self.config.referral_fee_rate
+        } else {
+            0
+        }
+    }
+
+    fn vault_amounts(&self) -> (u64, u64) {
+        match self.trade_direction {
+            TradeDirection::ZeroForOne => (self.input_vault_amount, self.output_vault_amount),
+            TradeDirection::OneForZero => (self.output_vault_amount, self.input_vault_amount),
+        }
+    }
+
+    /// Input and output amounts as seen by the curve
+    fn total_token_amounts(&self) -> (u64, u64) {
+        let (vault_0, vault_1) = self.vault_amounts();
+        let (total_0, total_1) = self.dex_state.vault_amount_with_virtual(vault_0, vault_1);
+        match self.trade_direction {
+            TradeDirection::ZeroForOne => (total_0, total_1),
+            TradeDirection::OneForZero => (total_1, total_0),
+        }
+    }
+
+    /// Real output amount available for the swap, virtual amounts can not be paid out
+    fn output_vault_amount_without_fee(&self) -> u64 {
+        let (vault_0, vault_1) = self.vault_amounts();
+        let (amount_0, amount_1) = self.dex_state.vault_amount_without_fee(vault_0, vault_1);
+        match self.trade_direction {
+            TradeDirection::ZeroForOne => amount_1,
+            TradeDirection::OneForZero => amount_0,
+        }
+    }
+
+    fn input_transfer_fee(&self, amount: u64) -> Result<u64> {
+        transfer_fee(
+            &self.input_mint.program,
+            self.input_mint.data,
+            self.epoch,
+            amount,
+        )
+    }
+
+    fn input_inverse_transfer_fee(&self, amount: u64) -> Result<u64> {
+        transfer_inverse_fee(
+            &self.input_mint.program,
+            self.input_mint.data,
+            self.epoch,
+            amount,
+        )
+    }
+
+    fn output_transfer_fee(&self, amount: u64) -> Result<u64> {
+        transfer_fee(
+            &self.output_mint.program,
+            self.output_mint.data,
+            self.epoch,
+            amount,
+        )
+    }
+
+    fn output_inverse_transfer_fee(&self, amount: u64) -> Result<u64> {
+        transfer_inverse_fee(
+            &self.output_mint.program,
+            self.output_mint.data,
+            self.epoch,
+            amount,
+        )
+    }
+
+    fn build_quote(
+        &self,
+        result: &SwapResult,
+        input_transfer_amount: u64,
+        input_transfer_fee: u64,
+        output_amount: u64,
+        output_transfer_fee: u64,
+    ) -> Result<SwapQuote> {
+        require_gte!(result.constant_after, result.constant_before);
+
+        let (total_input_token_amount, total_output_token_amount) = self.total_token_amounts();
+        let input_amount = u64::try_from(result.source_amount_swapped).unwrap();
+        let creator_fee = u64::try_from(result.creator_fee).unwrap();
+        let referral_fee = u64::try_from(result.referral_fee).unwrap();
+        let protocol_fee = u64::try_from(result.protocol_fee)
+            .unwrap()
+            .checked_sub(creator_fee)
+            .unwrap()
+            .checked_sub(referral_fee)
+            .unwrap();
+
+        // vault amounts once the transfers are done, the referral fee leaves the input vault
+        let input_vault_amount = self
+            .input_vault_amount
+            .checked_add(input_transfer_amount - input_transfer_fee)
+            .unwrap()
+            .checked_sub(referral_fee)
+            .unwrap();
+        let output_vault_amount = self.output_vault_amount.checked_sub(output_amount).unwrap();
+        let vault_reserve_amount = self.dex_state.get_vault_reserve_amount(
+            input_vault_amount,
+            output_vault_amount,
+            self.trade_direction,
+        );
+
+        Ok(SwapQuote {
+            total_input_token_amount,
+            total_output_token_amount,
+            input_transfer_amount,
+            input_transfer_fee,
+            input_amount,
+            output_amount,
+            output_transfer_fee,
+            amount_received: output_amount - output_transfer_fee,
+            protocol_fee,
+            creator_fee,
+            referral_fee,
+            constant_before: result.constant_before,
+            constant_after: result.constant_after,
+            price_impact_bps: price_impact_bps(
+                input_amount,
+                output_amount,
+                total_input_token_amount,
+                total_output_token_amount,
+            ),
+            remaining_tokens: self.dex_state.get_remaining_tokens(vault_reserve_amount),
+            is_ready_to_launch: self
+                .dex_state
+                .is_reached_reserve_bound(vault_reserve_amount),
+            unfilled_amount: 0,
+        })
+    }
+}
+
+/// 1 - (output_amount / input_amount) / (total_output / total_input), in basis points
+fn price_impact_bps(
+    input_amount: u64,
+    output_amount: u64,
+    total_input_token_amount: u64,
+    total_output_token_amount: u64,
+) -> u64 {
+    let execution = u128::from(output_amount)
+        * u128::from(total_input_token_amount)
+        * u128::from(PRICE_IMPACT_DENOMINATOR);
+    let spot = u128::from(input_amount) * u128::from(total_output_token_amount);
+    if spot == 0 {
+        return 0;
+    }
+    PRICE_IMPACT_DENOMINATOR.saturating_sub(u64::try_from(execution / spot).unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_spl::token::Token;
+
+    const SUPPLY: u64 = 1_000_000_000;
+    const RESERVE: u64 = 1_000_000;
+    const VAULT_RESERVE_BOUND: u64 = 2_000_000;
+
+    fn dex_state() -> DexState {
+        DexState {
+            vault_for_reserve_bound: true,
+            vault_reserve_bound: VAULT_RESERVE_BOUND,
+            initial_reserve: RESERVE,
+            initial_supply: SUPPLY,
+            ..Default::default()
+        }
+    }
+
+    fn config() -> ConfigState {
+        ConfigState {
+            swap_fee_rate: 10_000,
+            creator_fee_rate: 100_000,
+            referral_fee_rate: 200_000,
+            ..Default::default()
+        }
+    }
+
+    fn quoter<'a>(dex_state: &'a DexState, config: &'a ConfigState) -> SwapQuoter<'a> {
+        let mint = MintData {
+            program: Token::id(),
+            data: &[],
+        };
+        SwapQuoter {
+            dex_state,
+            config,
+            trade_direction: TradeDirection::OneForZero,
+            input_vault_amount: RESERVE,
+            output_vault_amount: SUPPLY,
+            input_mint: mint,
+            output_mint: mint,
+            epoch: 0,
+            timestamp: 0,
+            with_referrer: true,
+        }
+    }
+
+    #[test]
+    fn quote_base_input_splits_fees_and_flips_ready_to_launch() {
+        let (dex_state, config) = (dex_state(), config());
+        let quoter = quoter(&dex_state, &config);
+
+        let quote = quoter.quote_base_input(100_000, 0).unwrap();
+        assert_eq!(quote.input_amount, 100_000);
+        assert_eq!(
+            quote.protocol_fee + quote.creator_fee + quote.referral_fee,
+            1_000
+        );
+        assert_eq!(quote.creator_fee, 100);
+        assert_eq!(quote.referral_fee, 200);
+        // 1_000_000_000 - 1_000_000_000 * 1_000_000 / 1_099_000 rounded down
+        assert_eq!(quote.output_amount, 90_081_892);
+        assert_eq!(quote.amount_received, quote.output_amount);
+        assert!(quote.price_impact_bps > 0);
+        assert_eq!(
+            quote.remaining_tokens,
+            VAULT_RESERVE_BOUND - RESERVE - 99_800
+        );
+        assert!(!quote.is_ready_to_launch);
+
+        assert_eq!(
+            quoter.quote_base_input(100_000, quote.amount_received + 1),
+            Err(ErrorCode::ExceededSlippage.into())
+        );
+
+        let quote = quoter.quote_base_input(RESERVE * 2, 0).unwrap();
+        assert_eq!(quote.remaining_tokens, 0);
+        assert!(quote.is_ready_to_launch);
+    }
+
+    #[test]
+    fn quote_stops_at_reserve_bound() {
+        let (dex_state, config) = (dex_state(), config());
+        let quoter = quoter(&dex_state, &config);
+
+        // the referral fee of 2_004 leaves the input vault
+        let quote = quoter.quote_base_input(RESERVE * 2, 1_000).unwrap();
+        assert_eq!(quote.input_transfer_amount, 1_002_004);
+        assert_eq!(quote.unfilled_amount, RESERVE * 2 - 1_002_004);
+        assert_eq!(quote.referral_fee, 2_004);
+        assert_eq!(quote.remaining_tokens, 0);
+        assert!(quote.is_ready_to_launch);
+
+        let output = quoter.quote_base_output(u64::MAX, SUPPLY / 2).unwrap();
+        assert_eq!(output.input_transfer_amount, 1_002_004);
+        assert_eq!(output.amount_received, quote.amount_received);
+        assert_eq!(output.unfilled_amount, SUPPLY / 2 - quote.amount_received);
+    }
+
+    #[test]
+    fn quote_base_output_rejects_capped_fill_worse_than_requested_price() {
+        let (dex_state, config) = (dex_state(), config());
+        let quoter = quoter(&dex_state, &config);
+
+        // the capped fill costs the whole max input but buys less than half the supply
+        assert_eq!(
+            quoter.quote_base_output(1_002_004, SUPPLY / 2),
+            Err(ErrorCode::ExceededSlippage.into())
+        );
+
+        // requesting the filled amount at the same max input price passes
+        let quote = quoter.quote_base_output(u64::MAX, SUPPLY / 2).unwrap();
+        let filled = quoter
+            .quote_base_output(1_002_004, quote.amount_received)
+            .unwrap();
+        assert_eq!(filled.input_transfer_amount, 1_002_004);
+    }
+
+    #[test]
+    fn quote_rejects_ready_to_launch() {
+        let (mut dex_state, config) = (dex_state(), config());
+        dex_state.is_ready_to_launch = true;
+        let quoter = quoter(&dex_state, &config);
+
+        assert_eq!(
+            quoter.quote_base_input(100_000, 0),
+            Err(ErrorCode::DexReadyToLaunch.into())
+        );
+        assert_eq!(
+            quoter.quote_base_output(u64::MAX, 1_000),
+            Err(ErrorCode::DexReadyToLaunch.into())
+        );
+    }
+
+    #[test]
+    fn quote_base_output_matches_base_input() {
+        let (dex_state, config) = (dex_state(), config());
+        let quoter = quoter(&dex_state, &config);
+
+        let input = quoter.quote_base_input(100_000, 0).unwrap();
+        let output = quoter
+            .quote_base_output(u64::MAX, input.amount_received)
+            .unwrap();
+        assert_eq!(output.amount_received, input.amount_received);
+        assert!(output.input_transfer_amount <= input.input_transfer_amount);
+
+        assert_eq!(
+            quoter.quote_base_output(output.input_transfer_amount - 1, input.amount_received),
+            Err(ErrorCode::ExceededSlippage.into())
+        );
+        assert_eq!(
+            quoter.quote_base_output(u64::MAX, SUPPLY),
+            Err(ErrorCode::ZeroTradingTokens.into())
+        );
+    }
+
+    #[test]
+    fn quote_base_input_charges_sniper_fee() {
+        let (mut dex_state, config) = (dex_state(), config());
+        dex_state.sniper_fee_rate = 30_000;
+        dex_state.sniper_fee_period = 100;
+        let mut quoter = quoter(&dex_state, &config);
+
+        let quote = quoter.quote_base_input(100_000, 0).unwrap();
+        assert_eq!(
+            quote.protocol_fee + quote.creator_fee + quote.referral_fee,
+            3_000
+        );
+
+        quoter.timestamp = 100;
+        let quote = quoter.quote_base_input(100_000, 0).unwrap();
+        assert_eq!(
+            quote.protocol_fee + quote.creator_fee + quote.referral_fee,
+            1_000
+        );
+    }
+}
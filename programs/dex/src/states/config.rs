@@ -1,15 +1,117 @@
+use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
 
 #[account]
 #[derive(Default, Debug)]
 pub struct ConfigState {
     pub bump: u8,
+    pub index: u16,
+    /// disabled configs can not be used to create new dex
+    pub disabled: bool,
     pub swap_fee_rate: u64,
     pub launch_fee_rate: u64,
     pub initial_reserve: u64,
     pub vault_reserve_bound: u64,
+    pub curve_type: u8,
+    /// virtual reserve deposited instead of `initial_reserve`, 0 to disable
+    pub virtual_reserve: u64,
+    /// virtual supply added on top of the init amount
+    pub virtual_supply: u64,
+    /// pool creator share of the swap fee
+    pub creator_fee_rate: u64,
+    /// referrer share of the swap fee
+    pub referral_fee_rate: u64,
+    /// anti-sniper window after the dex creation in seconds, 0 to disable
+    pub sniper_window: u64,
+    /// maximum input of a buy during the anti-sniper window
+    pub sniper_max_swap_input: u64,
+    /// maximum total input of a wallet buys during the anti-sniper window
+    pub sniper_max_wallet_input: u64,
+    /// time to reach the vault reserve bound after the dex creation in seconds, 0 to disable
+    pub launch_window: u64,
+    /// lamports collected from the dex creator to fund the permissionless launch, 0 to disable
+    pub launch_lamports: u64,
+    /// share of the launch lamports paid to the launch caller
+    pub keeper_reward: u64,
+    /// venue the dex graduates to, see `LaunchTarget`
+    pub launch_target: u8,
+    /// share of the LP tokens burned at the Raydium launch, the rest is locked for the pool creator
+    pub lp_burn_rate: u64,
+    /// seconds after the launch before the locked LP tokens start to release
+    pub lp_lock_cliff: u64,
+    /// seconds after the cliff to release the locked LP tokens linearly, 0 to release at the cliff
+    pub lp_vesting_period: u64,
+    /// swap fee rate of a new dex decaying to `swap_fee_rate`, 0 to disable
+    pub sniper_fee_rate: u64,
+    /// seconds after the dex creation for the sniper fee rate to reach `swap_fee_rate`
+    pub sniper_fee_period: u64,
+    /// equal steps of the sniper fee rate decay, 0 for a linear decay
+    pub sniper_fee_steps: u64,
 }
 
 impl ConfigState {
     pub const LEN: usize = 8 + std::mem::size_of::<Self>();
+
+    /// Index seed of the config PDA, empty for index 0 so the first config keeps the
+    /// `[DEX_CONFIG_SEED]` address of the single config before indexed configs
+    pub fn index_seed(index: u16) -> Vec<u8> {
+        if index == 0 {
+            Vec::new()
+        } else {
+            index.to_be_bytes().to_vec()
+        }
+    }
+}
+
+/// Venues a dex can graduate to
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LaunchTarget {
+    /// Raydium CP-Swap pool, the LP tokens are burned
+    RaydiumCpSwap,
+    /// Orca Whirlpool of SPL Token mints, the full range position stays with the dex authority
+    Clmm,
+}
+
+impl TryFrom<u8> for LaunchTarget {
+    type Error = Error;
+
+    fn try_from(launch_target: u8) -> Result<Self> {
+        match launch_target {
+            0 => Ok(LaunchTarget::RaydiumCpSwap),
+            1 => Ok(LaunchTarget::Clmm),
+            _ => err!(ErrorCode::InvalidLaunchTarget),
+        }
+    }
+}
+
+impl From<LaunchTarget> for u8 {
+    fn from(val: LaunchTarget) -> Self {
+        val as u8
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn config_state_layout_test() {
+        // resizing breaks the deployed configs, see the upgrade notes
+        assert_eq!(ConfigState::LEN, 176);
+    }
+
+    #[test]
+    fn config_index_0_keeps_single_config_address() {
+        let program_id = Pubkey::new_unique();
+        let seed = crate::states::DEX_CONFIG_SEED.as_bytes();
+        assert_eq!(
+            Pubkey::find_program_address(&[seed, &ConfigState::index_seed(0)], &program_id),
+            Pubkey::find_program_address(&[seed], &program_id)
+        );
+        assert_ne!(
+            Pubkey::find_program_address(&[seed, &ConfigState::index_seed(1)], &program_id),
+            Pubkey::find_program_address(&[seed], &program_id)
+        );
+    }
 }
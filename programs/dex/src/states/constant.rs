@@ -10,3 +10,21 @@ pub const DEX_STATE_SEED: &str = "dex_state";
 pub const DEX_VAULT_SEED: &str = "dex_vault";
 #[constant]
 pub const DEX_CONFIG_SEED: &str = "dex_config";
+#[constant]
+pub const DEX_OBSERVATION_SEED: &str = "dex_observation";
+#[constant]
+pub const DEX_WALLET_LIMIT_SEED: &str = "dex_wallet_limit";
+#[constant]
+pub const DEX_TRADER_STATS_SEED: &str = "dex_trader_stats";
+#[constant]
+pub const DEX_FEE_DISTRIBUTION_SEED: &str = "dex_fee_distribution";
+#[constant]
+pub const DEX_NATIVE_ACCOUNT_SEED: &str = "dex_native_account";
+#[constant]
+pub const DEX_LIMIT_ORDER_SEED: &str = "dex_limit_order";
+#[constant]
+pub const DEX_LIMIT_ORDER_ESCROW_SEED: &str = "dex_limit_order_escrow";
+#[constant]
+pub const DEX_CLMM_VAULT_SEED: &str = "dex_clmm_vault";
+#[constant]
+pub const DEX_CLMM_POSITION_MINT_SEED: &str = "dex_clmm_position_mint";
@@ -1,12 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
 
-use crate::curve::TradeDirection;
+use crate::curve::{CurveCalculator, CurveType, TradeDirection};
+use crate::error::ErrorCode;
 
 pub const Q32: u128 = (u32::MAX as u128) + 1; // 2^32
 
 #[account(zero_copy(unsafe))]
-#[repr(packed)]
+#[repr(C, packed)]
 #[derive(Default, Debug)]
 pub struct DexState {
     /// pool creator
@@ -42,13 +43,72 @@ pub struct DexState {
     pub swap_fees_token_1: u64,
     pub launch_fees_token_0: u64,
     pub launch_fees_token_1: u64,
+
+    /// curve used to price swaps, see `CurveType`
+    pub curve_type: u8,
+    /// vault amounts at initialization, the curves are anchored to them
+    pub initial_reserve: u64,
+    pub initial_supply: u64,
+
+    /// Virtual amounts of token_0 and token_1, never transferred
+    pub virtual_token_0_amount: u64,
+    pub virtual_token_1_amount: u64,
+
+    /// config the dex belongs to, swaps and launch use its settings
+    pub config: Pubkey,
+
+    /// The pool creator fees amounts of token_0 and token_1
+    pub creator_fees_token_0: u64,
+    pub creator_fees_token_1: u64,
+
+    /// Buys are limited until this timestamp, 0 without anti-sniper window
+    pub sniper_window_end: u64,
+    /// Maximum input of a buy during the anti-sniper window
+    pub sniper_max_swap_input: u64,
+    /// Maximum total input of a wallet buys during the anti-sniper window
+    pub sniper_max_wallet_input: u64,
+
+    /// Dex not ready to launch at this timestamp refunds holders, 0 without deadline
+    pub launch_deadline: u64,
+    /// Reserve tokens the pool creator seeded the reserve vault with, withdrawn by the creator instead of refunded
+    pub seed_reserve: u64,
+
+    /// Lamports held by the dex account to fund the permissionless launch
+    pub launch_lamports: u64,
+    /// Share of the launch lamports paid to the launch caller
+    pub keeper_reward: u64,
+
+    /// Venue the dex graduates to, see `LaunchTarget`
+    pub launch_target: u8,
+
+    /// LP mint of the launched pool
+    pub lp_mint: Pubkey,
+    /// LP tokens locked for the pool creator at the launch
+    pub lp_locked_amount: u64,
+    /// Locked LP tokens already claimed by the pool creator
+    pub lp_claimed_amount: u64,
+    /// Locked LP tokens start to release at this timestamp
+    pub lp_unlock_start: u64,
+    /// Locked LP tokens release linearly over this period after the unlock start
+    pub lp_vesting_period: u64,
+
+    /// Swap fee rate at the sniper fee start decaying to the config swap fee rate, 0 without sniper fee
+    pub sniper_fee_rate: u64,
+    /// Timestamp the sniper fee rate starts to decay
+    pub sniper_fee_start: u64,
+    /// Sniper fee rate decays to the config swap fee rate over this period
+    pub sniper_fee_period: u64,
+    /// Equal steps of the sniper fee rate decay, 0 for a linear decay
+    pub sniper_fee_steps: u64,
 }
 
 impl DexState {
     pub const LEN: usize = 8 + std::mem::size_of::<Self>();
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         &mut self,
+        config: Pubkey,
         pool_creator: Pubkey,
         token_0_vault: Pubkey,
         token_1_vault: Pubkey,
@@ -56,7 +116,13 @@ impl DexState {
         token_1_mint: &InterfaceAccount<Mint>,
         vault_for_reserve_bound: bool,
         vault_reserve_bound: u64,
+        curve_type: u8,
+        initial_reserve: u64,
+        initial_supply: u64,
+        virtual_token_0_amount: u64,
+        virtual_token_1_amount: u64,
     ) {
+        self.config = config;
         self.pool_creator = pool_creator.key();
         self.token_0_vault = token_0_vault;
         self.token_1_vault = token_1_vault;
@@ -71,17 +137,53 @@ impl DexState {
         self.swap_fees_token_1 = 0;
         self.launch_fees_token_0 = 0;
         self.launch_fees_token_1 = 0;
+        self.creator_fees_token_0 = 0;
+        self.creator_fees_token_1 = 0;
+        self.curve_type = curve_type;
+        self.initial_reserve = initial_reserve;
+        self.initial_supply = initial_supply;
+        self.virtual_token_0_amount = virtual_token_0_amount;
+        self.virtual_token_1_amount = virtual_token_1_amount;
+    }
+
+    pub fn curve_calculator(&self) -> Result<CurveCalculator> {
+        Ok(CurveCalculator::new(
+            CurveType::try_from(self.curve_type)?,
+            self.vault_for_reserve_bound,
+            self.initial_supply,
+            self.initial_reserve,
+            self.vault_reserve_bound
+                .checked_add(self.get_virtual_reserve_amount())
+                .ok_or(ErrorCode::Overflow)?,
+        ))
     }
 
     pub fn vault_amount_without_fee(&self, vault_0: u64, vault_1: u64) -> (u64, u64) {
         (
-            vault_0.checked_sub(self.swap_fees_token_0).unwrap(),
-            vault_1.checked_sub(self.swap_fees_token_1).unwrap(),
+            vault_0
+                .checked_sub(self.swap_fees_token_0)
+                .unwrap()
+                .checked_sub(self.creator_fees_token_0)
+                .unwrap(),
+            vault_1
+                .checked_sub(self.swap_fees_token_1)
+                .unwrap()
+                .checked_sub(self.creator_fees_token_1)
+                .unwrap(),
+        )
+    }
+
+    /// Vault amounts without fee plus the virtual amounts, as seen by the curve
+    pub fn vault_amount_with_virtual(&self, vault_0: u64, vault_1: u64) -> (u64, u64) {
+        let (amount_0, amount_1) = self.vault_amount_without_fee(vault_0, vault_1);
+        (
+            amount_0.checked_add(self.virtual_token_0_amount).unwrap(),
+            amount_1.checked_add(self.virtual_token_1_amount).unwrap(),
         )
     }
 
     pub fn token_price_x32(&self, vault_0: u64, vault_1: u64) -> (u128, u128) {
-        let (token_0_amount, token_1_amount) = self.vault_amount_without_fee(vault_0, vault_1);
+        let (token_0_amount, token_1_amount) = self.vault_amount_with_virtual(vault_0, vault_1);
         (
             token_1_amount as u128 * Q32 / token_0_amount as u128,
             token_0_amount as u128 * Q32 / token_1_amount as u128,
@@ -105,13 +207,257 @@ impl DexState {
         }
     }
 
+    pub fn get_virtual_reserve_amount(&self) -> u64 {
+        if self.vault_for_reserve_bound {
+            self.virtual_token_1_amount
+        } else {
+            self.virtual_token_0_amount
+        }
+    }
+
+    /// Buys pay the reserve token to the dex
+    pub fn is_buy(&self, trade_direction: TradeDirection) -> bool {
+        match trade_direction {
+            TradeDirection::ZeroForOne => !self.vault_for_reserve_bound,
+            TradeDirection::OneForZero => self.vault_for_reserve_bound,
+        }
+    }
+
+    pub fn is_sniper_window(&self, timestamp: u64) -> bool {
+        timestamp < self.sniper_window_end
+    }
+
+    /// Swap fee rate at the timestamp, the sniper fee rate decays to `swap_fee_rate` over the sniper fee period
+    pub fn get_swap_fee_rate(&self, swap_fee_rate: u64, timestamp: u64) -> u64 {
+        let sniper_fee_end = self.sniper_fee_start.saturating_add(self.sniper_fee_period);
+        if self.sniper_fee_rate <= swap_fee_rate || timestamp >= sniper_fee_end {
+            return swap_fee_rate;
+        }
+
+        let elapsed = u128::from(timestamp.saturating_sub(self.sniper_fee_start));
+        let period = u128::from(self.sniper_fee_period);
+        let decay = u128::from(self.sniper_fee_rate - swap_fee_rate);
+        let decayed = if self.sniper_fee_steps == 0 {
+            decay * elapsed / period
+        } else {
+            let steps = u128::from(self.sniper_fee_steps);
+            decay * (elapsed * steps / period) / steps
+        };
+
+        self.sniper_fee_rate - u64::try_from(decayed).unwrap()
+    }
+
+    /// Swaps are disabled and holders redeem the reserve once the launch deadline passed
+    pub fn is_refunding(&self, timestamp: u64) -> bool {
+        self.launch_deadline != 0
+            && timestamp >= self.launch_deadline
+            && !self.is_ready_to_launch
+            && !self.is_launched
+    }
+
+    /// Supply tokens sold by the curve and the reserve tokens refunded for them,
+    /// the seed reserve of the pool creator excluded
+    pub fn get_refund_amounts(&self, supply_vault: u64, reserve_vault: u64) -> Option<(u64, u64)> {
+        let (supply_amount, reserve_amount) = if self.vault_for_reserve_bound {
+            let (supply_amount, reserve_amount) =
+                self.vault_amount_without_fee(supply_vault, reserve_vault);
+            (
+                supply_amount.checked_add(self.virtual_token_0_amount)?,
+                reserve_amount,
+            )
+        } else {
+            let (reserve_amount, supply_amount) =
+                self.vault_amount_without_fee(reserve_vault, supply_vault);
+            (
+                supply_amount.checked_add(self.virtual_token_1_amount)?,
+                reserve_amount,
+            )
+        };
+
+        Some((
+            self.initial_supply.checked_sub(supply_amount)?,
+            reserve_amount.checked_sub(self.seed_reserve)?,
+        ))
+    }
+
+    /// Locked LP tokens released at the timestamp and not claimed yet
+    pub fn get_claimable_lp(&self, timestamp: u64) -> u64 {
+        if timestamp < self.lp_unlock_start {
+            return 0;
+        }
+
+        let elapsed = timestamp - self.lp_unlock_start;
+        let released = if elapsed >= self.lp_vesting_period {
+            self.lp_locked_amount
+        } else {
+            u64::try_from(
+                u128::from(self.lp_locked_amount) * u128::from(elapsed)
+                    / u128::from(self.lp_vesting_period),
+            )
+            .unwrap()
+        };
+
+        released.saturating_sub(self.lp_claimed_amount)
+    }
+
+    /// Lamports of the dex account above rent not reserved for the launch
+    pub fn get_creator_lamports(&self, lamports: u64, rent: u64) -> u64 {
+        lamports
+            .saturating_sub(rent)
+            .saturating_sub(self.launch_lamports)
+    }
+
+    /// Launch lamports split into the pool creation share and the keeper reward
+    pub fn split_launch_lamports(&self) -> Option<(u64, u64)> {
+        let shared = self.launch_lamports.checked_sub(self.keeper_reward)?;
+        Some((shared, self.keeper_reward))
+    }
+
+    pub fn get_reserve_vault(&self) -> Pubkey {
+        if self.vault_for_reserve_bound {
+            self.token_1_vault
+        } else {
+            self.token_0_vault
+        }
+    }
+
+    pub fn get_supply_vault(&self) -> Pubkey {
+        if self.vault_for_reserve_bound {
+            self.token_0_vault
+        } else {
+            self.token_1_vault
+        }
+    }
+
     pub fn is_reached_reserve_bound(&self, amount: u64) -> bool {
         amount >= self.vault_reserve_bound
     }
 
     pub fn get_remaining_tokens(&self, vault_reserve_amount: u64) -> u64 {
         self.vault_reserve_bound
-            .checked_sub(vault_reserve_amount)
-            .unwrap_or_default()
+            .saturating_sub(vault_reserve_amount)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dex_state_layout_test() {
+        // resizing breaks the deployed dexes, see the upgrade notes
+        assert_eq!(DexState::LEN, 511);
+    }
+
+    #[test]
+    fn refund_amounts_test() {
+        let mut dex_state = DexState {
+            vault_for_reserve_bound: true,
+            initial_supply: 1_000,
+            seed_reserve: 20,
+            swap_fees_token_0: 5,
+            swap_fees_token_1: 3,
+            ..Default::default()
+        };
+
+        // tokens outside of the curve and the seed reserve are not refunded
+        assert_eq!(dex_state.get_refund_amounts(1_005, 23), Some((0, 0)));
+        assert_eq!(dex_state.get_refund_amounts(605, 123), Some((400, 100)));
+
+        // the virtual supply was never sold
+        dex_state.initial_supply = 1_100;
+        dex_state.virtual_token_0_amount = 100;
+        assert_eq!(dex_state.get_refund_amounts(605, 123), Some((400, 100)));
+
+        dex_state.vault_for_reserve_bound = false;
+        dex_state.virtual_token_0_amount = 0;
+        dex_state.virtual_token_1_amount = 100;
+        assert_eq!(dex_state.get_refund_amounts(603, 125), Some((400, 100)));
+    }
+
+    #[test]
+    fn claimable_lp_test() {
+        let mut dex_state = DexState {
+            lp_locked_amount: 1_000,
+            lp_unlock_start: 100,
+            lp_vesting_period: 50,
+            ..Default::default()
+        };
+
+        assert_eq!(dex_state.get_claimable_lp(99), 0);
+        assert_eq!(dex_state.get_claimable_lp(100), 0);
+        assert_eq!(dex_state.get_claimable_lp(125), 500);
+        assert_eq!(dex_state.get_claimable_lp(200), 1_000);
+
+        dex_state.lp_claimed_amount = 500;
+        assert_eq!(dex_state.get_claimable_lp(140), 300);
+
+        dex_state.lp_vesting_period = 0;
+        assert_eq!(dex_state.get_claimable_lp(100), 500);
+    }
+
+    #[test]
+    fn swap_fee_rate_test() {
+        let mut dex_state = DexState::default();
+        assert_eq!(dex_state.get_swap_fee_rate(10_000, 0), 10_000);
+
+        dex_state.sniper_fee_rate = 30_000;
+        dex_state.sniper_fee_start = 100;
+        dex_state.sniper_fee_period = 100;
+
+        assert_eq!(dex_state.get_swap_fee_rate(10_000, 100), 30_000);
+        assert_eq!(dex_state.get_swap_fee_rate(10_000, 125), 25_000);
+        assert_eq!(dex_state.get_swap_fee_rate(10_000, 199), 10_200);
+        assert_eq!(dex_state.get_swap_fee_rate(10_000, 200), 10_000);
+        assert_eq!(dex_state.get_swap_fee_rate(40_000, 100), 40_000);
+
+        dex_state.sniper_fee_steps = 4;
+        assert_eq!(dex_state.get_swap_fee_rate(10_000, 124), 30_000);
+        assert_eq!(dex_state.get_swap_fee_rate(10_000, 125), 25_000);
+        assert_eq!(dex_state.get_swap_fee_rate(10_000, 199), 15_000);
+        assert_eq!(dex_state.get_swap_fee_rate(10_000, 200), 10_000);
+    }
+
+    #[test]
+    fn creator_lamports_test() {
+        let mut dex_state = DexState {
+            launch_lamports: 1_000,
+            ..Default::default()
+        };
+        let rent = 100;
+
+        // a withdrawal before the crank leaves rent and the launch lamports
+        let lamports = rent + 1_000 + 250;
+        let withdrawn = dex_state.get_creator_lamports(lamports, rent);
+        assert_eq!(withdrawn, 250);
+        assert_eq!(lamports - withdrawn, rent + dex_state.launch_lamports);
+        assert_eq!(
+            dex_state.get_creator_lamports(lamports - withdrawn, rent),
+            0
+        );
+
+        // an underfunded account never pays the creator
+        assert_eq!(dex_state.get_creator_lamports(rent + 999, rent), 0);
+        assert_eq!(dex_state.get_creator_lamports(rent - 1, rent), 0);
+
+        // released after the launch
+        dex_state.launch_lamports = 0;
+        assert_eq!(dex_state.get_creator_lamports(rent + 1_250, rent), 1_250);
+    }
+
+    #[test]
+    fn split_launch_lamports_test() {
+        let mut dex_state = DexState {
+            launch_lamports: 1_000,
+            keeper_reward: 10,
+            ..Default::default()
+        };
+        assert_eq!(dex_state.split_launch_lamports(), Some((990, 10)));
+
+        // the keeper never takes more than the launch lamports
+        dex_state.keeper_reward = 1_000;
+        assert_eq!(dex_state.split_launch_lamports(), Some((0, 1_000)));
+        dex_state.keeper_reward = 1_001;
+        assert_eq!(dex_state.split_launch_lamports(), None);
     }
 }
@@ -1,3 +1,4 @@
+use super::FeeRecipient;
 use anchor_lang::prelude::*;
 
 #[event]
@@ -35,10 +36,29 @@ pub struct InitializeConfigEvent {
     pub admin_id: Pubkey,
     #[index]
     pub config_id: Pubkey,
+    pub index: u16,
     pub swap_fee_rate: u64,
     pub launch_fee_rate: u64,
     pub initial_reserve: u64,
     pub vault_reserve_bound: u64,
+    pub curve_type: u8,
+    pub virtual_reserve: u64,
+    pub virtual_supply: u64,
+    pub creator_fee_rate: u64,
+    pub referral_fee_rate: u64,
+    pub sniper_window: u64,
+    pub sniper_max_swap_input: u64,
+    pub sniper_max_wallet_input: u64,
+    pub launch_window: u64,
+    pub launch_lamports: u64,
+    pub keeper_reward: u64,
+    pub launch_target: u8,
+    pub lp_burn_rate: u64,
+    pub lp_lock_cliff: u64,
+    pub lp_vesting_period: u64,
+    pub sniper_fee_rate: u64,
+    pub sniper_fee_period: u64,
+    pub sniper_fee_steps: u64,
 }
 
 #[event]
@@ -46,6 +66,8 @@ pub struct InitializeConfigEvent {
 pub struct UpdateConfigSwapFeeRateEvent {
     #[index]
     pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
     pub old_swap_fee_rate: u64,
     pub new_swap_fee_rate: u64,
 }
@@ -55,6 +77,8 @@ pub struct UpdateConfigSwapFeeRateEvent {
 pub struct UpdateConfigLaunchFeeRateEvent {
     #[index]
     pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
     pub old_launch_fee_rate: u64,
     pub new_launch_fee_rate: u64,
 }
@@ -64,6 +88,8 @@ pub struct UpdateConfigLaunchFeeRateEvent {
 pub struct UpdateConfigInitialReserveEvent {
     #[index]
     pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
     pub old_initial_reserve: u64,
     pub new_initial_reserve: u64,
 }
@@ -73,15 +99,164 @@ pub struct UpdateConfigInitialReserveEvent {
 pub struct UpdateConfigVaultReserveBoundEvent {
     #[index]
     pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
     pub old_vault_reserve_bound: u64,
     pub new_vault_reserve_bound: u64,
 }
 
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UpdateConfigCurveTypeEvent {
+    #[index]
+    pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
+    pub old_curve_type: u8,
+    pub new_curve_type: u8,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UpdateConfigVirtualReserveEvent {
+    #[index]
+    pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
+    pub old_virtual_reserve: u64,
+    pub new_virtual_reserve: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UpdateConfigVirtualSupplyEvent {
+    #[index]
+    pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
+    pub old_virtual_supply: u64,
+    pub new_virtual_supply: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UpdateConfigCreatorFeeRateEvent {
+    #[index]
+    pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
+    pub old_creator_fee_rate: u64,
+    pub new_creator_fee_rate: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UpdateConfigReferralFeeRateEvent {
+    #[index]
+    pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
+    pub old_referral_fee_rate: u64,
+    pub new_referral_fee_rate: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UpdateConfigDisabledEvent {
+    #[index]
+    pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
+    pub old_disabled: bool,
+    pub new_disabled: bool,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UpdateConfigSniperProtectionEvent {
+    #[index]
+    pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
+    pub old_sniper_window: u64,
+    pub new_sniper_window: u64,
+    pub old_sniper_max_swap_input: u64,
+    pub new_sniper_max_swap_input: u64,
+    pub old_sniper_max_wallet_input: u64,
+    pub new_sniper_max_wallet_input: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UpdateConfigLaunchWindowEvent {
+    #[index]
+    pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
+    pub old_launch_window: u64,
+    pub new_launch_window: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UpdateConfigLaunchLamportsEvent {
+    #[index]
+    pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
+    pub old_launch_lamports: u64,
+    pub new_launch_lamports: u64,
+    pub old_keeper_reward: u64,
+    pub new_keeper_reward: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UpdateConfigLaunchTargetEvent {
+    #[index]
+    pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
+    pub old_launch_target: u8,
+    pub new_launch_target: u8,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UpdateConfigLpPolicyEvent {
+    #[index]
+    pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
+    pub old_lp_burn_rate: u64,
+    pub new_lp_burn_rate: u64,
+    pub old_lp_lock_cliff: u64,
+    pub new_lp_lock_cliff: u64,
+    pub old_lp_vesting_period: u64,
+    pub new_lp_vesting_period: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UpdateConfigSniperFeeEvent {
+    #[index]
+    pub admin_id: Pubkey,
+    #[index]
+    pub config_id: Pubkey,
+    pub old_sniper_fee_rate: u64,
+    pub new_sniper_fee_rate: u64,
+    pub old_sniper_fee_period: u64,
+    pub new_sniper_fee_period: u64,
+    pub old_sniper_fee_steps: u64,
+    pub new_sniper_fee_steps: u64,
+}
+
 #[event]
 #[cfg_attr(feature = "client", derive(Debug))]
 pub struct InitializeDexEvent {
     #[index]
     pub dex_id: Pubkey,
+    pub config_id: Pubkey,
     pub payer_id: Pubkey,
     pub mint_zero: Pubkey,
     pub mint_one: Pubkey,
@@ -89,6 +264,14 @@ pub struct InitializeDexEvent {
     pub token_one_amount: u64,
     pub reserve_bound: u64,
     pub vault_for_reserve_bound: bool,
+    pub curve_type: u8,
+    pub virtual_token_zero_amount: u64,
+    pub virtual_token_one_amount: u64,
+    pub sniper_window_end: u64,
+    pub sniper_fee_rate: u64,
+    pub launch_deadline: u64,
+    pub launch_lamports: u64,
+    pub launch_target: u8,
 }
 
 /// Emitted when swap
@@ -97,9 +280,9 @@ pub struct InitializeDexEvent {
 pub struct SwapEvent {
     #[index]
     pub dex_id: Pubkey,
-    /// pool vault sub trade fees
+    /// pool vault sub trade fees plus virtual amount
     pub input_vault_before: u64,
-    /// pool vault sub trade fees
+    /// pool vault sub trade fees plus virtual amount
     pub output_vault_before: u64,
     /// calculate result without transfer fee
     pub input_amount: u64,
@@ -108,10 +291,34 @@ pub struct SwapEvent {
     pub input_transfer_fee: u64,
     pub output_transfer_fee: u64,
     pub remaining_tokens: u64,
+    /// swap fee kept by the protocol
+    pub protocol_fee: u64,
+    /// swap fee share of the pool creator
+    pub creator_fee: u64,
+    /// swap fee share of the referrer
+    pub referral_fee: u64,
+    /// request not filled as the swap stops at the vault reserve bound, 0 for a full fill
+    pub unfilled_amount: u64,
     pub base_input: bool,
     pub zero_to_one: bool,
 }
 
+/// Emitted when swap through two dex, after the swap event of each dex
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct SwapRouteEvent {
+    #[index]
+    pub first_dex_id: Pubkey,
+    #[index]
+    pub second_dex_id: Pubkey,
+    /// input amount of the first dex
+    pub amount_in: u64,
+    /// shared token amount received from the first dex and swapped in the second dex
+    pub intermediate_amount: u64,
+    /// output amount received from the second dex
+    pub amount_out: u64,
+}
+
 #[event]
 #[cfg_attr(feature = "client", derive(Debug))]
 pub struct DexIsReadyToLaunchEvent {
@@ -124,8 +331,12 @@ pub struct DexIsReadyToLaunchEvent {
 pub struct DexLaunchedEvent {
     #[index]
     pub dex_id: Pubkey,
+    /// Raydium CP-Swap pool, default for the other launch targets
     #[index]
     pub raydium_id: Pubkey,
+    /// pool created on the launch target
+    #[index]
+    pub pool_id: Pubkey,
     #[index]
     pub admin_id: Pubkey,
     pub amount_0: u64,
@@ -135,6 +346,11 @@ pub struct DexLaunchedEvent {
     pub transfer_fee_0: u64,
     pub transfer_fee_1: u64,
     pub lp_burned: u64,
+    /// LP tokens locked for the pool creator
+    pub lp_locked: u64,
+    /// lamports paid to the caller of the permissionless launch
+    pub keeper_reward: u64,
+    pub launch_target: u8,
 }
 
 #[event]
@@ -147,3 +363,120 @@ pub struct WithdrawDexFeeEvent {
     pub token_zero_amount: u64,
     pub token_one_amount: u64,
 }
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct WithdrawCreatorFeeEvent {
+    #[index]
+    pub creator_id: Pubkey,
+    #[index]
+    pub dex_id: Pubkey,
+    pub token_zero_amount: u64,
+    pub token_one_amount: u64,
+    /// unused launch lamports returned to the creator
+    pub lamports: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UpdateFeeRecipientsEvent {
+    #[index]
+    pub admin_id: Pubkey,
+    #[index]
+    pub fee_distribution_id: Pubkey,
+    pub old_recipients: Vec<FeeRecipient>,
+    pub new_recipients: Vec<FeeRecipient>,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct DistributeDexFeeEvent {
+    #[index]
+    pub dex_id: Pubkey,
+    #[index]
+    pub recipient_id: Pubkey,
+    pub token_zero_amount: u64,
+    pub token_one_amount: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ClaimLockedLpEvent {
+    #[index]
+    pub creator_id: Pubkey,
+    #[index]
+    pub dex_id: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RefundEvent {
+    #[index]
+    pub dex_id: Pubkey,
+    pub payer_id: Pubkey,
+    /// supply tokens received by the dex
+    pub supply_amount: u64,
+    pub supply_transfer_fee: u64,
+    /// reserve tokens transferred from the dex
+    pub reserve_amount: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct WithdrawSeedReserveEvent {
+    #[index]
+    pub creator_id: Pubkey,
+    #[index]
+    pub dex_id: Pubkey,
+    /// reserve tokens transferred from the dex
+    pub amount: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PlaceLimitOrderEvent {
+    #[index]
+    pub dex_id: Pubkey,
+    #[index]
+    pub order_id: Pubkey,
+    pub owner: Pubkey,
+    pub zero_for_one: bool,
+    /// input tokens received by the escrow
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+    pub trigger_price_x32: u128,
+    pub trigger_above: bool,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct CancelLimitOrderEvent {
+    #[index]
+    pub dex_id: Pubkey,
+    #[index]
+    pub order_id: Pubkey,
+    pub owner: Pubkey,
+    /// input tokens returned to the owner
+    pub amount_in: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FillLimitOrderEvent {
+    #[index]
+    pub dex_id: Pubkey,
+    #[index]
+    pub order_id: Pubkey,
+    pub owner: Pubkey,
+    /// caller of the permissionless crank
+    pub keeper: Pubkey,
+    /// input tokens swapped from the escrow
+    pub amount_in: u64,
+    /// output tokens received by the owner
+    pub amount_out: u64,
+    /// input tokens left in the escrow, the order is closed when empty
+    pub amount_left: u64,
+    /// token_0 price of the dex the order triggered at, Q32.32
+    pub token_0_price_x32: u128,
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+/// Maximum number of dex fee recipients
+pub const MAX_FEE_RECIPIENTS: usize = 5;
+/// Recipient weights add up to this value
+pub const FEE_WEIGHT_DENOMINATOR: u16 = 10_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct FeeRecipient {
+    /// Owner of the token accounts receiving the share
+    pub wallet: Pubkey,
+    /// Share of the withdrawn dex fees in basis points
+    pub weight_bps: u16,
+}
+
+/// Recipients of the dex fees withdrawn by the admin
+#[account]
+#[derive(Default, Debug)]
+pub struct FeeDistributionState {
+    pub bump: u8,
+    pub recipients_count: u8,
+    pub recipients: [FeeRecipient; MAX_FEE_RECIPIENTS],
+}
+
+impl FeeDistributionState {
+    pub const LEN: usize = 8 + std::mem::size_of::<Self>();
+
+    /// Distribution sending everything to the wallet
+    pub fn single_recipient(wallet: Pubkey) -> Self {
+        let mut fee_distribution = Self {
+            recipients_count: 1,
+            ..Default::default()
+        };
+        fee_distribution.recipients[0] = FeeRecipient {
+            wallet,
+            weight_bps: FEE_WEIGHT_DENOMINATOR,
+        };
+        fee_distribution
+    }
+
+    pub fn get_recipients(&self) -> &[FeeRecipient] {
+        &self.recipients[..usize::from(self.recipients_count)]
+    }
+
+    /// Share of the amount by recipient, the last recipient receives the rounding dust
+    pub fn split(&self, amount: u64) -> Vec<u64> {
+        let recipients = self.get_recipients();
+        let mut shares = Vec::with_capacity(recipients.len());
+        let mut remaining = amount;
+        for (index, recipient) in recipients.iter().enumerate() {
+            let share = if index + 1 == recipients.len() {
+                remaining
+            } else {
+                u64::try_from(
+                    u128::from(amount) * u128::from(recipient.weight_bps)
+                        / u128::from(FEE_WEIGHT_DENOMINATOR),
+                )
+                .unwrap()
+            };
+            remaining -= share;
+            shares.push(share);
+        }
+        shares
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn split_test() {
+        let mut fee_distribution = FeeDistributionState::default();
+        for (index, weight_bps) in [5_000, 3_333, 1_667].into_iter().enumerate() {
+            fee_distribution.recipients[index] = FeeRecipient {
+                wallet: Pubkey::new_unique(),
+                weight_bps,
+            };
+        }
+        fee_distribution.recipients_count = 3;
+
+        assert_eq!(fee_distribution.split(10_000), vec![5_000, 3_333, 1_667]);
+        assert_eq!(fee_distribution.split(1_001), vec![500, 333, 168]);
+        assert_eq!(fee_distribution.split(0), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn single_recipient_test() {
+        let wallet = Pubkey::new_unique();
+        let fee_distribution = FeeDistributionState::single_recipient(wallet);
+
+        assert_eq!(fee_distribution.get_recipients().len(), 1);
+        assert_eq!(fee_distribution.get_recipients()[0].wallet, wallet);
+        assert_eq!(fee_distribution.split(1_001), vec![1_001]);
+    }
+}
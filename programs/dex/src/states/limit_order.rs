@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+/// Tokens of a trader escrowed until the dex price crosses the trigger price
+#[account]
+#[derive(Default, Debug)]
+pub struct LimitOrderState {
+    pub bump: u8,
+    pub escrow_bump: u8,
+    pub dex_id: Pubkey,
+    pub owner: Pubkey,
+    /// Chosen by the owner, unique per dex and owner while the order is open
+    pub order_id: u64,
+    /// The order sells token_0 for token_1 when set, token_1 for token_0 otherwise
+    pub zero_for_one: bool,
+    /// Input tokens left in the escrow
+    pub amount_in: u64,
+    /// Minimum output of the input tokens left, reduced in proportion by partial fills
+    pub minimum_amount_out: u64,
+    /// Token_0 price in the format of the dex observations, `token_1 * Q32 / token_0`
+    pub trigger_price_x32: u128,
+    /// Fill at or above the trigger price when set, at or below otherwise
+    pub trigger_above: bool,
+    pub created_at: u64,
+}
+
+impl LimitOrderState {
+    pub const LEN: usize = 8 + std::mem::size_of::<Self>();
+
+    /// The order can be filled at the token_0 price before the swap
+    pub fn is_triggered(&self, token_0_price_x32: u128) -> bool {
+        if self.trigger_above {
+            token_0_price_x32 >= self.trigger_price_x32
+        } else {
+            token_0_price_x32 <= self.trigger_price_x32
+        }
+    }
+
+    /// Keep the minimum output proportional to the input left after a partial fill
+    pub fn fill(&mut self, amount_in_left: u64) {
+        self.minimum_amount_out = u64::try_from(
+            u128::from(self.minimum_amount_out) * u128::from(amount_in_left)
+                / u128::from(self.amount_in),
+        )
+        .unwrap();
+        self.amount_in = amount_in_left;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn is_triggered_test() {
+        let mut order = LimitOrderState {
+            trigger_price_x32: 1_000,
+            trigger_above: true,
+            ..Default::default()
+        };
+        assert!(!order.is_triggered(999));
+        assert!(order.is_triggered(1_000));
+        assert!(order.is_triggered(1_001));
+
+        order.trigger_above = false;
+        assert!(order.is_triggered(999));
+        assert!(order.is_triggered(1_000));
+        assert!(!order.is_triggered(1_001));
+    }
+
+    #[test]
+    fn fill_test() {
+        let mut order = LimitOrderState {
+            amount_in: 1_000,
+            minimum_amount_out: 3_000,
+            ..Default::default()
+        };
+
+        order.fill(400);
+        assert_eq!(order.amount_in, 400);
+        assert_eq!(order.minimum_amount_out, 1_200);
+
+        order.fill(0);
+        assert_eq!(order.amount_in, 0);
+        assert_eq!(order.minimum_amount_out, 0);
+    }
+}
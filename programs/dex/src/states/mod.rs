@@ -4,6 +4,21 @@ pub use config::*;
 pub mod dex;
 pub use dex::*;
 
+pub mod oracle;
+pub use oracle::*;
+
+pub mod wallet_limit;
+pub use wallet_limit::*;
+
+pub mod trader_stats;
+pub use trader_stats::*;
+
+pub mod limit_order;
+pub use limit_order::*;
+
+pub mod fee_distribution;
+pub use fee_distribution::*;
+
 pub mod events;
 pub use events::*;
 
@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+
+/// Number of observations kept by a dex
+pub const OBSERVATION_NUM: usize = 100;
+
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug)]
+pub struct Observation {
+    /// The block timestamp of the observation
+    pub block_timestamp: u64,
+    /// The cumulative token_0 price in token_1, Q32.32, multiplied by seconds elapsed
+    pub cumulative_token_0_price_x32: u128,
+    /// The cumulative token_1 price in token_0, Q32.32, multiplied by seconds elapsed
+    pub cumulative_token_1_price_x32: u128,
+}
+
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[derive(Debug)]
+pub struct ObservationState {
+    /// Whether the first observation is written
+    pub initialized: bool,
+    /// The index of the latest observation
+    pub observation_index: u16,
+    pub dex_id: Pubkey,
+    /// Ring buffer of observations, at most one per second
+    pub observations: [Observation; OBSERVATION_NUM],
+}
+
+impl Default for ObservationState {
+    fn default() -> Self {
+        Self {
+            initialized: false,
+            observation_index: 0,
+            dex_id: Pubkey::default(),
+            observations: [Observation::default(); OBSERVATION_NUM],
+        }
+    }
+}
+
+impl ObservationState {
+    pub const LEN: usize = 8 + std::mem::size_of::<Self>();
+
+    pub fn initialize(&mut self, dex_id: Pubkey) {
+        self.initialized = false;
+        self.observation_index = 0;
+        self.dex_id = dex_id;
+    }
+
+    /// Accumulate the prices before the swap over the time since the latest observation.
+    /// Only the first swap of a second writes an observation, so a trade can't move
+    /// the price it is recorded at.
+    pub fn update(
+        &mut self,
+        block_timestamp: u64,
+        token_0_price_x32: u128,
+        token_1_price_x32: u128,
+    ) {
+        let observation_index = self.observation_index;
+        if !self.initialized {
+            self.initialized = true;
+            self.observations[usize::from(observation_index)].block_timestamp = block_timestamp;
+            return;
+        }
+
+        let last_observation = self.observations[usize::from(observation_index)];
+        let delta_time = block_timestamp.saturating_sub(last_observation.block_timestamp);
+        if delta_time == 0 {
+            return;
+        }
+
+        let next_index = (usize::from(observation_index) + 1) % OBSERVATION_NUM;
+        self.observations[next_index] = Observation {
+            block_timestamp,
+            cumulative_token_0_price_x32: last_observation
+                .cumulative_token_0_price_x32
+                .wrapping_add(token_0_price_x32.wrapping_mul(u128::from(delta_time))),
+            cumulative_token_1_price_x32: last_observation
+                .cumulative_token_1_price_x32
+                .wrapping_add(token_1_price_x32.wrapping_mul(u128::from(delta_time))),
+        };
+        self.observation_index = next_index as u16;
+    }
+
+    /// Time weighted average prices of token_0 and token_1, Q32.32, over at least
+    /// `period` seconds up to the latest observation.
+    /// Returns None if the observations don't cover the period yet.
+    pub fn twap_x32(&self, period: u64) -> Option<(u128, u128)> {
+        if !self.initialized || period == 0 {
+            return None;
+        }
+
+        let latest = self.observations[usize::from(self.observation_index)];
+        let latest_timestamp = latest.block_timestamp;
+        // newest observation at least `period` seconds older than the latest one
+        let oldest = (1..OBSERVATION_NUM)
+            .map(|offset| {
+                self.observations[(usize::from(self.observation_index) + OBSERVATION_NUM - offset)
+                    % OBSERVATION_NUM]
+            })
+            .take_while(|observation| observation.block_timestamp != 0)
+            .find(|observation| observation.block_timestamp + period <= latest_timestamp)?;
+
+        let delta_time = u128::from(latest_timestamp - oldest.block_timestamp);
+        Some((
+            latest
+                .cumulative_token_0_price_x32
+                .wrapping_sub(oldest.cumulative_token_0_price_x32)
+                / delta_time,
+            latest
+                .cumulative_token_1_price_x32
+                .wrapping_sub(oldest.cumulative_token_1_price_x32)
+                / delta_time,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn observation_state_layout_test() {
+        // resizing breaks the observations of deployed dexes
+        assert_eq!(ObservationState::LEN, 4_043);
+    }
+
+    #[test]
+    fn twap_averages_prices_over_period() {
+        let mut state = ObservationState::default();
+        assert_eq!(state.twap_x32(1), None);
+
+        state.update(100, 10, 1);
+        // same second, ignored
+        state.update(100, 1_000, 1_000);
+        state.update(110, 20, 2);
+        state.update(130, 40, 4);
+        assert_eq!({ state.observation_index }, 2);
+
+        // 20 for 10s, then 40 for 20s
+        assert_eq!(state.twap_x32(30), Some((1_000 / 30, 100 / 30)));
+        assert_eq!(state.twap_x32(20), Some((40, 4)));
+        assert_eq!(state.twap_x32(31), None);
+
+        for timestamp in 131..131 + OBSERVATION_NUM as u64 {
+            state.update(timestamp, 1, 1);
+        }
+        assert_eq!(state.twap_x32(OBSERVATION_NUM as u64 - 1), Some((1, 1)));
+        assert_eq!(state.twap_x32(OBSERVATION_NUM as u64), None);
+    }
+}
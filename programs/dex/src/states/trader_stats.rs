@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+/// Swap statistics of a trader on a dex
+#[account]
+#[derive(Default, Debug)]
+pub struct TraderStatsState {
+    pub bump: u8,
+    pub dex_id: Pubkey,
+    pub trader: Pubkey,
+    /// Total reserve token amount transferred in by the trader buys
+    pub buy_volume: u64,
+    /// Total reserve token amount transferred out by the trader sells
+    pub sell_volume: u64,
+    pub trade_count: u64,
+    pub first_trade_timestamp: u64,
+    pub last_trade_timestamp: u64,
+}
+
+impl TraderStatsState {
+    pub const LEN: usize = 8 + std::mem::size_of::<Self>();
+
+    /// Record a swap of the trader, the volume is the reserve token amount of the swap
+    pub fn record_trade(&mut self, is_buy: bool, volume: u64, timestamp: u64) {
+        if is_buy {
+            self.buy_volume = self.buy_volume.checked_add(volume).unwrap();
+        } else {
+            self.sell_volume = self.sell_volume.checked_add(volume).unwrap();
+        }
+
+        if self.trade_count == 0 {
+            self.first_trade_timestamp = timestamp;
+        }
+        self.trade_count = self.trade_count.checked_add(1).unwrap();
+        self.last_trade_timestamp = timestamp;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn trader_stats_state_layout_test() {
+        assert_eq!(TraderStatsState::LEN, 120);
+    }
+
+    #[test]
+    fn record_trade_test() {
+        let mut trader_stats = TraderStatsState::default();
+
+        trader_stats.record_trade(true, 1_000, 10);
+        trader_stats.record_trade(false, 400, 25);
+        trader_stats.record_trade(true, 500, 30);
+
+        assert_eq!(trader_stats.buy_volume, 1_500);
+        assert_eq!(trader_stats.sell_volume, 400);
+        assert_eq!(trader_stats.trade_count, 3);
+        assert_eq!(trader_stats.first_trade_timestamp, 10);
+        assert_eq!(trader_stats.last_trade_timestamp, 30);
+    }
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+/// Buys of a wallet during the anti-sniper window of a dex
+#[account]
+#[derive(Default, Debug)]
+pub struct WalletLimitState {
+    pub bump: u8,
+    pub dex_id: Pubkey,
+    pub wallet: Pubkey,
+    /// Total input of the wallet buys
+    pub input_amount: u64,
+}
+
+impl WalletLimitState {
+    pub const LEN: usize = 8 + std::mem::size_of::<Self>();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn wallet_limit_state_layout_test() {
+        assert_eq!(WalletLimitState::LEN, 88);
+    }
+}
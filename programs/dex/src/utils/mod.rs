@@ -1,7 +1,9 @@
+pub mod account;
 pub mod account_load;
 pub mod math;
 pub mod token;
 
+pub use account::*;
 pub use account_load::*;
 pub use math::*;
 pub use token::*;
@@ -1,4 +1,5 @@
 use crate::error::ErrorCode;
+use crate::utils::create_pda_account;
 use anchor_lang::prelude::*;
 use anchor_spl::{
     token::{Token, TokenAccount},
@@ -8,13 +9,13 @@ use anchor_spl::{
             self,
             extension::{
                 transfer_fee::{TransferFeeConfig, MAX_FEE_BASIS_POINTS},
-                ExtensionType, StateWithExtensions,
+                transfer_hook, ExtensionType, StateWithExtensions,
             },
         },
     },
     token_interface::{
-        initialize_account3, spl_token_2022::extension::BaseStateWithExtensions,
-        InitializeAccount3, Mint,
+        close_account, initialize_account3, spl_token_2022::extension::BaseStateWithExtensions,
+        sync_native, CloseAccount, InitializeAccount3, Mint, SyncNative,
     },
 };
 
@@ -22,58 +23,105 @@ pub fn transfer_from_user_to_dex_vault<'a>(
     authority: AccountInfo<'a>,
     from: AccountInfo<'a>,
     to_vault: AccountInfo<'a>,
-    mint: AccountInfo<'a>,
+    mint: &InterfaceAccount<'a, Mint>,
     token_program: AccountInfo<'a>,
     amount: u64,
-    mint_decimals: u8,
+    hook_accounts: &[AccountInfo<'a>],
 ) -> Result<()> {
     if amount == 0 {
         return Ok(());
     }
-    token_2022::transfer_checked(
-        CpiContext::new(
-            token_program.to_account_info(),
-            token_2022::TransferChecked {
-                from,
-                to: to_vault,
-                authority,
-                mint,
-            },
-        ),
+    transfer_checked_with_hook(
+        token_program,
+        token_2022::TransferChecked {
+            from,
+            to: to_vault,
+            authority,
+            mint: mint.to_account_info(),
+        },
         amount,
-        mint_decimals,
+        mint.decimals,
+        hook_accounts,
+        &[],
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn transfer_from_dex_vault_to_user<'a>(
     authority: AccountInfo<'a>,
     from_vault: AccountInfo<'a>,
     to: AccountInfo<'a>,
-    mint: AccountInfo<'a>,
+    mint: &InterfaceAccount<'a, Mint>,
     token_program: AccountInfo<'a>,
     amount: u64,
-    mint_decimals: u8,
     signer_seeds: &[&[&[u8]]],
+    hook_accounts: &[AccountInfo<'a>],
 ) -> Result<()> {
     if amount == 0 {
         return Ok(());
     }
-    token_2022::transfer_checked(
-        CpiContext::new_with_signer(
-            token_program.to_account_info(),
-            token_2022::TransferChecked {
-                from: from_vault,
-                to,
-                authority,
-                mint,
-            },
-            signer_seeds,
-        ),
+    transfer_checked_with_hook(
+        token_program,
+        token_2022::TransferChecked {
+            from: from_vault,
+            to,
+            authority,
+            mint: mint.to_account_info(),
+        },
         amount,
-        mint_decimals,
+        mint.decimals,
+        hook_accounts,
+        signer_seeds,
     )
 }
 
+/// Transfer checked forwarding the extra accounts of the mint transfer hook,
+/// the token program looks the accounts it needs up by address
+fn transfer_checked_with_hook<'a>(
+    token_program: AccountInfo<'a>,
+    accounts: token_2022::TransferChecked<'a>,
+    amount: u64,
+    decimals: u8,
+    hook_accounts: &[AccountInfo<'a>],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if hook_accounts.is_empty() || !has_transfer_hook(&accounts.mint)? {
+        return token_2022::transfer_checked(
+            CpiContext::new_with_signer(token_program, accounts, signer_seeds),
+            amount,
+            decimals,
+        );
+    }
+
+    let mut instruction = spl_token_2022::instruction::transfer_checked(
+        token_program.key,
+        accounts.from.key,
+        accounts.mint.key,
+        accounts.to.key,
+        accounts.authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    let mut account_infos = vec![
+        accounts.from,
+        accounts.mint,
+        accounts.to,
+        accounts.authority,
+    ];
+    for account in hook_accounts {
+        instruction.accounts.push(AccountMeta {
+            pubkey: account.key(),
+            is_signer: false,
+            is_writable: account.is_writable,
+        });
+        account_infos.push(account.clone());
+    }
+
+    anchor_lang::solana_program::program::invoke_signed(&instruction, &account_infos, signer_seeds)
+        .map_err(Into::into)
+}
+
 /// Issue a spl_token `MintTo` instruction.
 pub fn token_mint_to<'a>(
     authority: AccountInfo<'a>,
@@ -124,15 +172,43 @@ pub fn get_transfer_inverse_fee(mint_info: &AccountInfo, post_fee_amount: u64) -
     if *mint_info.owner == Token::id() {
         return Ok(0);
     }
+    transfer_inverse_fee(
+        mint_info.owner,
+        &mint_info.try_borrow_data()?,
+        Clock::get()?.epoch,
+        post_fee_amount,
+    )
+}
+
+/// Calculate the fee for input amount
+pub fn get_transfer_fee(mint_info: &AccountInfo, pre_fee_amount: u64) -> Result<u64> {
+    if *mint_info.owner == Token::id() {
+        return Ok(0);
+    }
+    transfer_fee(
+        mint_info.owner,
+        &mint_info.try_borrow_data()?,
+        Clock::get()?.epoch,
+        pre_fee_amount,
+    )
+}
+
+/// Calculate the fee for output amount from the mint account data at an epoch
+pub fn transfer_inverse_fee(
+    mint_program: &Pubkey,
+    mint_data: &[u8],
+    epoch: u64,
+    post_fee_amount: u64,
+) -> Result<u64> {
+    if *mint_program == Token::id() {
+        return Ok(0);
+    }
     if post_fee_amount == 0 {
         return err!(ErrorCode::InvalidInput);
     }
-    let mint_data = mint_info.try_borrow_data()?;
-    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(mint_data)?;
 
     let fee = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
-        let epoch = Clock::get()?.epoch;
-
         let transfer_fee = transfer_fee_config.get_epoch_fee(epoch);
         if u16::from(transfer_fee.transfer_fee_basis_points) == MAX_FEE_BASIS_POINTS {
             u64::from(transfer_fee.maximum_fee)
@@ -147,17 +223,21 @@ pub fn get_transfer_inverse_fee(mint_info: &AccountInfo, post_fee_amount: u64) -
     Ok(fee)
 }
 
-/// Calculate the fee for input amount
-pub fn get_transfer_fee(mint_info: &AccountInfo, pre_fee_amount: u64) -> Result<u64> {
-    if *mint_info.owner == Token::id() {
+/// Calculate the fee for input amount from the mint account data at an epoch
+pub fn transfer_fee(
+    mint_program: &Pubkey,
+    mint_data: &[u8],
+    epoch: u64,
+    pre_fee_amount: u64,
+) -> Result<u64> {
+    if *mint_program == Token::id() {
         return Ok(0);
     }
-    let mint_data = mint_info.try_borrow_data()?;
-    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(mint_data)?;
 
     let fee = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
         transfer_fee_config
-            .calculate_epoch_fee(Clock::get()?.epoch, pre_fee_amount)
+            .calculate_epoch_fee(epoch, pre_fee_amount)
             .unwrap()
     } else {
         0
@@ -165,6 +245,16 @@ pub fn get_transfer_fee(mint_info: &AccountInfo, pre_fee_amount: u64) -> Result<
     Ok(fee)
 }
 
+/// Whether the mint calls a transfer hook program on transfers
+pub fn has_transfer_hook(mint_info: &AccountInfo) -> Result<bool> {
+    if *mint_info.owner == Token::id() {
+        return Ok(false);
+    }
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    Ok(transfer_hook::get_program_id(&mint).is_some())
+}
+
 pub fn is_supported_mint(mint_account: &InterfaceAccount<Mint>) -> Result<bool> {
     let mint_info = mint_account.to_account_info();
     if *mint_info.owner == Token::id() {
@@ -179,6 +269,7 @@ pub fn is_supported_mint(mint_account: &InterfaceAccount<Mint>) -> Result<bool>
         matches!(
             e,
             ExtensionType::TransferFeeConfig
+                | ExtensionType::TransferHook
                 | ExtensionType::MetadataPointer
                 | ExtensionType::TokenMetadata
         )
@@ -212,17 +303,14 @@ pub fn create_token_account<'a>(
             TokenAccount::LEN
         }
     };
-    let lamports = Rent::get()?.minimum_balance(space);
-    let cpi_accounts = anchor_lang::system_program::CreateAccount {
-        from: payer.to_account_info(),
-        to: token_account.to_account_info(),
-    };
-    let cpi_context = CpiContext::new(system_program.to_account_info(), cpi_accounts);
-    anchor_lang::system_program::create_account(
-        cpi_context.with_signer(signer_seeds),
-        lamports,
-        space as u64,
+    // a funded token account address can't block the creation
+    create_pda_account(
+        payer,
+        token_account,
+        system_program,
+        space,
         token_program.key,
+        signer_seeds,
     )?;
     initialize_account3(CpiContext::new(
         token_program.to_account_info(),
@@ -233,3 +321,80 @@ pub fn create_token_account<'a>(
         },
     ))
 }
+
+/// Move lamports of the payer into a native mint token account
+pub fn wrap_native<'a>(
+    payer: AccountInfo<'a>,
+    token_account: AccountInfo<'a>,
+    system_program: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    lamports: u64,
+) -> Result<()> {
+    if lamports == 0 {
+        return Ok(());
+    }
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            system_program,
+            anchor_lang::system_program::Transfer {
+                from: payer,
+                to: token_account.clone(),
+            },
+        ),
+        lamports,
+    )?;
+    sync_native(CpiContext::new(
+        token_program,
+        SyncNative {
+            account: token_account,
+        },
+    ))
+}
+
+/// Close a token account, a native mint token account releases its balance as lamports
+pub fn close_token_account<'a>(
+    authority: AccountInfo<'a>,
+    token_account: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    close_account(CpiContext::new_with_signer(
+        token_program,
+        CloseAccount {
+            account: token_account,
+            destination,
+            authority,
+        },
+        signer_seeds,
+    ))
+}
+
+/// Move the transfer fees withheld by a token account to its mint, a token account holding withheld fees can not be closed
+pub fn harvest_withheld_tokens<'a>(
+    mint: AccountInfo<'a>,
+    token_account: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+) -> Result<()> {
+    if *mint.owner != token_2022::Token2022::id() {
+        return Ok(());
+    }
+    {
+        let mint_data = mint.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+        if mint_state.get_extension::<TransferFeeConfig>().is_err() {
+            return Ok(());
+        }
+    }
+    let instruction =
+        spl_token_2022::extension::transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+            token_program.key,
+            mint.key,
+            &[token_account.key],
+        )?;
+    anchor_lang::solana_program::program::invoke(
+        &instruction,
+        &[mint, token_account, token_program],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use super::pda::*;
+use crate::{accounts, instruction};
+use anchor_lang::{
+    prelude::*, solana_program::instruction::Instruction, solana_program::sysvar, system_program,
+    InstructionData,
+};
+use anchor_spl::associated_token;
+
+pub fn initialize_authority_manager(payer: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeAuthorityManager {
+            payer,
+            authority_manager: authority_manager_address().0,
+            authority: authority_address().0,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::InitializeAuthorityManager {},
+    )
+}
+
+pub fn remove_admin(payer: Pubkey, index: u64) -> Instruction {
+    build(
+        update_authority_manager_accounts(payer),
+        instruction::RemoveAdmin { index },
+    )
+}
+
+pub fn set_admin(payer: Pubkey, index: u64, admin: Pubkey) -> Instruction {
+    build(
+        update_authority_manager_accounts(payer),
+        instruction::SetAdmin { index, admin },
+    )
+}
+
+fn update_authority_manager_accounts(payer: Pubkey) -> accounts::UpdateAuthorityManager {
+    accounts::UpdateAuthorityManager {
+        payer,
+        authority_manager: authority_manager_address().0,
+    }
+}
+
+pub fn initialize_faucet_vault(payer: Pubkey, mint: Pubkey, token_program: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeFaucetVault {
+            payer,
+            authority_manager: authority_manager_address().0,
+            authority: authority_address().0,
+            faucet_vault: faucet_vault_address(&mint).0,
+            mint,
+            token_program,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::InitializeFaucetVault {},
+    )
+}
+
+pub fn initialize_faucet_claim(admin: Pubkey, mint: Pubkey, token_program: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeFaucetClaim {
+            admin,
+            mint,
+            authority_manager: authority_manager_address().0,
+            authority: authority_address().0,
+            faucet_vault: faucet_vault_address(&mint).0,
+            faucet_claim: faucet_claim_address(&mint).0,
+            token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::InitializeFaucetClaim {},
+    )
+}
+
+pub fn destroy_faucet_claim(payer: Pubkey, mint: Pubkey) -> Instruction {
+    build(
+        accounts::DestroyFaucetClaim {
+            payer,
+            faucet_claim: faucet_claim_address(&mint).0,
+            authority_manager: authority_manager_address().0,
+        },
+        instruction::DestroyFaucetClaim {},
+    )
+}
+
+/// Create the next shard of the faucet claim, `shard_index` is the current shards count of the claim
+pub fn initialize_faucet_claim_shard(
+    admin: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    shard_index: u16,
+    merkle_root: [u8; 32],
+) -> Instruction {
+    let faucet_claim = faucet_claim_address(&mint).0;
+    build(
+        accounts::InitializeFaucetClaimShard {
+            admin,
+            authority_manager: authority_manager_address().0,
+            faucet_claim,
+            faucet_claim_shard: faucet_claim_shard_address(&faucet_claim, shard_index).0,
+            mint,
+            token_program,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::InitializeFaucetClaimShard { merkle_root },
+    )
+}
+
+pub fn destroy_faucet_claim_shard(payer: Pubkey, mint: Pubkey, shard_index: u16) -> Instruction {
+    let faucet_claim = faucet_claim_address(&mint).0;
+    build(
+        accounts::DestroyFaucetClaimShard {
+            payer,
+            faucet_claim,
+            faucet_claim_shard: faucet_claim_shard_address(&faucet_claim, shard_index).0,
+            authority_manager: authority_manager_address().0,
+        },
+        instruction::DestroyFaucetClaimShard {},
+    )
+}
+
+/// Leaf of the payer in the merkle tree of a faucet claim shard with its proofs
+#[derive(Clone, Debug)]
+pub struct ClaimProof {
+    pub shard_index: u16,
+    pub index: u16,
+    pub amount: u64,
+    pub proofs: Vec<[u8; 32]>,
+}
+
+pub fn claim(
+    payer: Pubkey,
+    payer_vault: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    proof: ClaimProof,
+) -> Instruction {
+    let faucet_claim = faucet_claim_address(&mint).0;
+    build(
+        accounts::Claim {
+            payer,
+            payer_vault,
+            faucet_claim,
+            faucet_claim_shard: faucet_claim_shard_address(&faucet_claim, proof.shard_index).0,
+            authority_manager: authority_manager_address().0,
+            authority: authority_address().0,
+            faucet_vault: faucet_vault_address(&mint).0,
+            mint,
+            token_program,
+        },
+        instruction::Claim {
+            proofs: proof.proofs,
+            index: proof.index,
+            amount: proof.amount,
+        },
+    )
+}
+
+pub fn withdraw_expired_faucet_claim(
+    payer: Pubkey,
+    payer_vault: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+) -> Instruction {
+    build(
+        accounts::WithdrawExpiredFaucetClaim {
+            payer,
+            payer_vault,
+            faucet_claim: faucet_claim_address(&mint).0,
+            authority_manager: authority_manager_address().0,
+            authority: authority_address().0,
+            faucet_vault: faucet_vault_address(&mint).0,
+            mint,
+            token_program,
+        },
+        instruction::WithdrawExpiredFaucetClaim {},
+    )
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
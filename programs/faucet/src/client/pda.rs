@@ -0,0 +1,30 @@
+use crate::states::*;
+use anchor_lang::prelude::Pubkey;
+
+pub fn authority_manager_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FAUCET_AUTHORITY_MANAGER_SEED.as_bytes()], &crate::id())
+}
+
+pub fn authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FAUCET_AUTHORITY_SEED.as_bytes()], &crate::id())
+}
+
+pub fn faucet_vault_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FAUCET_VAULT_SEED.as_bytes(), mint.as_ref()], &crate::id())
+}
+
+pub fn faucet_claim_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FAUCET_CLAIM_SEED.as_bytes(), mint.as_ref()], &crate::id())
+}
+
+/// Shard of a faucet claim, shards are indexed from 0 in their creation order
+pub fn faucet_claim_shard_address(faucet_claim: &Pubkey, index: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            FAUCET_CLAIM_SHARD_SEED.as_bytes(),
+            faucet_claim.as_ref(),
+            &index.to_be_bytes(),
+        ],
+        &crate::id(),
+    )
+}
@@ -24,4 +24,6 @@ pub enum ErrorCode {
     InvalidProof,
     #[msg("Failed calculate transfer fee")]
     FiledCalculateTransferFee,
+    #[msg("Faucet claim shards not destroyed")]
+    ShardsNotDestroyed,
 }
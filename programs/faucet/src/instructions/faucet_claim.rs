@@ -24,6 +24,7 @@ pub fn initialize_faucet_claim(ctx: Context<InitializeFaucetClaim>) -> Result<()
     faucet_claim.total_claimed_amount = 0;
     faucet_claim.shards = 0;
     faucet_claim.bump = ctx.bumps.faucet_claim;
+    faucet_claim.destroyed_shards = 0;
 
     emit!(InitializeFaucetClaimEvent {
         faucet_claim_id,
@@ -183,6 +184,9 @@ pub struct DestroyFaucetClaim<'info> {
     #[account(mut, constraint = authority_manager.is_admin(payer.key) @ FaucetError::InvalidAdmin)]
     pub payer: Signer<'info>,
     #[account(
+        mut,
+        close = payer,
+        constraint = faucet_claim.is_all_shards_destroyed() @ FaucetError::ShardsNotDestroyed,
         seeds = [
             FAUCET_CLAIM_SEED.as_bytes(), faucet_claim.mint.as_ref(),
         ],
@@ -190,9 +194,7 @@ pub struct DestroyFaucetClaim<'info> {
     )]
     pub faucet_claim: Box<Account<'info, FaucetClaim>>,
     #[account(
-        mut,
         constraint = faucet_claim.is_finished(Clock::get()?.unix_timestamp as u64) @ FaucetError::FaucetNotFinished,
-        close = payer,
         seeds = [
             FAUCET_AUTHORITY_MANAGER_SEED.as_bytes(),
         ],
@@ -42,6 +42,7 @@ pub struct InitializeFaucetClaimShard<'info> {
     )]
     pub authority_manager: Box<Account<'info, AuthorityManager>>,
     #[account(
+        mut,
         seeds = [
             FAUCET_CLAIM_SEED.as_bytes(), mint.key().as_ref(),
         ],
@@ -172,6 +173,8 @@ pub struct Claim<'info> {
 }
 
 pub fn destroy_faucet_claim_shard(ctx: Context<DestroyFaucetClaimShard>) -> Result<()> {
+    ctx.accounts.faucet_claim.destroyed_shards += 1;
+
     emit!(DestroyFaucetClaimShardEvent {
         faucet_claim_id: ctx.accounts.faucet_claim.key(),
         faucet_claim_shard_id: ctx.accounts.faucet_claim_shard.key(),
@@ -184,6 +187,7 @@ pub struct DestroyFaucetClaimShard<'info> {
     #[account(mut, constraint = authority_manager.is_admin(payer.key) @ FaucetError::InvalidAdmin)]
     pub payer: Signer<'info>,
     #[account(
+        mut,
         seeds = [
             FAUCET_CLAIM_SEED.as_bytes(), faucet_claim.mint.as_ref(),
         ],
@@ -192,6 +196,7 @@ pub struct DestroyFaucetClaimShard<'info> {
     pub faucet_claim: Box<Account<'info, FaucetClaim>>,
     #[account(
         mut,
+        close = payer,
         seeds = [
             FAUCET_CLAIM_SHARD_SEED.as_bytes(), faucet_claim.key().as_ref(), &faucet_claim_shard.load()?.index.to_be_bytes(),
         ],
@@ -200,9 +205,7 @@ pub struct DestroyFaucetClaimShard<'info> {
     )]
     pub faucet_claim_shard: AccountLoader<'info, FaucetClaimShard>,
     #[account(
-        mut,
         constraint = faucet_claim.is_finished(Clock::get()?.unix_timestamp as u64) @ FaucetError::FaucetNotFinished,
-        close = payer,
         seeds = [
             FAUCET_AUTHORITY_MANAGER_SEED.as_bytes(),
         ],
@@ -12,6 +12,8 @@ pub struct FaucetClaim {
     pub total_claimed_amount: u64,
     pub shards: u16,
     pub bump: u8,
+    /// Shards closed by `destroy_faucet_claim_shard`, the claim is closed after all of them
+    pub destroyed_shards: u16,
 }
 
 impl FaucetClaim {
@@ -24,6 +26,10 @@ impl FaucetClaim {
         now >= self.claim_starts
     }
 
+    pub fn is_all_shards_destroyed(&self) -> bool {
+        self.destroyed_shards == self.shards
+    }
+
     pub fn rest_amount(&self) -> u64 {
         self.total_faucet_amount - self.total_claimed_amount
     }
@@ -0,0 +1,245 @@
+use super::pda::*;
+use crate::{accounts, instruction};
+use anchor_lang::{
+    prelude::*, solana_program::instruction::Instruction, solana_program::sysvar, system_program,
+    InstructionData,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address_with_program_id},
+    metadata, token,
+};
+use dex::client::pda as dex_pda;
+use faucet::client::pda as faucet_pda;
+
+/// Accounts of the dex creation, `mint` is the token of the payer and `mint_authority`
+/// the new token minted by the launcher
+#[derive(Clone, Debug)]
+pub struct InitializeDexKeys {
+    pub payer: Pubkey,
+    pub dex_config: Pubkey,
+    pub mint: Pubkey,
+    pub token_program_payer: Pubkey,
+    pub mint_authority: Pubkey,
+    pub token_program_authority: Pubkey,
+    /// Extra accounts of the mint transfer hooks
+    pub hook_accounts: Vec<AccountMeta>,
+}
+
+pub fn initialize_authority_manager(payer: Pubkey, faucet_authority: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeAuthorityManager {
+            payer,
+            authority_manager: authority_manager_address().0,
+            authority: authority_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeAuthorityManager { faucet_authority },
+        Vec::new(),
+    )
+}
+
+pub fn update_authority_manager_admin(payer: Pubkey, new_admin: Pubkey) -> Instruction {
+    build(
+        update_authority_manager_accounts(payer),
+        instruction::UpdateAuthorityManagerAdmin { new_admin },
+        Vec::new(),
+    )
+}
+
+pub fn update_authority_manager_faucet_authority(
+    payer: Pubkey,
+    faucet_authority: Pubkey,
+) -> Instruction {
+    build(
+        update_authority_manager_accounts(payer),
+        instruction::UpdateAuthorityManagerFaucetAuthority { faucet_authority },
+        Vec::new(),
+    )
+}
+
+fn update_authority_manager_accounts(payer: Pubkey) -> accounts::UpdateAuthorityManager {
+    accounts::UpdateAuthorityManager {
+        payer,
+        authority_manager: authority_manager_address().0,
+    }
+}
+
+pub fn initialize_config(payer: Pubkey) -> Instruction {
+    build(
+        accounts::InitializeConfig {
+            payer,
+            config: config_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeConfig {},
+        Vec::new(),
+    )
+}
+
+pub fn update_config_team_tokens(payer: Pubkey, team_tokens: u64) -> Instruction {
+    build(
+        update_config_accounts(payer),
+        instruction::UpdateConfigTeamTokens { team_tokens },
+        Vec::new(),
+    )
+}
+
+pub fn update_config_faucet_tokens(payer: Pubkey, faucet_tokens: u64) -> Instruction {
+    build(
+        update_config_accounts(payer),
+        instruction::UpdateConfigFaucetTokens { faucet_tokens },
+        Vec::new(),
+    )
+}
+
+fn update_config_accounts(payer: Pubkey) -> accounts::UpdateConfigState {
+    accounts::UpdateConfigState {
+        payer,
+        authority_manager: authority_manager_address().0,
+        config: config_address().0,
+    }
+}
+
+pub fn initialize_dex(keys: &InitializeDexKeys) -> Instruction {
+    let dex_state = dex_pda::dex_state_address(&keys.mint, &keys.mint_authority).0;
+
+    build(
+        accounts::CpiInitializeDex {
+            payer: keys.payer,
+            payer_vault: payer_vault(keys),
+            payer_vault_authority: payer_vault_authority(keys),
+            authority: authority_address().0,
+            authority_manager: authority_manager_address().0,
+            config: config_address().0,
+            dex_program: dex::id(),
+            dex_config: keys.dex_config,
+            dex_authority_manager: dex_pda::authority_manager_address().0,
+            dex_authority: dex_pda::authority_address().0,
+            dex_state,
+            dex_observation: dex_pda::observation_address(&dex_state).0,
+            dex_vault_authority: dex_pda::dex_vault_address(&dex_state, &keys.mint_authority).0,
+            dex_vault: dex_pda::dex_vault_address(&dex_state, &keys.mint).0,
+            team_vault: team_vault_address(&keys.mint_authority).0,
+            mint_authority: keys.mint_authority,
+            mint: keys.mint,
+            token_program: token::ID,
+            token_program_payer: keys.token_program_payer,
+            token_program_authority: keys.token_program_authority,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::InitializeDex {},
+        keys.hook_accounts.clone(),
+    )
+}
+
+/// Same as `initialize_dex` sending the faucet tokens to the faucet vault of the new mint
+pub fn initialize_dex_with_faucet(keys: &InitializeDexKeys) -> Instruction {
+    let dex_state = dex_pda::dex_state_address(&keys.mint, &keys.mint_authority).0;
+
+    build(
+        accounts::CpiInitializeDexWithFaucet {
+            payer: keys.payer,
+            payer_vault: payer_vault(keys),
+            payer_vault_authority: payer_vault_authority(keys),
+            authority: authority_address().0,
+            authority_manager: authority_manager_address().0,
+            config: config_address().0,
+            faucet_authority: faucet_pda::authority_address().0,
+            dex_program: dex::id(),
+            dex_config: keys.dex_config,
+            dex_authority_manager: dex_pda::authority_manager_address().0,
+            dex_authority: dex_pda::authority_address().0,
+            dex_state,
+            dex_observation: dex_pda::observation_address(&dex_state).0,
+            dex_vault_authority: dex_pda::dex_vault_address(&dex_state, &keys.mint_authority).0,
+            dex_vault: dex_pda::dex_vault_address(&dex_state, &keys.mint).0,
+            faucet_vault: faucet_pda::faucet_vault_address(&keys.mint_authority).0,
+            team_vault: team_vault_address(&keys.mint_authority).0,
+            mint_authority: keys.mint_authority,
+            mint: keys.mint,
+            token_program: token::ID,
+            token_program_payer: keys.token_program_payer,
+            token_program_authority: keys.token_program_authority,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::InitializeDexWithFaucet {},
+        keys.hook_accounts.clone(),
+    )
+}
+
+fn payer_vault(keys: &InitializeDexKeys) -> Pubkey {
+    get_associated_token_address_with_program_id(&keys.payer, &keys.mint, &keys.token_program_payer)
+}
+
+fn payer_vault_authority(keys: &InitializeDexKeys) -> Pubkey {
+    get_associated_token_address_with_program_id(
+        &keys.payer,
+        &keys.mint_authority,
+        &keys.token_program_authority,
+    )
+}
+
+/// Create the mint of a new token with its metadata, the mint is a new keypair signing the transaction
+pub fn initialize_mint(
+    payer: Pubkey,
+    mint: Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Instruction {
+    build(
+        accounts::InitializeMint {
+            payer,
+            authority_manager: authority_manager_address().0,
+            authority: authority_address().0,
+            mint,
+            metadata_account: mint_metadata_address(&mint).0,
+            token_program: token::ID,
+            token_metadata_program: metadata::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::InitializeMint { name, symbol, uri },
+        Vec::new(),
+    )
+}
+
+pub fn withdraw_team_tokens(
+    admin: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+    recipient: Pubkey,
+) -> Instruction {
+    build(
+        accounts::WithdrawTeamTokens {
+            admin,
+            authority: authority_address().0,
+            authority_manager: authority_manager_address().0,
+            team_vault: team_vault_address(&mint).0,
+            recipient,
+            mint,
+            token_program,
+        },
+        instruction::WithdrawTeamTokens {},
+        Vec::new(),
+    )
+}
+
+fn build(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(remaining_accounts);
+
+    Instruction {
+        program_id: crate::id(),
+        accounts: account_metas,
+        data: data.data(),
+    }
+}
@@ -0,0 +1,4 @@
+//! Client side helpers to derive the program addresses and build the instructions of the launcher
+
+pub mod instructions;
+pub mod pda;
@@ -0,0 +1,34 @@
+use crate::states::*;
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::metadata;
+
+pub fn authority_manager_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LAUNCHER_AUTHORITY_MANAGER_SEED.as_bytes()], &crate::id())
+}
+
+pub fn authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LAUNCHER_AUTHORITY_SEED.as_bytes()], &crate::id())
+}
+
+pub fn config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LAUNCHER_CONFIG_SEED.as_bytes()], &crate::id())
+}
+
+pub fn team_vault_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LAUNCHER_TEAM_VAULT_SEED.as_bytes(), mint.as_ref()],
+        &crate::id(),
+    )
+}
+
+/// Metadata account of the mint owned by the token metadata program
+pub fn mint_metadata_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            LAUNCHER_MINT_METADATA.as_bytes(),
+            metadata::ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &metadata::ID,
+    )
+}
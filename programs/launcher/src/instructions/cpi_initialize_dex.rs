@@ -72,6 +72,9 @@ pub struct CpiInitializeDex<'info> {
     /// CHECK: dex_state
     #[account(mut)]
     pub dex_state: UncheckedAccount<'info>,
+    /// CHECK: dex price observations
+    #[account(mut)]
+    pub dex_observation: UncheckedAccount<'info>,
     /// CHECK: zero mint account vault for the dex
     #[account(mut)]
     pub dex_vault_authority: UncheckedAccount<'info>,
@@ -181,6 +184,9 @@ pub struct CpiInitializeDexWithFaucet<'info> {
     /// CHECK: dex_state
     #[account(mut)]
     pub dex_state: UncheckedAccount<'info>,
+    /// CHECK: dex price observations
+    #[account(mut)]
+    pub dex_observation: UncheckedAccount<'info>,
     /// CHECK: zero mint account vault for the dex
     #[account(mut)]
     pub dex_vault_authority: UncheckedAccount<'info>,
@@ -243,6 +249,7 @@ struct DexInitializer<'info> {
     pub dex_authority_manager: AccountInfo<'info>,
     pub dex_authority: AccountInfo<'info>,
     pub dex_state: AccountInfo<'info>,
+    pub dex_observation: AccountInfo<'info>,
     pub dex_vault_authority: AccountInfo<'info>,
     pub dex_vault: AccountInfo<'info>,
     pub team_vault: AccountInfo<'info>,
@@ -254,6 +261,8 @@ struct DexInitializer<'info> {
     pub associated_token_program: AccountInfo<'info>,
     pub system_program: AccountInfo<'info>,
     pub rent: AccountInfo<'info>,
+    /// Extra accounts of the payer mint transfer hook, forwarded to the dex
+    pub hook_accounts: Vec<AccountInfo<'info>>,
 }
 
 impl<'info> DexInitializer<'info> {
@@ -273,6 +282,7 @@ impl<'info> DexInitializer<'info> {
             dex_authority_manager: ctx.accounts.dex_authority_manager.to_account_info(),
             dex_authority: ctx.accounts.dex_authority.to_account_info(),
             dex_state: ctx.accounts.dex_state.to_account_info(),
+            dex_observation: ctx.accounts.dex_observation.to_account_info(),
             dex_vault_authority: ctx.accounts.dex_vault_authority.to_account_info(),
             dex_vault: ctx.accounts.dex_vault.to_account_info(),
             team_vault: ctx.accounts.team_vault.to_account_info(),
@@ -284,6 +294,7 @@ impl<'info> DexInitializer<'info> {
             associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
             rent: ctx.accounts.rent.to_account_info(),
+            hook_accounts: ctx.remaining_accounts.to_vec(),
         }
     }
 
@@ -301,6 +312,7 @@ impl<'info> DexInitializer<'info> {
             dex_authority_manager: ctx.accounts.dex_authority_manager.to_account_info(),
             dex_authority: ctx.accounts.dex_authority.to_account_info(),
             dex_state: ctx.accounts.dex_state.to_account_info(),
+            dex_observation: ctx.accounts.dex_observation.to_account_info(),
             dex_vault_authority: ctx.accounts.dex_vault_authority.to_account_info(),
             dex_vault: ctx.accounts.dex_vault.to_account_info(),
             team_vault: ctx.accounts.team_vault.to_account_info(),
@@ -312,6 +324,7 @@ impl<'info> DexInitializer<'info> {
             associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
             rent: ctx.accounts.rent.to_account_info(),
+            hook_accounts: ctx.remaining_accounts.to_vec(),
         }
     }
 
@@ -420,6 +433,7 @@ impl<'info> DexInitializer<'info> {
             authority_manager: self.dex_authority_manager.clone(),
             authority: self.dex_authority.clone(),
             dex_state: self.dex_state.clone(),
+            observation_state: self.dex_observation.clone(),
             mint_zero: sorted_mints.mint_zero,
             mint_one: sorted_mints.mint_one,
             payer_vault_zero: sorted_mints.payer_vault_zero,
@@ -435,7 +449,8 @@ impl<'info> DexInitializer<'info> {
         };
 
         let cpi_context =
-            CpiContext::new_with_signer(self.dex_program.clone(), cpi_accounts, signer_seeds);
+            CpiContext::new_with_signer(self.dex_program.clone(), cpi_accounts, signer_seeds)
+                .with_remaining_accounts(self.hook_accounts.clone());
 
         dex::cpi::initialize_dex(
             cpi_context,
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+#[cfg(feature = "client")]
+pub mod client;
 pub mod errors;
 pub mod instructions;
 pub mod states;
@@ -0,0 +1,164 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::{ExecuteInstruction, TransferHookInstruction};
+
+declare_id!("GXZFYnt2CsATZpvGdvrEkMvNGxMvCJDRBrGML5FhtjS2");
+
+#[constant]
+pub const EXTRA_ACCOUNT_METAS_SEED: &str = "extra-account-metas";
+#[constant]
+pub const COUNTER_SEED: &str = "counter";
+
+/// Minimal transfer hook. It counts the transfers of a mint in a counter
+/// account resolved from the extra account metas, so local tests can check
+/// the extra accounts are forwarded on every transfer.
+#[program]
+pub mod transfer_hook {
+
+    use super::*;
+
+    /// Create the extra account metas of the mint and its transfer counter
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        // the counter of the mint, the mint is the account at index 1 of execute
+        let account_metas = vec![ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: COUNTER_SEED.as_bytes().to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            true,
+        )?];
+
+        let account_size = ExtraAccountMetaList::size_of(account_metas.len())?;
+        let mint = ctx.accounts.mint.key();
+        let seeds = [
+            EXTRA_ACCOUNT_METAS_SEED.as_bytes(),
+            mint.as_ref(),
+            &[ctx.bumps.extra_account_meta_list],
+        ];
+
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.extra_account_meta_list.to_account_info(),
+                },
+                &[seeds.as_slice()],
+            ),
+            Rent::get()?.minimum_balance(account_size),
+            account_size as u64,
+            ctx.program_id,
+        )?;
+
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &account_metas,
+        )?;
+
+        ctx.accounts.counter.bump = ctx.bumps.counter;
+
+        Ok(())
+    }
+
+    /// Count the transfer, called by the token program
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `amount` - amount transferred
+    ///
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.transfers = counter.transfers.checked_add(1).unwrap();
+        counter.amount = counter.amount.checked_add(amount).unwrap();
+
+        Ok(())
+    }
+
+    /// Route the transfer hook interface execute instruction to `transfer_hook`
+    pub fn fallback<'info>(
+        program_id: &Pubkey,
+        accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {
+        match TransferHookInstruction::unpack(data)? {
+            TransferHookInstruction::Execute { amount } => {
+                __private::__global::transfer_hook(program_id, accounts, &amount.to_le_bytes())
+            }
+            _ => Err(ProgramError::InvalidInstructionData.into()),
+        }
+    }
+}
+
+/// Transfers of a mint
+#[account]
+#[derive(Default, Debug)]
+pub struct CounterState {
+    pub bump: u8,
+    pub transfers: u64,
+    pub amount: u64,
+}
+
+impl CounterState {
+    pub const LEN: usize = 8 + std::mem::size_of::<Self>();
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: Extra account metas of the mint, created by the instruction
+    #[account(
+        mut,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED.as_bytes(), mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        seeds = [COUNTER_SEED.as_bytes(), mint.key().as_ref()],
+        bump,
+        payer = payer,
+        space = CounterState::LEN
+    )]
+    pub counter: Account<'info, CounterState>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts of the transfer hook interface execute instruction, in its order
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: source token account owner, can be a pda
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: Extra account metas of the mint
+    #[account(
+        seeds = [EXTRA_ACCOUNT_METAS_SEED.as_bytes(), mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [COUNTER_SEED.as_bytes(), mint.key().as_ref()],
+        bump = counter.bump
+    )]
+    pub counter: Account<'info, CounterState>,
+}
@@ -2,7 +2,7 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program, BN } from "@coral-xyz/anchor";
 import { Dex } from "../target/types/dex";
 import { Launcher } from "../target/types/launcher";
-import { Keypair } from "@solana/web3.js";
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { DexUtils } from "./utils";
 import { expect } from "chai";
 import { LauncherUtils } from "./utils/launcher.utils";
@@ -76,6 +76,299 @@ describe("dex.admin.test", () => {
     );
   });
 
+  it("Should update curve type", async () => {
+    await dexUtils.initializeAuthorityManager(signer, cpiAuthority);
+    let dexConfig = await dexUtils.initializeConfig(signer);
+
+    let newCurveType = 1;
+    await dexUtils.updateCurveType(signer, newCurveType);
+    let actual = (await dexUtils.getConfigState(dexConfig)).curveType;
+
+    expect(actual, "Curve type mismatch!").equal(newCurveType);
+
+    await dexUtils.updateCurveType(signer, 0);
+  });
+
+  it("Should update virtual reserves", async () => {
+    await dexUtils.initializeAuthorityManager(signer, cpiAuthority);
+    let dexConfig = await dexUtils.initializeConfig(signer);
+
+    let newVirtualReserve = new BN(30 * 10 ** 9);
+    let newVirtualSupply = new BN(200_000_000).mul(new BN(10 ** 9));
+    await dexUtils.updateVirtualReserve(signer, newVirtualReserve);
+    await dexUtils.updateVirtualSupply(signer, newVirtualSupply);
+    let config = await dexUtils.getConfigState(dexConfig);
+
+    expect(config.virtualReserve.toString(), "Virtual reserve mismatch!").equal(
+      newVirtualReserve.toString()
+    );
+    expect(config.virtualSupply.toString(), "Virtual supply mismatch!").equal(
+      newVirtualSupply.toString()
+    );
+
+    await dexUtils.updateVirtualReserve(signer, new BN(0));
+    await dexUtils.updateVirtualSupply(signer, new BN(0));
+  });
+
+  it("Should update creator fee", async () => {
+    await dexUtils.initializeAuthorityManager(signer, cpiAuthority);
+    let dexConfig = await dexUtils.initializeConfig(signer);
+
+    let newCreatorFeeRate = new BN(200_000);
+    await dexUtils.updateCreatorFeeRate(signer, newCreatorFeeRate);
+
+    let actual = (await dexUtils.getConfigState(dexConfig)).creatorFeeRate;
+    expect(actual.toNumber(), "Creator fee rate mismatch!").equal(
+      newCreatorFeeRate.toNumber()
+    );
+
+    await dexUtils.updateCreatorFeeRate(signer, new BN(0));
+  });
+
+  it("Should update referral fee", async () => {
+    await dexUtils.initializeAuthorityManager(signer, cpiAuthority);
+    let dexConfig = await dexUtils.initializeConfig(signer);
+
+    let newReferralFeeRate = new BN(100_000);
+    await dexUtils.updateReferralFeeRate(signer, newReferralFeeRate);
+
+    let actual = (await dexUtils.getConfigState(dexConfig)).referralFeeRate;
+    expect(actual.toNumber(), "Referral fee rate mismatch!").equal(
+      newReferralFeeRate.toNumber()
+    );
+
+    await dexUtils.updateReferralFeeRate(signer, new BN(0));
+  });
+
+  it("Should update sniper protection", async () => {
+    await dexUtils.initializeAuthorityManager(signer, cpiAuthority);
+    let dexConfig = await dexUtils.initializeConfig(signer);
+
+    let newSniperWindow = new BN(60);
+    let newSniperMaxSwapInput = new BN(1_000);
+    let newSniperMaxWalletInput = new BN(5_000);
+    await dexUtils.updateSniperProtection(
+      signer,
+      newSniperWindow,
+      newSniperMaxSwapInput,
+      newSniperMaxWalletInput
+    );
+
+    let config = await dexUtils.getConfigState(dexConfig);
+    expect(config.sniperWindow.toNumber(), "Sniper window mismatch!").equal(
+      newSniperWindow.toNumber()
+    );
+    expect(
+      config.sniperMaxSwapInput.toNumber(),
+      "Sniper max swap input mismatch!"
+    ).equal(newSniperMaxSwapInput.toNumber());
+    expect(
+      config.sniperMaxWalletInput.toNumber(),
+      "Sniper max wallet input mismatch!"
+    ).equal(newSniperMaxWalletInput.toNumber());
+
+    let failed = false;
+    try {
+      await dexUtils.updateSniperProtection(
+        signer,
+        newSniperWindow,
+        new BN(0),
+        newSniperMaxWalletInput
+      );
+    } catch {
+      failed = true;
+    }
+    expect(failed, "Zero swap input limit accepted!").equal(true);
+
+    await dexUtils.updateSniperProtection(
+      signer,
+      new BN(0),
+      new BN(0),
+      new BN(0)
+    );
+  });
+
+  it("Should update launch window", async () => {
+    await dexUtils.initializeAuthorityManager(signer, cpiAuthority);
+    let dexConfig = await dexUtils.initializeConfig(signer);
+
+    let newLaunchWindow = new BN(86_400);
+    await dexUtils.updateLaunchWindow(signer, newLaunchWindow);
+
+    let actual = (await dexUtils.getConfigState(dexConfig)).launchWindow;
+    expect(actual.toNumber(), "Launch window mismatch!").equal(
+      newLaunchWindow.toNumber()
+    );
+
+    await dexUtils.updateLaunchWindow(signer, new BN(0));
+  });
+
+  it("Should update launch lamports", async () => {
+    await dexUtils.initializeAuthorityManager(signer, cpiAuthority);
+    let dexConfig = await dexUtils.initializeConfig(signer);
+
+    let newLaunchLamports = new BN(LAMPORTS_PER_SOL);
+    let newKeeperReward = new BN(LAMPORTS_PER_SOL / 100);
+    await dexUtils.updateLaunchLamports(
+      signer,
+      newLaunchLamports,
+      newKeeperReward
+    );
+
+    let config = await dexUtils.getConfigState(dexConfig);
+    expect(
+      config.launchLamports.toNumber(),
+      "Launch lamports mismatch!"
+    ).equal(newLaunchLamports.toNumber());
+    expect(config.keeperReward.toNumber(), "Keeper reward mismatch!").equal(
+      newKeeperReward.toNumber()
+    );
+
+    await dexUtils.updateLaunchLamports(signer, new BN(0), new BN(0));
+  });
+
+  it("Should update launch target", async () => {
+    await dexUtils.initializeAuthorityManager(signer, cpiAuthority);
+    let dexConfig = await dexUtils.initializeConfig(signer);
+
+    await dexUtils.updateLaunchTarget(signer, 1);
+
+    let actual = (await dexUtils.getConfigState(dexConfig)).launchTarget;
+    expect(actual, "Launch target mismatch!").equal(1);
+
+    let failed = false;
+    try {
+      await dexUtils.updateLaunchTarget(signer, 2);
+    } catch {
+      failed = true;
+    }
+    expect(failed, "Unknown launch target accepted!").equal(true);
+
+    await dexUtils.updateLaunchTarget(signer, 0);
+  });
+
+  it("Should update lp policy", async () => {
+    await dexUtils.initializeAuthorityManager(signer, cpiAuthority);
+    let dexConfig = await dexUtils.initializeConfig(signer);
+
+    let newLpBurnRate = new BN(250_000);
+    let newLpLockCliff = new BN(86_400);
+    let newLpVestingPeriod = new BN(30 * 86_400);
+    await dexUtils.updateLpPolicy(
+      signer,
+      newLpBurnRate,
+      newLpLockCliff,
+      newLpVestingPeriod
+    );
+
+    let config = await dexUtils.getConfigState(dexConfig);
+    expect(config.lpBurnRate.toNumber(), "Lp burn rate mismatch!").equal(
+      newLpBurnRate.toNumber()
+    );
+    expect(config.lpLockCliff.toNumber(), "Lp lock cliff mismatch!").equal(
+      newLpLockCliff.toNumber()
+    );
+    expect(
+      config.lpVestingPeriod.toNumber(),
+      "Lp vesting period mismatch!"
+    ).equal(newLpVestingPeriod.toNumber());
+
+    await dexUtils.updateLpPolicy(
+      signer,
+      new BN(1_000_000),
+      new BN(0),
+      new BN(0)
+    );
+  });
+
+  it("Should update sniper fee", async () => {
+    await dexUtils.initializeAuthorityManager(signer, cpiAuthority);
+    let dexConfig = await dexUtils.initializeConfig(signer);
+
+    let newSniperFeeRate = new BN(30_000);
+    let newSniperFeePeriod = new BN(60);
+    let newSniperFeeSteps = new BN(3);
+    await dexUtils.updateSniperFee(
+      signer,
+      newSniperFeeRate,
+      newSniperFeePeriod,
+      newSniperFeeSteps
+    );
+
+    let config = await dexUtils.getConfigState(dexConfig);
+    expect(config.sniperFeeRate.toNumber(), "Sniper fee rate mismatch!").equal(
+      newSniperFeeRate.toNumber()
+    );
+    expect(
+      config.sniperFeePeriod.toNumber(),
+      "Sniper fee period mismatch!"
+    ).equal(newSniperFeePeriod.toNumber());
+    expect(
+      config.sniperFeeSteps.toNumber(),
+      "Sniper fee steps mismatch!"
+    ).equal(newSniperFeeSteps.toNumber());
+
+    let failed = false;
+    try {
+      await dexUtils.updateSniperFee(
+        signer,
+        new BN(30_001),
+        newSniperFeePeriod,
+        newSniperFeeSteps
+      );
+    } catch {
+      failed = true;
+    }
+    expect(failed, "Sniper fee rate above max fee rate!").equal(true);
+
+    await dexUtils.updateSniperFee(signer, new BN(0), new BN(0), new BN(0));
+  });
+
+  it("Should update fee recipients", async () => {
+    await dexUtils.initializeAuthorityManager(signer, cpiAuthority);
+
+    let treasury = Keypair.generate().publicKey;
+    let recipients = [
+      { wallet: signer.publicKey, weightBps: 8_000 },
+      { wallet: treasury, weightBps: 2_000 },
+    ];
+    await dexUtils.updateFeeRecipients(signer, recipients);
+
+    let feeDistribution = await dexUtils.getFeeDistributionState();
+    expect(
+      feeDistribution.recipientsCount,
+      "Fee recipients count mismatch!"
+    ).equal(recipients.length);
+    recipients.forEach((recipient, index) => {
+      let actual = feeDistribution.recipients[index];
+      expect(actual.wallet.toString(), "Fee recipient mismatch!").equal(
+        recipient.wallet.toString()
+      );
+      expect(actual.weightBps, "Fee weight mismatch!").equal(
+        recipient.weightBps
+      );
+    });
+
+    await dexUtils.updateFeeRecipients(signer, [
+      { wallet: signer.publicKey, weightBps: 10_000 },
+    ]);
+  });
+
+  it("Should create and disable config tier", async () => {
+    await dexUtils.initializeAuthorityManager(signer, cpiAuthority);
+    let tierIndex = 1;
+    let dexConfig = await dexUtils.initializeConfig(signer, tierIndex);
+
+    let config = await dexUtils.getConfigState(dexConfig);
+    expect(config.index, "Config index mismatch!").equal(tierIndex);
+
+    await dexUtils.updateConfigDisabled(signer, true, tierIndex);
+    let actual = (await dexUtils.getConfigState(dexConfig)).disabled;
+    expect(actual, "Config disabled mismatch!").equal(true);
+
+    await dexUtils.updateConfigDisabled(signer, false, tierIndex);
+  });
+
   it("Should update cpi authority", async () => {
     let authorityManager = await dexUtils.initializeAuthorityManager(
       signer,
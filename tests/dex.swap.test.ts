@@ -3,7 +3,14 @@ import { Program, BN } from "@coral-xyz/anchor";
 import { Dex } from "../target/types/dex";
 import { Faucet } from "../target/types/faucet";
 import { Launcher } from "../target/types/launcher";
-import { Commitment, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { TransferHook } from "../target/types/transfer_hook";
+import {
+  Commitment,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  SystemProgram,
+  Transaction,
+} from "@solana/web3.js";
 import {
   DexUtils,
   TokenUtils,
@@ -14,7 +21,13 @@ import {
   SwapCalculator,
   SetupSwapTest,
   FaucetUtils,
+  WhirlpoolUtils,
+  TransferHookUtils,
 } from "./utils";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
 import { expect } from "chai";
 import { LauncherUtils } from "./utils/launcher.utils";
 
@@ -32,6 +45,13 @@ describe("dex.swap.test", () => {
   const launcherUtils = new LauncherUtils(launcherProgram, confirmOptions);
   const dexUtils = new DexUtils(dexProgram, confirmOptions);
   const raydiumUtils = new RaydiumUtils(raydiumProgram, confirmOptions);
+  const whirlpoolUtils = new WhirlpoolUtils(anchor.getProvider().connection);
+  const transferHookProgram = anchor.workspace
+    .TransferHook as Program<TransferHook>;
+  const transferHookUtils = new TransferHookUtils(
+    transferHookProgram,
+    confirmOptions
+  );
   const tokenUtils = new TokenUtils(
     anchor.getProvider().connection,
     confirmOptions
@@ -62,6 +82,302 @@ describe("dex.swap.test", () => {
           expect(actual, "Dex ready to launch!").equal(false);
         });
 
+        it("Should quote base input", async () => {
+          let swapTest = await swapInputTemplate.setupSwapBaseInput(signer);
+          let args = swapTest.swapBaseInputArgs;
+
+          let quote = await dexUtils.quoteBaseInput(args);
+
+          let outputBefore = await tokenUtils.getBalance(args.outputAta);
+          await dexUtils.swapBaseInput(signer, args);
+          let outputAfter = await tokenUtils.getBalance(args.outputAta);
+
+          expect(
+            quote.amountReceived.toString(),
+            "Quoted output mismatch!"
+          ).equal(outputAfter.sub(outputBefore).toString());
+          expect(
+            quote.protocolFee.toString(),
+            "Quoted protocol fee mismatch!"
+          ).equal(swapTest.swapInputExpected.swapResult.protocolFee.toString());
+        });
+
+        it("Should record price observations", async () => {
+          let swapTest = await swapInputTemplate.setupSwapBaseInput(signer);
+
+          await dexUtils.swapBaseInput(signer, swapTest.swapBaseInputArgs);
+
+          let observation = await dexUtils.getObservationState(
+            swapTest.dexAccounts.observation
+          );
+          expect(observation.initialized, "Observation not initialized!").equal(
+            true
+          );
+          expect(observation.dexId.toBase58()).equal(
+            swapTest.dexAccounts.dex.toBase58()
+          );
+
+          await sleep(2000);
+          await dexUtils.swapBaseInput(signer, swapTest.swapBaseInputArgs);
+
+          observation = await dexUtils.getObservationState(
+            swapTest.dexAccounts.observation
+          );
+          let latest = observation.observations[observation.observationIndex];
+          expect(observation.observationIndex).equal(1);
+          expect(latest.cumulativeToken0PriceX32.gtn(0)).equal(true);
+          expect(latest.cumulativeToken1PriceX32.gtn(0)).equal(true);
+        });
+
+        it("Should record trader stats", async () => {
+          let swapTest = await swapInputTemplate.setupSwapBaseInput(signer);
+          let args = { ...swapTest.swapBaseInputArgs, traderStats: true };
+          let [traderStats] = dexUtils.pdaGetter.getTraderStatsAddress(
+            swapTest.dexAccounts.dex,
+            signer.publicKey
+          );
+
+          let inputBefore = await tokenUtils.getBalance(args.inputAta);
+          await dexUtils.swapBaseInput(signer, args);
+          await dexUtils.swapBaseInput(signer, args);
+          let inputAfter = await tokenUtils.getBalance(args.inputAta);
+
+          // swaps without the account are not recorded
+          await dexUtils.swapBaseInput(signer, swapTest.swapBaseInputArgs);
+
+          let traderStatsState = await dexUtils.getTraderStatsState(
+            traderStats
+          );
+          expect(
+            traderStatsState.trader.toBase58(),
+            "Trader mismatch!"
+          ).equal(signer.publicKey.toBase58());
+          expect(
+            traderStatsState.tradeCount.toNumber(),
+            "Trade count mismatch!"
+          ).equal(2);
+          expect(
+            traderStatsState.buyVolume.toString(),
+            "Buy volume mismatch!"
+          ).equal(inputBefore.sub(inputAfter).toString());
+          expect(traderStatsState.sellVolume.toNumber()).equal(0);
+          expect(
+            traderStatsState.lastTradeTimestamp.gte(
+              traderStatsState.firstTradeTimestamp
+            )
+          ).equal(true);
+        });
+
+        it("Should fill triggered limit orders", async () => {
+          let swapTest = await swapInputTemplate.setupSwapBaseInput(signer);
+          let args = swapTest.swapBaseInputArgs;
+          let orderArgs = {
+            inputToken: args.inputToken,
+            inputTokenProgram: args.inputTokenProgram,
+            inputAta: args.inputAta,
+            dexAccounts: args.dexAccounts,
+            amountIn: args.amountIn,
+            minimumAmountOut: new BN(0),
+          };
+          let [triggered] = dexUtils.pdaGetter.getLimitOrderAddress(
+            args.dexAccounts.dex,
+            signer.publicKey,
+            new BN(0)
+          );
+          let [resting] = dexUtils.pdaGetter.getLimitOrderAddress(
+            args.dexAccounts.dex,
+            signer.publicKey,
+            new BN(1)
+          );
+
+          let inputBefore = await tokenUtils.getBalance(args.inputAta);
+          // any positive price is at or above 1, no price reaches 2^120
+          await dexUtils.placeLimitOrder(signer, {
+            ...orderArgs,
+            orderId: new BN(0),
+            triggerPriceX32: new BN(1),
+            triggerAbove: true,
+          });
+          await dexUtils.placeLimitOrder(signer, {
+            ...orderArgs,
+            orderId: new BN(1),
+            triggerPriceX32: new BN(2).pow(new BN(120)),
+            triggerAbove: true,
+          });
+
+          // each escrow is owned by its order, not by the dex authority
+          let [restingEscrow] =
+            dexUtils.pdaGetter.getLimitOrderEscrowAddress(resting);
+          let escrowAccount = await getAccount(
+            anchor.getProvider().connection,
+            restingEscrow,
+            undefined,
+            args.inputTokenProgram
+          );
+          expect(
+            escrowAccount.owner.toBase58(),
+            "Escrow not owned by order!"
+          ).equal(resting.toBase58());
+
+          let outputBefore = await tokenUtils.getBalance(args.outputAta);
+          await dexUtils.fillOrders(signer, args, [
+            {
+              order: triggered,
+              owner: signer.publicKey,
+              outputAta: args.outputAta,
+            },
+            {
+              order: resting,
+              owner: signer.publicKey,
+              outputAta: args.outputAta,
+            },
+          ]);
+          let outputAfter = await tokenUtils.getBalance(args.outputAta);
+
+          expect(
+            await dexUtils.getLimitOrderState(triggered),
+            "Filled order not closed!"
+          ).equal(null);
+          expect(outputAfter.gt(outputBefore), "No order output!").equal(true);
+
+          let restingState = await dexUtils.getLimitOrderState(resting);
+          expect(
+            restingState.amountIn.toString(),
+            "Resting order filled!"
+          ).equal(args.amountIn.toString());
+
+          await dexUtils.cancelLimitOrder(signer, {
+            ...orderArgs,
+            orderId: new BN(1),
+          });
+          expect(
+            await dexUtils.getLimitOrderState(resting),
+            "Cancelled order not closed!"
+          ).equal(null);
+
+          let inputAfter = await tokenUtils.getBalance(args.inputAta);
+          expect(
+            inputBefore.sub(inputAfter).toString(),
+            "Only the filled order spends input!"
+          ).equal(args.amountIn.toString());
+        });
+
+        it("Should limit buys during anti-sniper window", async () => {
+          await dexUtils.initializeAuthorityManager(
+            signer,
+            launcherUtils.pdaGetter.getAuthorityAddress()[0]
+          );
+          await dexUtils.initializeConfig(signer);
+          await dexUtils.updateSniperProtection(
+            signer,
+            new BN(600),
+            new BN(1_000),
+            new BN(1_500)
+          );
+
+          let swapTest = await swapInputTemplate.setupSwapBaseInput(signer);
+
+          await dexUtils.updateSniperProtection(
+            signer,
+            new BN(0),
+            new BN(0),
+            new BN(0)
+          );
+
+          await dexUtils.swapBaseInput(signer, swapTest.swapBaseInputArgs);
+
+          let [walletLimit] = dexUtils.pdaGetter.getWalletLimitAddress(
+            swapTest.dexAccounts.dex,
+            signer.publicKey
+          );
+          let walletLimitState = await dexUtils.getWalletLimitState(
+            walletLimit
+          );
+          expect(
+            walletLimitState.inputAmount.toNumber(),
+            "Wallet input mismatch!"
+          ).equal(swapTest.swapBaseInputArgs.amountIn.toNumber());
+
+          let failed = false;
+          try {
+            await dexUtils.swapBaseInput(signer, swapTest.swapBaseInputArgs);
+          } catch {
+            failed = true;
+          }
+          expect(failed, "Wallet input limit exceeded!").equal(true);
+        });
+
+        it("Should refund after launch deadline", async () => {
+          await dexUtils.initializeAuthorityManager(
+            signer,
+            launcherUtils.pdaGetter.getAuthorityAddress()[0]
+          );
+          await dexUtils.initializeConfig(signer);
+          await dexUtils.updateLaunchWindow(signer, new BN(30));
+
+          let swapTest = await swapInputTemplate.setupSwapBaseInput(signer);
+
+          await dexUtils.updateLaunchWindow(signer, new BN(0));
+
+          let args = swapTest.swapBaseInputArgs;
+          let supplyBefore = await tokenUtils.getBalance(args.outputAta);
+          await dexUtils.swapBaseInput(signer, args);
+          let bought = (await tokenUtils.getBalance(args.outputAta)).sub(
+            supplyBefore
+          );
+
+          let launchDeadline = (
+            await dexUtils.getDexState(swapTest.dexAccounts.dex)
+          ).launchDeadline.toNumber();
+          await sleep(Math.max(0, launchDeadline * 1000 - Date.now()) + 2000);
+
+          let failed = false;
+          try {
+            await dexUtils.swapBaseInput(signer, args);
+          } catch {
+            failed = true;
+          }
+          expect(failed, "Swap after launch deadline!").equal(true);
+
+          let refundArgs = {
+            supplyToken: args.outputToken,
+            supplyTokenProgram: args.outputTokenProgram,
+            reserveToken: args.inputToken,
+            reserveTokenProgram: args.inputTokenProgram,
+            supplyAta: args.outputAta,
+            reserveAta: args.inputAta,
+            supplyVault: args.outputVault,
+            reserveVault: args.inputVault,
+            amount: bought,
+            dexAccounts: swapTest.dexAccounts,
+          };
+          let reserveBefore = await tokenUtils.getBalance(args.inputAta);
+          await dexUtils.refund(signer, refundArgs);
+          let refunded = (await tokenUtils.getBalance(args.inputAta)).sub(
+            reserveBefore
+          );
+          expect(refunded.gtn(0), "Nothing refunded!").equal(true);
+
+          let dexState = await dexUtils.getDexState(swapTest.dexAccounts.dex);
+          let seedReserve = dexState.seedReserve;
+          reserveBefore = await tokenUtils.getBalance(args.inputAta);
+          await dexUtils.withdrawSeedReserve(signer, refundArgs);
+          let withdrawn = (await tokenUtils.getBalance(args.inputAta)).sub(
+            reserveBefore
+          );
+          expect(withdrawn.toString(), "Seed reserve mismatch!").equal(
+            seedReserve.toString()
+          );
+          // the only buyer redeemed the whole reserve the curve collected, the fees stay
+          let reserveFees = dexState.token0Mint.equals(args.inputToken)
+            ? dexState.swapFeesToken0.add(dexState.creatorFeesToken0)
+            : dexState.swapFeesToken1.add(dexState.creatorFeesToken1);
+          expect(
+            (await tokenUtils.getBalance(args.inputVault)).toString(),
+            "Reserve left in the vault!"
+          ).equal(reserveFees.toString());
+        });
+
         it("Should swap base input and prepared to launch", async () => {
           let swapTest = await swapInputTemplate.setupSwapBaseInput(
             signer,
@@ -105,6 +421,184 @@ describe("dex.swap.test", () => {
           expect(actual, "Dex ready to launch!").equal(false);
         });
 
+        it("Should swap route through two dex", async () => {
+          let routeTest = await swapInputTemplate.setupSwapRoute(signer);
+          await dexUtils.swapBaseInput(signer, routeTest.swapBaseInputArgs);
+
+          let args = routeTest.swapRouteArgs;
+          let reserveBefore = await tokenUtils.getBalance(args.first.outputAta);
+          let outputBefore = await tokenUtils.getBalance(args.second.outputAta);
+
+          await dexUtils.swapRoute(signer, args);
+
+          let reserveAfter = await tokenUtils.getBalance(args.first.outputAta);
+          let outputAfter = await tokenUtils.getBalance(args.second.outputAta);
+          expect(
+            reserveAfter.toString(),
+            "Shared token balance changed!"
+          ).equal(reserveBefore.toString());
+          expect(outputAfter.gt(outputBefore), "No route output!").equal(true);
+        });
+
+        it("Should fail swap route below minimum amount out", async () => {
+          let routeTest = await swapInputTemplate.setupSwapRoute(signer);
+          await dexUtils.swapBaseInput(signer, routeTest.swapBaseInputArgs);
+
+          let args = {
+            ...routeTest.swapRouteArgs,
+            minimumAmountOut: new BN("18446744073709551615"),
+          };
+
+          let failed = false;
+          try {
+            await dexUtils.swapRoute(signer, args);
+          } catch {
+            failed = true;
+          }
+          expect(failed, "Route minimum amount out exceeded!").equal(true);
+        });
+
+        it("Should swap base input with native SOL", async () => {
+          let nativeVault = await tokenUtils.initializeNativeMint(
+            signer,
+            3 * LAMPORTS_PER_SOL
+          );
+          let swapTest = await swapInputTemplate.setupSwapBaseInput(
+            signer,
+            false,
+            true,
+            nativeVault
+          );
+          let args = {
+            ...swapTest.swapBaseInputArgs,
+            amountIn: new BN(LAMPORTS_PER_SOL / 10),
+          };
+          let connection = anchor.getProvider().connection;
+          let [nativeTokenAccount] = dexUtils.pdaGetter.getNativeAccountAddress(
+            signer.publicKey
+          );
+
+          // buy with lamports
+          let lamportsBefore = await connection.getBalance(signer.publicKey);
+          let wrappedBefore = await tokenUtils.getBalance(args.inputAta);
+          let supplyBefore = await tokenUtils.getBalance(args.outputAta);
+          await dexUtils.swapBaseInputNative(signer, args);
+
+          let lamportsAfter = await connection.getBalance(signer.publicKey);
+          let wrappedAfter = await tokenUtils.getBalance(args.inputAta);
+          let supplyAfter = await tokenUtils.getBalance(args.outputAta);
+          expect(
+            lamportsBefore - lamportsAfter >= args.amountIn.toNumber(),
+            "Lamports not paid!"
+          ).equal(true);
+          expect(wrappedAfter.toString(), "Wrapped balance changed!").equal(
+            wrappedBefore.toString()
+          );
+          expect(supplyAfter.gt(supplyBefore), "No supply bought!").equal(
+            true
+          );
+          expect(
+            await connection.getAccountInfo(nativeTokenAccount),
+            "Native token account not closed!"
+          ).equal(null);
+
+          // a funded native token account address doesn't block the swap
+          await anchor.getProvider().sendAndConfirm(
+            new Transaction().add(
+              SystemProgram.transfer({
+                fromPubkey: signer.publicKey,
+                toPubkey: nativeTokenAccount,
+                lamports: LAMPORTS_PER_SOL / 100,
+              })
+            )
+          );
+
+          // sell for lamports
+          await dexUtils.swapBaseInputNative(signer, {
+            ...args,
+            inputToken: args.outputToken,
+            inputTokenProgram: args.outputTokenProgram,
+            outputToken: args.inputToken,
+            outputTokenProgram: args.inputTokenProgram,
+            inputAta: args.outputAta,
+            outputAta: args.inputAta,
+            inputVault: args.outputVault,
+            outputVault: args.inputVault,
+            amountIn: supplyAfter.sub(supplyBefore),
+            minimumAmountOut: new BN(1),
+          });
+
+          let lamportsSold = await connection.getBalance(signer.publicKey);
+          let supplySold = await tokenUtils.getBalance(args.outputAta);
+          expect(lamportsSold > lamportsAfter, "No lamports received!").equal(
+            true
+          );
+          expect(supplySold.toString(), "Supply not sold!").equal(
+            supplyBefore.toString()
+          );
+        });
+
+        it("Should swap base input and split withdrawn fees", async () => {
+          await dexUtils.initializeAuthorityManager(
+            signer,
+            launcherUtils.pdaGetter.getAuthorityAddress()[0]
+          );
+          let swapTest = await swapInputTemplate.setupSwapBaseInput(signer);
+          await dexUtils.swapBaseInput(signer, swapTest.swapBaseInputArgs);
+
+          let protocolFee = await swapInputTemplate.getDexSwapFees(
+            swapTest.dexAccounts,
+            swapTest.zeroToOne
+          );
+
+          let recipients = [
+            { wallet: Keypair.generate().publicKey, weightBps: 7_000 },
+            { wallet: Keypair.generate().publicKey, weightBps: 3_000 },
+          ];
+          await dexUtils.updateFeeRecipients(signer, recipients);
+
+          let recipientTokenAccounts = [];
+          for (let recipient of recipients) {
+            recipientTokenAccounts.push(
+              await tokenUtils.createAta(
+                signer,
+                recipient.wallet,
+                swapTest.dexAccounts.vaultZero.mint.address
+              ),
+              await tokenUtils.createAta(
+                signer,
+                recipient.wallet,
+                swapTest.dexAccounts.vaultOne.mint.address
+              )
+            );
+          }
+
+          await dexUtils.withdrawDexFee(signer, {
+            dexAccounts: swapTest.dexAccounts,
+            recipientTokenAccounts,
+          });
+
+          await dexUtils.updateFeeRecipients(signer, [
+            { wallet: signer.publicKey, weightBps: 10_000 },
+          ]);
+
+          // the protocol fee is taken from the input token
+          let offset = swapTest.zeroToOne ? 0 : 1;
+          let firstShare = protocolFee.muln(7_000).divn(10_000);
+          let actualFirst = await tokenUtils.getBalance(
+            recipientTokenAccounts[offset]
+          );
+          let actualSecond = await tokenUtils.getBalance(
+            recipientTokenAccounts[2 + offset]
+          );
+          expect(actualFirst.toString(), "First share mismatch!").equal(
+            firstShare.toString()
+          );
+          expect(actualSecond.toString(), "Second share mismatch!").equal(
+            protocolFee.sub(firstShare).toString()
+          );
+        });
+
         it("Should swap 5 times base input with fee", async () => {
           let swapTest = await swapInputTemplate.setupSwapBaseInput(signer);
 
@@ -156,7 +650,7 @@ describe("dex.swap.test", () => {
           );
 
           let expectedLaunchFee = swapCalculator.curve.Fee(
-            swapTest.swapBaseInputArgs.amountIn
+            swapTest.swapInputExpected.inputAmountFee.amount
               .add(initDexVaultAmount)
               .sub(swapTest.swapInputExpected.swapResult.protocolFee),
             launchFeeRate
@@ -184,6 +678,27 @@ describe("dex.swap.test", () => {
             "Dex not ready to launch!"
           ).equal(true);
 
+          let reserveBound = (
+            await dexUtils.getDexState(swapTest.dexAccounts.dex)
+          ).vaultReserveBound;
+          let reserveVaultAmount = await swapInputTemplate.getDexBalance(
+            swapTest.dexAccounts,
+            swapTest.zeroToOne
+          );
+          expect(
+            reserveVaultAmount.toString(),
+            "Reserve vault overshoots the bound!"
+          ).equal(reserveBound.toString());
+
+          let userReserveAmount = await swapInputTemplate.getAtaBalance(
+            swapTest.atas,
+            swapTest.zeroToOne
+          );
+          expect(
+            userReserveAmount.toString(),
+            "Unused input wasn't left with the user!"
+          ).equal(swapTest.swapInputExpected.unfilledAmount.toString());
+
           let [raydiumPool] = raydiumUtils.pdaGetter.getStateAddress(
             ammConfigAddress,
             swapTest.dexAccounts.vaultZero.mint.address,
@@ -221,6 +736,259 @@ describe("dex.swap.test", () => {
             "Raydium pool wasn't created!"
           ).not.to.be.null;
         });
+
+        it("Should swap base input and crank launch by keeper", async () => {
+          await dexUtils.initializeAuthorityManager(
+            signer,
+            launcherUtils.pdaGetter.getAuthorityAddress()[0]
+          );
+          await dexUtils.initializeConfig(signer);
+          let keeperReward = new BN(LAMPORTS_PER_SOL / 100);
+          await dexUtils.updateLaunchLamports(
+            signer,
+            new BN(LAMPORTS_PER_SOL),
+            keeperReward
+          );
+
+          let swapTest = await swapInputTemplate.setupSwapBaseInput(
+            signer,
+            true
+          );
+
+          await dexUtils.updateLaunchLamports(signer, new BN(0), new BN(0));
+
+          let launchLamports = (
+            await dexUtils.getDexState(swapTest.dexAccounts.dex)
+          ).launchLamports;
+          expect(launchLamports.toNumber(), "Launch lamports mismatch!").equal(
+            LAMPORTS_PER_SOL
+          );
+
+          await dexUtils.swapBaseInput(signer, swapTest.swapBaseInputArgs);
+
+          let keeper = new Keypair();
+          let connection = anchor.getProvider().connection;
+          await anchor
+            .getProvider()
+            .sendAndConfirm(
+              new Transaction().add(
+                SystemProgram.transfer({
+                  fromPubkey: signer.publicKey,
+                  toPubkey: keeper.publicKey,
+                  lamports: LAMPORTS_PER_SOL / 10,
+                })
+              )
+            );
+          let keeperBefore = await connection.getBalance(keeper.publicKey);
+
+          await dexUtils.crankLaunchDex(keeper, {
+            cpSwapProgram: raydiumProgram.programId,
+            raydiumAmmConfig: ammConfigAddress,
+            raydiumPdaGetter: raydiumUtils.pdaGetter,
+            dexAccounts: swapTest.dexAccounts,
+          });
+          await sleep(1000);
+
+          let [raydiumPool] = raydiumUtils.pdaGetter.getStateAddress(
+            ammConfigAddress,
+            swapTest.dexAccounts.vaultZero.mint.address,
+            swapTest.dexAccounts.vaultOne.mint.address
+          );
+          expect(
+            await raydiumUtils.getPoolState(raydiumPool),
+            "Raydium pool wasn't created!"
+          ).not.to.be.null;
+
+          let keeperAfter = await connection.getBalance(keeper.publicKey);
+          expect(keeperAfter > keeperBefore, "Keeper not rewarded!").equal(
+            true
+          );
+
+          let dexState = await dexUtils.getDexState(swapTest.dexAccounts.dex);
+          expect(dexState.isLaunched, "Dex not launched!").equal(true);
+          expect(
+            dexState.launchLamports.toNumber(),
+            "Launch lamports not released!"
+          ).equal(0);
+        });
+
+        it("Should reject crank launch with foreign vaults", async () => {
+          await dexUtils.initializeAuthorityManager(
+            signer,
+            launcherUtils.pdaGetter.getAuthorityAddress()[0]
+          );
+          await dexUtils.initializeConfig(signer);
+          await dexUtils.updateLaunchLamports(
+            signer,
+            new BN(LAMPORTS_PER_SOL),
+            new BN(LAMPORTS_PER_SOL / 100)
+          );
+
+          let swapTest = await swapInputTemplate.setupSwapBaseInput(
+            signer,
+            true
+          );
+          let foreignTest = await swapInputTemplate.setupSwapBaseInput(
+            signer,
+            true
+          );
+
+          await dexUtils.updateLaunchLamports(signer, new BN(0), new BN(0));
+
+          await dexUtils.swapBaseInput(signer, swapTest.swapBaseInputArgs);
+
+          let failed = false;
+          try {
+            await dexUtils.crankLaunchDex(signer, {
+              cpSwapProgram: raydiumProgram.programId,
+              raydiumAmmConfig: ammConfigAddress,
+              raydiumPdaGetter: raydiumUtils.pdaGetter,
+              dexAccounts: {
+                ...swapTest.dexAccounts,
+                vaultZero: foreignTest.dexAccounts.vaultZero,
+                vaultOne: foreignTest.dexAccounts.vaultOne,
+              },
+            });
+          } catch {
+            failed = true;
+          }
+          expect(failed, "Launched with foreign vaults!").equal(true);
+
+          let foreignVaultBalance = await tokenUtils.getBalance(
+            foreignTest.dexAccounts.vaultZero.address
+          );
+          expect(foreignVaultBalance.gtn(0), "Foreign vault drained!").equal(
+            true
+          );
+
+          let dexState = await dexUtils.getDexState(swapTest.dexAccounts.dex);
+          expect(dexState.isLaunched, "Dex launched!").equal(false);
+        });
+
+        it("Should swap base input, launch and claim locked LP", async () => {
+          await dexUtils.initializeAuthorityManager(
+            signer,
+            launcherUtils.pdaGetter.getAuthorityAddress()[0]
+          );
+          await dexUtils.initializeConfig(signer);
+          await dexUtils.updateLpPolicy(
+            signer,
+            new BN(500_000),
+            new BN(0),
+            new BN(0)
+          );
+
+          let swapTest = await swapInputTemplate.setupSwapBaseInput(
+            signer,
+            true
+          );
+
+          await dexUtils.swapBaseInput(signer, swapTest.swapBaseInputArgs);
+          await dexUtils.launchDex(signer, {
+            cpSwapProgram: raydiumProgram.programId,
+            raydiumAmmConfig: ammConfigAddress,
+            raydiumPdaGetter: raydiumUtils.pdaGetter,
+            dexAccounts: swapTest.dexAccounts,
+            sharedLamports: new BN(LAMPORTS_PER_SOL),
+          });
+
+          await dexUtils.updateLpPolicy(
+            signer,
+            new BN(1_000_000),
+            new BN(0),
+            new BN(0)
+          );
+
+          let dexState = await dexUtils.getDexState(swapTest.dexAccounts.dex);
+          expect(dexState.lpLockedAmount.gtn(0), "No LP locked!").equal(true);
+
+          let recipientLpToken = await tokenUtils.createAta(
+            signer,
+            signer.publicKey,
+            dexState.lpMint
+          );
+          await dexUtils.claimLockedLp(signer, {
+            dexAccounts: swapTest.dexAccounts,
+            lpMint: dexState.lpMint,
+            recipientLpToken,
+          });
+
+          let claimed = await tokenUtils.getBalance(recipientLpToken);
+          expect(claimed.toString(), "Claimed LP mismatch!").equal(
+            dexState.lpLockedAmount.toString()
+          );
+        });
+
+        it("Should swap base input and launch to clmm", async () => {
+          await dexUtils.initializeAuthorityManager(
+            signer,
+            launcherUtils.pdaGetter.getAuthorityAddress()[0]
+          );
+          await dexUtils.initializeConfig(signer);
+          await dexUtils.updateLaunchTarget(signer, 1);
+
+          let swapTest = await swapInputTemplate.setupSwapBaseInput(
+            signer,
+            true
+          );
+
+          await dexUtils.updateLaunchTarget(signer, 0);
+
+          await dexUtils.swapBaseInput(signer, swapTest.swapBaseInputArgs);
+
+          let failed = false;
+          try {
+            await dexUtils.launchDex(signer, {
+              cpSwapProgram: raydiumProgram.programId,
+              raydiumAmmConfig: ammConfigAddress,
+              raydiumPdaGetter: raydiumUtils.pdaGetter,
+              dexAccounts: swapTest.dexAccounts,
+              sharedLamports: new BN(LAMPORTS_PER_SOL),
+            });
+          } catch {
+            failed = true;
+          }
+          expect(failed, "Launched to another target!").equal(true);
+
+          await dexUtils.launchDexClmm(signer, {
+            whirlpoolPdaGetter: whirlpoolUtils.pdaGetter,
+            dexAccounts: swapTest.dexAccounts,
+            sharedLamports: new BN(LAMPORTS_PER_SOL),
+          });
+
+          let [pool] = whirlpoolUtils.pdaGetter.getWhirlpoolAddress(
+            swapTest.dexAccounts.vaultZero.mint.address,
+            swapTest.dexAccounts.vaultOne.mint.address
+          );
+          expect(
+            (await whirlpoolUtils.getAccountOwner(pool))?.toBase58(),
+            "Whirlpool wasn't created!"
+          ).equal(whirlpoolUtils.pdaGetter.programId.toBase58());
+
+          // the position token of the full range position stays with the dex authority
+          let [positionMint] = dexUtils.pdaGetter.getClmmPositionMintAddress(
+            swapTest.dexAccounts.dex
+          );
+          let [position] =
+            whirlpoolUtils.pdaGetter.getPositionAddress(positionMint);
+          expect(
+            (await whirlpoolUtils.getAccountOwner(position))?.toBase58(),
+            "Position wasn't opened!"
+          ).equal(whirlpoolUtils.pdaGetter.programId.toBase58());
+          let positionToken = await tokenUtils.getBalance(
+            getAssociatedTokenAddressSync(
+              positionMint,
+              swapTest.dexAccounts.authority,
+              true
+            )
+          );
+          expect(positionToken.toNumber(), "Position token mismatch!").equal(1);
+
+          expect(
+            (await dexUtils.getDexState(swapTest.dexAccounts.dex)).isLaunched,
+            "Dex not launched!"
+          ).equal(true);
+        });
       });
 
       describe("SwapBaseOutput", () => {
@@ -387,6 +1155,38 @@ describe("dex.swap.test", () => {
           }
         });
 
+        it("Should reject transfer hook mint", async () => {
+          await dexUtils.initializeAuthorityManager(
+            signer,
+            launcherUtils.pdaGetter.getAuthorityAddress()[0]
+          );
+          await dexUtils.initializeConfig(signer);
+
+          let reserveVault = await tokenUtils.initialize2022HookMint(
+            signer,
+            210_000_000 * 10 ** 9,
+            transferHookProgram.programId
+          );
+          let mint = reserveVault.mint.address;
+          await transferHookUtils.initializeExtraAccountMetaList(signer, mint);
+          let hookAccounts = transferHookUtils.hookAccounts(mint);
+
+          // no launch target forwards the hook accounts yet
+          let failed = false;
+          try {
+            await swapInputTemplate.setupSwapBaseInput(
+              signer,
+              false,
+              false,
+              reserveVault,
+              hookAccounts
+            );
+          } catch {
+            failed = true;
+          }
+          expect(failed, "Transfer hook mint accepted!").equal(true);
+        });
+
         it("Should swap base input with fee and launch", async () => {
           let swapTest = await swapInputTemplate.setupSwapBaseInput(
             signer,
@@ -406,13 +1206,13 @@ describe("dex.swap.test", () => {
           let transferFee = swapCalculator.calculateTransferFee(
             swapTest.swapInputExpected.args.inputMintConfig,
             swapTest.swapInputExpected.args.epoch,
-            swapTest.swapBaseInputArgs.amountIn
+            swapTest.swapInputExpected.inputAmountFee.amount
               .add(initDexVaultAmount)
               .sub(swapTest.swapInputExpected.swapResult.protocolFee)
           );
 
           let expectedLaunchFee = swapCalculator.curve.Fee(
-            swapTest.swapBaseInputArgs.amountIn
+            swapTest.swapInputExpected.inputAmountFee.amount
               .add(initDexVaultAmount)
               .sub(swapTest.swapInputExpected.swapResult.protocolFee)
               .sub(transferFee.fee),
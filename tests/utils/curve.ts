@@ -256,6 +256,8 @@ export interface SwapBaseResult {
   swapCalculation: SwapCalculation;
   swapResult: SwapResult;
   args: SwapBaseInputArgs;
+  // input left with the user as the swap stops at the vault reserve bound
+  unfilledAmount?: BN;
 }
 
 export interface SwapBaseInputArgs {
@@ -269,6 +271,8 @@ export interface SwapBaseInputArgs {
   amountIn: BN;
   minimumAmountOut: BN;
   epoch: bigint;
+  // reserve tokens left until the vault reserve bound, only for buys
+  remainingTokens?: BN;
 }
 
 export interface SwapBaseOutputArgs {
@@ -422,6 +426,29 @@ export class SwapCalculator {
       args.epoch,
       args.amountIn
     );
+    let minimumAmountOut = args.minimumAmountOut;
+    let unfilledAmount = this.curve.ZERO;
+
+    if (
+      args.remainingTokens !== undefined &&
+      inputFeeCalculation.actualAmount.gt(args.remainingTokens)
+    ) {
+      // only the input reaching the vault reserve bound is swapped
+      let inverseFee = this.calculateTransferInverseFee(
+        args.inputMintConfig,
+        args.epoch,
+        args.remainingTokens
+      );
+      inputFeeCalculation = {
+        amount: inverseFee.actualAmount,
+        actualAmount: args.remainingTokens,
+        fee: inverseFee.fee,
+      };
+      minimumAmountOut = minimumAmountOut
+        .mul(inputFeeCalculation.amount)
+        .div(args.amountIn);
+      unfilledAmount = args.amountIn.sub(inputFeeCalculation.amount);
+    }
 
     let swapCalculation = this.calculateTradeAmountsAndPriceBeforeSwap(
       args.inputProtocolFee,
@@ -451,7 +478,7 @@ export class SwapCalculator {
       throw Error("Received zero");
     }
 
-    if (args.minimumAmountOut.gt(outputFeeCalculation.actualAmount)) {
+    if (minimumAmountOut.gt(outputFeeCalculation.actualAmount)) {
       throw Error("ExceededSlippage");
     }
 
@@ -461,6 +488,7 @@ export class SwapCalculator {
       swapCalculation: swapCalculation,
       swapResult: result,
       args,
+      unfilledAmount,
     };
   }
 
@@ -9,11 +9,21 @@ import {
   SYSVAR_RENT_PUBKEY,
   ComputeBudgetProgram,
   TransactionSignature,
+  AccountMeta,
 } from "@solana/web3.js";
-import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import {
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  NATIVE_MINT,
+} from "@solana/spl-token";
 import { ASSOCIATED_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
 import { Mint, TokenUtils, TokenVault } from "./token.utils";
 import { RaydiumPda } from "./raydium.utils";
+import {
+  WhirlpoolPda,
+  fullRangeTickIndex,
+  whirlpoolsConfigAddress,
+} from "./whirlpool.utils";
 import { createPoolFeeReceive } from "./raydium.idl";
 //import { SYSTEM_PROGRAM_ID } from "@raydium-io/raydium-sdk-v2";
 
@@ -24,6 +34,7 @@ export interface DexAccounts {
   vaultZero: TokenVault;
   vaultOne: TokenVault;
   dex: PublicKey;
+  observation: PublicKey;
 }
 
 export interface UserVaults {
@@ -59,6 +70,66 @@ export interface SwapBaseInputArgs {
   amountIn: BN;
   minimumAmountOut: BN;
   dexAccounts: DexAccounts;
+  referrer?: PublicKey;
+  // extra accounts of the mint transfer hooks
+  hookAccounts?: AccountMeta[];
+  // record the swap in the signer trader stats
+  traderStats?: boolean;
+}
+
+export type SwapHopArgs = Omit<
+  SwapBaseInputArgs,
+  "amountIn" | "minimumAmountOut" | "referrer" | "hookAccounts"
+>;
+
+export type SwapRouteHopArgs = SwapHopArgs &
+  Pick<SwapBaseInputArgs, "referrer" | "hookAccounts">;
+
+export interface SwapRouteArgs {
+  // the output ata of the first hop is the input ata of the second hop
+  first: SwapRouteHopArgs;
+  second: SwapRouteHopArgs;
+  amountIn: BN;
+  minimumAmountOut: BN;
+}
+
+export interface LimitOrderArgs {
+  inputToken: PublicKey;
+  inputTokenProgram: PublicKey;
+  inputAta: PublicKey;
+  orderId: BN;
+  dexAccounts: DexAccounts;
+  // extra accounts of the mint transfer hooks
+  hookAccounts?: AccountMeta[];
+}
+
+export interface PlaceLimitOrderArgs extends LimitOrderArgs {
+  amountIn: BN;
+  minimumAmountOut: BN;
+  // token_0 price in the format of the dex observations
+  triggerPriceX32: BN;
+  triggerAbove: boolean;
+}
+
+export interface OrderFill {
+  order: PublicKey;
+  owner: PublicKey;
+  // owner token account of the output mint
+  outputAta: PublicKey;
+}
+
+export interface RefundArgs {
+  supplyToken: PublicKey;
+  supplyTokenProgram: PublicKey;
+  reserveToken: PublicKey;
+  reserveTokenProgram: PublicKey;
+  supplyAta: PublicKey;
+  reserveAta: PublicKey;
+  supplyVault: PublicKey;
+  reserveVault: PublicKey;
+  amount: BN;
+  dexAccounts: DexAccounts;
+  hookAccounts?: AccountMeta[];
 }
 
 export interface SwapBaseOutputArgs {
@@ -73,13 +144,48 @@ export interface SwapBaseOutputArgs {
   maxAmountIn: BN;
   amountOutLessFee: BN;
   dexAccounts: DexAccounts;
+  referrer?: PublicKey;
+  // extra accounts of the mint transfer hooks
+  hookAccounts?: AccountMeta[];
+  // record the swap in the signer trader stats
+  traderStats?: boolean;
 }
 
-export interface LaunchDexArgs {
+export interface CrankLaunchDexArgs {
   cpSwapProgram: PublicKey;
   raydiumAmmConfig: PublicKey;
   raydiumPdaGetter: RaydiumPda;
   dexAccounts: DexAccounts;
+}
+
+export interface LaunchDexArgs extends CrankLaunchDexArgs {
+  sharedLamports: BN;
+}
+
+export interface ClaimLockedLpArgs {
+  dexAccounts: DexAccounts;
+  lpMint: PublicKey;
+  recipientLpToken: PublicKey;
+}
+
+export interface FeeRecipient {
+  wallet: PublicKey;
+  weightBps: number;
+}
+
+export interface WithdrawDexFeeArgs {
+  dexAccounts: DexAccounts;
+  // token_0 and token_1 accounts of each fee recipient in order
+  recipientTokenAccounts: PublicKey[];
+  hookAccounts?: AccountMeta[];
+}
+
+export interface CrankLaunchDexClmmArgs {
+  whirlpoolPdaGetter: WhirlpoolPda;
+  dexAccounts: DexAccounts;
+}
+
+export interface LaunchDexClmmArgs extends CrankLaunchDexClmmArgs {
   sharedLamports: BN;
 }
 
@@ -114,9 +220,9 @@ export class DexUtils {
       .rpc();
     return authorityManager;
   }
-  async initializeConfig(payer: Signer) {
+  async initializeConfig(payer: Signer, index = 0) {
     let [authorityManager] = this.pdaGetter.getAuthorityManagerAddress();
-    let [config] = this.pdaGetter.getConfigStateAddress();
+    let [config] = this.pdaGetter.getConfigStateAddress(index);
 
     let configState = await this.getConfigState(config);
     if (configState != null) {
@@ -124,7 +230,7 @@ export class DexUtils {
     }
 
     await this.program.methods
-      .initializeConfig()
+      .initializeConfig(index)
       .accounts({
         admin: payer.publicKey,
         authorityManager,
@@ -140,6 +246,154 @@ export class DexUtils {
   ): Promise<TransactionSignature> {
     return await this.program.methods
       .swapBaseInput(args.amountIn, args.minimumAmountOut)
+      .accounts(this.swapAccounts(signer, args))
+      .remainingAccounts(
+        this.swapRemainingAccounts(args.referrer, args.hookAccounts)
+      )
+      .rpc(this.confirmOptions);
+  }
+  async swapBaseInputNative(
+    signer: Signer,
+    args: SwapBaseInputArgs
+  ): Promise<TransactionSignature> {
+    return await this.program.methods
+      .swapBaseInputNative(args.amountIn, args.minimumAmountOut)
+      .accounts(this.swapNativeAccounts(signer, args))
+      .remainingAccounts(
+        this.swapRemainingAccounts(args.referrer, args.hookAccounts)
+      )
+      .rpc(this.confirmOptions);
+  }
+  async swapBaseOutputNative(
+    signer: Signer,
+    args: SwapBaseOutputArgs
+  ): Promise<TransactionSignature> {
+    return await this.program.methods
+      .swapBaseOutputNative(args.maxAmountIn, args.amountOutLessFee)
+      .accounts(this.swapNativeAccounts(signer, args))
+      .remainingAccounts(
+        this.swapRemainingAccounts(args.referrer, args.hookAccounts)
+      )
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 300000 }),
+      ])
+      .rpc(this.confirmOptions);
+  }
+  swapNativeAccounts(signer: Signer, args: SwapHopArgs) {
+    let { inputTokenAccount, outputTokenAccount, ...accounts } =
+      this.swapAccounts(signer, args);
+    return {
+      ...accounts,
+      // the signer ata of the other mint, the native side is paid in lamports
+      tokenAccount: args.inputToken.equals(NATIVE_MINT)
+        ? outputTokenAccount
+        : inputTokenAccount,
+      nativeTokenAccount: this.pdaGetter.getNativeAccountAddress(
+        signer.publicKey
+      )[0],
+    };
+  }
+  async swapRoute(
+    signer: Signer,
+    args: SwapRouteArgs
+  ): Promise<TransactionSignature> {
+    // each hop has its own referrer and hook accounts, split by the first hop count
+    let firstRemainingAccounts = this.swapRemainingAccounts(
+      args.first.referrer,
+      args.first.hookAccounts
+    );
+    return await this.program.methods
+      .swapRoute(
+        args.amountIn,
+        args.minimumAmountOut,
+        firstRemainingAccounts.length
+      )
+      .accounts({
+        first: this.swapAccounts(signer, args.first),
+        second: this.swapAccounts(signer, args.second),
+      })
+      .remainingAccounts([
+        ...firstRemainingAccounts,
+        ...this.swapRemainingAccounts(
+          args.second.referrer,
+          args.second.hookAccounts
+        ),
+      ])
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 400000 }),
+      ])
+      .rpc(this.confirmOptions);
+  }
+  async quoteBaseInput(args: SwapBaseInputArgs) {
+    return await this.program.methods
+      .quoteBaseInput(
+        args.amountIn,
+        args.minimumAmountOut,
+        args.referrer != undefined
+      )
+      .accounts(this.quoteAccounts(args))
+      .view();
+  }
+  async quoteBaseOutput(args: SwapBaseOutputArgs) {
+    return await this.program.methods
+      .quoteBaseOutput(
+        args.maxAmountIn,
+        args.amountOutLessFee,
+        args.referrer != undefined
+      )
+      .accounts(this.quoteAccounts(args))
+      .view();
+  }
+  quoteAccounts(args: SwapHopArgs) {
+    return {
+      config: args.dexAccounts.config,
+      dexState: args.dexAccounts.dex,
+      inputVault: args.inputVault,
+      outputVault: args.outputVault,
+      inputTokenMint: args.inputToken,
+      outputTokenMint: args.outputToken,
+    };
+  }
+  swapAccounts(signer: Signer, args: SwapHopArgs) {
+    return {
+      payer: signer.publicKey,
+      // signer ata accounts
+      inputTokenAccount: args.inputAta,
+      outputTokenAccount: args.outputAta,
+      inputTokenProgram: args.inputTokenProgram,
+      outputTokenProgram: args.outputTokenProgram,
+      // dex accounts
+      authority: args.dexAccounts.authority,
+      dexState: args.dexAccounts.dex,
+      observationState: args.dexAccounts.observation,
+      inputVault: args.inputVault,
+      outputVault: args.outputVault,
+      inputTokenMint: args.inputToken,
+      outputTokenMint: args.outputToken,
+      config: args.dexAccounts.config,
+      authorityManager: args.dexAccounts.authorityManager,
+      walletLimit: this.pdaGetter.getWalletLimitAddress(
+        args.dexAccounts.dex,
+        signer.publicKey
+      )[0],
+      traderStats: this.traderStatsAccount(signer, args),
+      systemProgram: SystemProgram.programId,
+    };
+  }
+  traderStatsAccount(signer: Signer, args: SwapHopArgs) {
+    return args.traderStats
+      ? this.pdaGetter.getTraderStatsAddress(
+          args.dexAccounts.dex,
+          signer.publicKey
+        )[0]
+      : null;
+  }
+  async swapBaseOutput(
+    signer: Signer,
+    args: SwapBaseOutputArgs
+  ): Promise<TransactionSignature> {
+    return await this.program.methods
+      .swapBaseOutput(args.maxAmountIn, args.amountOutLessFee)
       .accounts({
         payer: signer.publicKey,
         // signer ata accounts
@@ -150,47 +404,175 @@ export class DexUtils {
         // dex accounts
         authority: args.dexAccounts.authority,
         dexState: args.dexAccounts.dex,
+        observationState: args.dexAccounts.observation,
         inputVault: args.inputVault,
         outputVault: args.outputVault,
         inputTokenMint: args.inputToken,
         outputTokenMint: args.outputToken,
         config: args.dexAccounts.config,
         authorityManager: args.dexAccounts.authorityManager,
+        walletLimit: this.pdaGetter.getWalletLimitAddress(
+          args.dexAccounts.dex,
+          signer.publicKey
+        )[0],
+        traderStats: this.traderStatsAccount(signer, args),
       })
+      .remainingAccounts(
+        this.swapRemainingAccounts(args.referrer, args.hookAccounts)
+      )
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 300000 }),
+      ])
       .rpc(this.confirmOptions);
   }
-  async swapBaseOutput(
+  async placeLimitOrder(
     signer: Signer,
-    args: SwapBaseOutputArgs
+    args: PlaceLimitOrderArgs
   ): Promise<TransactionSignature> {
+    let [order] = this.pdaGetter.getLimitOrderAddress(
+      args.dexAccounts.dex,
+      signer.publicKey,
+      args.orderId
+    );
     return await this.program.methods
-      .swapBaseOutput(args.maxAmountIn, args.amountOutLessFee)
+      .placeLimitOrder(
+        args.orderId,
+        args.amountIn,
+        args.minimumAmountOut,
+        args.triggerPriceX32,
+        args.triggerAbove
+      )
       .accounts({
-        payer: signer.publicKey,
-        // signer ata accounts
+        owner: signer.publicKey,
+        dexState: args.dexAccounts.dex,
+        order,
+        escrow: this.pdaGetter.getLimitOrderEscrowAddress(order)[0],
         inputTokenAccount: args.inputAta,
-        outputTokenAccount: args.outputAta,
+        inputTokenMint: args.inputToken,
         inputTokenProgram: args.inputTokenProgram,
-        outputTokenProgram: args.outputTokenProgram,
-        // dex accounts
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(args.hookAccounts ?? [])
+      .rpc(this.confirmOptions);
+  }
+  async cancelLimitOrder(
+    signer: Signer,
+    args: LimitOrderArgs
+  ): Promise<TransactionSignature> {
+    let [order] = this.pdaGetter.getLimitOrderAddress(
+      args.dexAccounts.dex,
+      signer.publicKey,
+      args.orderId
+    );
+    return await this.program.methods
+      .cancelLimitOrder()
+      .accounts({
+        owner: signer.publicKey,
+        order,
+        escrow: this.pdaGetter.getLimitOrderEscrowAddress(order)[0],
+        inputTokenAccount: args.inputAta,
+        inputTokenMint: args.inputToken,
+        inputTokenProgram: args.inputTokenProgram,
+      })
+      .remainingAccounts(args.hookAccounts ?? [])
+      .rpc(this.confirmOptions);
+  }
+  async fillOrders(
+    keeper: Signer,
+    args: SwapHopArgs,
+    orders: OrderFill[],
+    hookAccounts: AccountMeta[] = []
+  ): Promise<TransactionSignature> {
+    let orderAccounts = orders.flatMap((fill) =>
+      [
+        fill.order,
+        this.pdaGetter.getLimitOrderEscrowAddress(fill.order)[0],
+        fill.owner,
+        fill.outputAta,
+      ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+    );
+    return await this.program.methods
+      .fillOrders(orders.length)
+      .accounts({
+        keeper: keeper.publicKey,
+        config: args.dexAccounts.config,
+        authorityManager: args.dexAccounts.authorityManager,
         authority: args.dexAccounts.authority,
         dexState: args.dexAccounts.dex,
+        observationState: args.dexAccounts.observation,
         inputVault: args.inputVault,
         outputVault: args.outputVault,
+        inputTokenProgram: args.inputTokenProgram,
+        outputTokenProgram: args.outputTokenProgram,
         inputTokenMint: args.inputToken,
         outputTokenMint: args.outputToken,
-        config: args.dexAccounts.config,
-        authorityManager: args.dexAccounts.authorityManager,
+        systemProgram: SystemProgram.programId,
       })
+      .remainingAccounts(orderAccounts.concat(hookAccounts))
       .preInstructions([
-        ComputeBudgetProgram.setComputeUnitLimit({ units: 300000 }),
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 400000 }),
       ])
       .rpc(this.confirmOptions);
   }
-  async launchDex(
+  async refund(signer: Signer, args: RefundArgs): Promise<TransactionSignature> {
+    return await this.program.methods
+      .refund(args.amount)
+      .accounts({
+        payer: signer.publicKey,
+        authorityManager: args.dexAccounts.authorityManager,
+        authority: args.dexAccounts.authority,
+        dexState: args.dexAccounts.dex,
+        supplyTokenAccount: args.supplyAta,
+        reserveTokenAccount: args.reserveAta,
+        supplyVault: args.supplyVault,
+        reserveVault: args.reserveVault,
+        supplyTokenProgram: args.supplyTokenProgram,
+        reserveTokenProgram: args.reserveTokenProgram,
+        supplyTokenMint: args.supplyToken,
+        reserveTokenMint: args.reserveToken,
+      })
+      .remainingAccounts(args.hookAccounts ?? [])
+      .rpc(this.confirmOptions);
+  }
+  async withdrawSeedReserve(
     signer: Signer,
-    args: LaunchDexArgs
+    args: RefundArgs
   ): Promise<TransactionSignature> {
+    return await this.program.methods
+      .withdrawSeedReserve()
+      .accounts({
+        poolCreator: signer.publicKey,
+        authorityManager: args.dexAccounts.authorityManager,
+        authority: args.dexAccounts.authority,
+        dexState: args.dexAccounts.dex,
+        reserveTokenAccount: args.reserveAta,
+        reserveVault: args.reserveVault,
+        reserveTokenProgram: args.reserveTokenProgram,
+        reserveTokenMint: args.reserveToken,
+      })
+      .remainingAccounts(args.hookAccounts ?? [])
+      .rpc(this.confirmOptions);
+  }
+  swapRemainingAccounts(
+    referrer?: PublicKey,
+    hookAccounts?: AccountMeta[]
+  ): AccountMeta[] {
+    if (hookAccounts == undefined) {
+      return referrer == undefined
+        ? []
+        : [{ pubkey: referrer, isSigner: false, isWritable: true }];
+    }
+    // the dex program id stands for no referrer before the hook accounts
+    return [
+      {
+        pubkey: referrer ?? this.program.programId,
+        isSigner: false,
+        isWritable: referrer != undefined,
+      },
+      ...hookAccounts,
+    ];
+  }
+  launchDexAccounts(signer: Signer, args: CrankLaunchDexArgs) {
     let [auth] = args.raydiumPdaGetter.getAuthAddress();
     let [state] = args.raydiumPdaGetter.getStateAddress(
       args.raydiumAmmConfig,
@@ -216,37 +598,192 @@ export class DexUtils {
       ASSOCIATED_PROGRAM_ID
     );
 
+    return {
+      dexAuthority: args.dexAccounts.authority,
+      dexConfig: args.dexAccounts.config,
+      dexState: args.dexAccounts.dex,
+      cpSwapProgram: args.cpSwapProgram,
+      payer: signer.publicKey,
+      dexAuthorityManager: args.dexAccounts.authorityManager,
+      ammConfig: args.raydiumAmmConfig,
+      authority: auth,
+      poolState: state,
+      token0Mint: args.dexAccounts.vaultZero.mint.address,
+      token1Mint: args.dexAccounts.vaultOne.mint.address,
+      creatorToken0: args.dexAccounts.vaultZero.address,
+      creatorToken1: args.dexAccounts.vaultOne.address,
+      token0Vault: vault0,
+      token1Vault: vault1,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      token0Program: args.dexAccounts.vaultZero.mint.program,
+      token1Program: args.dexAccounts.vaultOne.mint.program,
+      associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      observationState: oracle,
+      lpMint,
+      createPoolFee: createPoolFeeReceive,
+      creatorLpToken,
+      rent: SYSVAR_RENT_PUBKEY,
+    };
+  }
+  async launchDex(
+    signer: Signer,
+    args: LaunchDexArgs
+  ): Promise<TransactionSignature> {
     return await this.program.methods
       .launchDex(args.sharedLamports)
+      .accounts(this.launchDexAccounts(signer, args))
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 350000 }),
+      ])
+      .rpc(this.confirmOptions);
+  }
+  async crankLaunchDex(
+    signer: Signer,
+    args: CrankLaunchDexArgs
+  ): Promise<TransactionSignature> {
+    return await this.program.methods
+      .crankLaunchDex()
+      .accounts(this.launchDexAccounts(signer, args))
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 350000 }),
+      ])
+      .rpc(this.confirmOptions);
+  }
+  async claimLockedLp(
+    signer: Signer,
+    args: ClaimLockedLpArgs
+  ): Promise<TransactionSignature> {
+    let [lpVault] = PublicKey.findProgramAddressSync(
+      [
+        args.dexAccounts.authority.toBuffer(),
+        TOKEN_PROGRAM_ID.toBuffer(),
+        args.lpMint.toBuffer(),
+      ],
+      ASSOCIATED_PROGRAM_ID
+    );
+
+    return await this.program.methods
+      .claimLockedLp()
       .accounts({
-        dexAuthority: args.dexAccounts.authority,
-        dexConfig: args.dexAccounts.config,
+        poolCreator: signer.publicKey,
+        authorityManager: args.dexAccounts.authorityManager,
+        authority: args.dexAccounts.authority,
         dexState: args.dexAccounts.dex,
-        cpSwapProgram: args.cpSwapProgram,
-        payer: signer.publicKey,
-        dexAuthorityManager: args.dexAccounts.authorityManager,
-        ammConfig: args.raydiumAmmConfig,
-        authority: auth,
-        poolState: state,
-        token0Mint: args.dexAccounts.vaultZero.mint.address,
-        token1Mint: args.dexAccounts.vaultOne.mint.address,
-        creatorToken0: args.dexAccounts.vaultZero.address,
-        creatorToken1: args.dexAccounts.vaultOne.address,
-        token0Vault: vault0,
-        token1Vault: vault1,
+        lpMint: args.lpMint,
+        lpVault,
+        recipientLpToken: args.recipientLpToken,
         tokenProgram: TOKEN_PROGRAM_ID,
-        token0Program: args.dexAccounts.vaultZero.mint.program,
-        token1Program: args.dexAccounts.vaultOne.mint.program,
-        associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
-        observationState: oracle,
-        lpMint,
-        createPoolFee: createPoolFeeReceive,
-        creatorLpToken,
-        rent: SYSVAR_RENT_PUBKEY,
       })
+      .rpc(this.confirmOptions);
+  }
+  async withdrawDexFee(
+    signer: Signer,
+    args: WithdrawDexFeeArgs
+  ): Promise<TransactionSignature> {
+    let [feeDistribution] = this.pdaGetter.getFeeDistributionAddress();
+
+    return await this.program.methods
+      .withdrawDexFee()
+      .accounts({
+        admin: signer.publicKey,
+        authorityManager: args.dexAccounts.authorityManager,
+        authority: args.dexAccounts.authority,
+        dexState: args.dexAccounts.dex,
+        token0Vault: args.dexAccounts.vaultZero.address,
+        token1Vault: args.dexAccounts.vaultOne.address,
+        vault0Mint: args.dexAccounts.vaultZero.mint.address,
+        vault1Mint: args.dexAccounts.vaultOne.mint.address,
+        feeDistribution,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenProgram2022: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        ...args.recipientTokenAccounts.map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: true,
+        })),
+        ...(args.hookAccounts ?? []),
+      ])
+      .rpc(this.confirmOptions);
+  }
+  launchDexClmmAccounts(signer: Signer, args: CrankLaunchDexClmmArgs) {
+    let pdaGetter = args.whirlpoolPdaGetter;
+    let mint0 = args.dexAccounts.vaultZero.mint.address;
+    let mint1 = args.dexAccounts.vaultOne.mint.address;
+    let [whirlpool] = pdaGetter.getWhirlpoolAddress(mint0, mint1);
+    let [positionMint] = this.pdaGetter.getClmmPositionMintAddress(
+      args.dexAccounts.dex
+    );
+
+    return {
+      payer: signer.publicKey,
+      dexAuthority: args.dexAccounts.authority,
+      dexAuthorityManager: args.dexAccounts.authorityManager,
+      dexConfig: args.dexAccounts.config,
+      dexState: args.dexAccounts.dex,
+      whirlpoolProgram: pdaGetter.programId,
+      whirlpoolsConfig: whirlpoolsConfigAddress,
+      feeTier: pdaGetter.getFeeTierAddress()[0],
+      poolState: whirlpool,
+      token0Mint: mint0,
+      token1Mint: mint1,
+      creatorToken0: args.dexAccounts.vaultZero.address,
+      creatorToken1: args.dexAccounts.vaultOne.address,
+      token0Vault: this.pdaGetter.getClmmVaultAddress(
+        args.dexAccounts.dex,
+        mint0
+      )[0],
+      token1Vault: this.pdaGetter.getClmmVaultAddress(
+        args.dexAccounts.dex,
+        mint1
+      )[0],
+      tickArrayLower: pdaGetter.getTickArrayAddress(
+        whirlpool,
+        -fullRangeTickIndex
+      )[0],
+      tickArrayUpper: pdaGetter.getTickArrayAddress(
+        whirlpool,
+        fullRangeTickIndex
+      )[0],
+      positionMint,
+      position: pdaGetter.getPositionAddress(positionMint)[0],
+      positionTokenAccount: PublicKey.findProgramAddressSync(
+        [
+          args.dexAccounts.authority.toBuffer(),
+          TOKEN_PROGRAM_ID.toBuffer(),
+          positionMint.toBuffer(),
+        ],
+        ASSOCIATED_PROGRAM_ID
+      )[0],
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      rent: SYSVAR_RENT_PUBKEY,
+    };
+  }
+  async launchDexClmm(
+    signer: Signer,
+    args: LaunchDexClmmArgs
+  ): Promise<TransactionSignature> {
+    return await this.program.methods
+      .launchDexClmm(args.sharedLamports)
+      .accounts(this.launchDexClmmAccounts(signer, args))
       .preInstructions([
-        ComputeBudgetProgram.setComputeUnitLimit({ units: 350000 }),
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 400000 }),
+      ])
+      .rpc(this.confirmOptions);
+  }
+  async crankLaunchDexClmm(
+    signer: Signer,
+    args: CrankLaunchDexClmmArgs
+  ): Promise<TransactionSignature> {
+    return await this.program.methods
+      .crankLaunchDexClmm()
+      .accounts(this.launchDexClmmAccounts(signer, args))
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 400000 }),
       ])
       .rpc(this.confirmOptions);
   }
@@ -321,6 +858,186 @@ export class DexUtils {
       })
       .rpc(this.confirmOptions);
   }
+  async updateCurveType(signer: Signer, curveType: number) {
+    let [config] = this.pdaGetter.getConfigStateAddress();
+    let [authorityManager] = this.pdaGetter.getAuthorityManagerAddress();
+    return await this.program.methods
+      .updateConfigCurveType(curveType)
+      .accounts({
+        admin: signer.publicKey,
+        authorityManager,
+        config,
+      })
+      .rpc(this.confirmOptions);
+  }
+  async updateVirtualReserve(signer: Signer, virtualReserve: BN) {
+    let [config] = this.pdaGetter.getConfigStateAddress();
+    let [authorityManager] = this.pdaGetter.getAuthorityManagerAddress();
+    return await this.program.methods
+      .updateConfigVirtualReserve(virtualReserve)
+      .accounts({
+        admin: signer.publicKey,
+        authorityManager,
+        config,
+      })
+      .rpc(this.confirmOptions);
+  }
+  async updateVirtualSupply(signer: Signer, virtualSupply: BN) {
+    let [config] = this.pdaGetter.getConfigStateAddress();
+    let [authorityManager] = this.pdaGetter.getAuthorityManagerAddress();
+    return await this.program.methods
+      .updateConfigVirtualSupply(virtualSupply)
+      .accounts({
+        admin: signer.publicKey,
+        authorityManager,
+        config,
+      })
+      .rpc(this.confirmOptions);
+  }
+  async updateCreatorFeeRate(signer: Signer, creatorFeeRate: BN) {
+    let [config] = this.pdaGetter.getConfigStateAddress();
+    let [authorityManager] = this.pdaGetter.getAuthorityManagerAddress();
+    return await this.program.methods
+      .updateConfigCreatorFeeRate(creatorFeeRate)
+      .accounts({
+        admin: signer.publicKey,
+        authorityManager,
+        config,
+      })
+      .rpc(this.confirmOptions);
+  }
+  async updateReferralFeeRate(signer: Signer, referralFeeRate: BN) {
+    let [config] = this.pdaGetter.getConfigStateAddress();
+    let [authorityManager] = this.pdaGetter.getAuthorityManagerAddress();
+    return await this.program.methods
+      .updateConfigReferralFeeRate(referralFeeRate)
+      .accounts({
+        admin: signer.publicKey,
+        authorityManager,
+        config,
+      })
+      .rpc(this.confirmOptions);
+  }
+  async updateSniperProtection(
+    signer: Signer,
+    sniperWindow: BN,
+    sniperMaxSwapInput: BN,
+    sniperMaxWalletInput: BN
+  ) {
+    let [config] = this.pdaGetter.getConfigStateAddress();
+    let [authorityManager] = this.pdaGetter.getAuthorityManagerAddress();
+    return await this.program.methods
+      .updateConfigSniperProtection(
+        sniperWindow,
+        sniperMaxSwapInput,
+        sniperMaxWalletInput
+      )
+      .accounts({
+        admin: signer.publicKey,
+        authorityManager,
+        config,
+      })
+      .rpc(this.confirmOptions);
+  }
+  async updateLaunchWindow(signer: Signer, launchWindow: BN) {
+    let [config] = this.pdaGetter.getConfigStateAddress();
+    let [authorityManager] = this.pdaGetter.getAuthorityManagerAddress();
+    return await this.program.methods
+      .updateConfigLaunchWindow(launchWindow)
+      .accounts({
+        admin: signer.publicKey,
+        authorityManager,
+        config,
+      })
+      .rpc(this.confirmOptions);
+  }
+  async updateLaunchLamports(
+    signer: Signer,
+    launchLamports: BN,
+    keeperReward: BN
+  ) {
+    let [config] = this.pdaGetter.getConfigStateAddress();
+    let [authorityManager] = this.pdaGetter.getAuthorityManagerAddress();
+    return await this.program.methods
+      .updateConfigLaunchLamports(launchLamports, keeperReward)
+      .accounts({
+        admin: signer.publicKey,
+        authorityManager,
+        config,
+      })
+      .rpc(this.confirmOptions);
+  }
+  async updateLaunchTarget(signer: Signer, launchTarget: number) {
+    let [config] = this.pdaGetter.getConfigStateAddress();
+    let [authorityManager] = this.pdaGetter.getAuthorityManagerAddress();
+    return await this.program.methods
+      .updateConfigLaunchTarget(launchTarget)
+      .accounts({
+        admin: signer.publicKey,
+        authorityManager,
+        config,
+      })
+      .rpc(this.confirmOptions);
+  }
+  async updateLpPolicy(
+    signer: Signer,
+    lpBurnRate: BN,
+    lpLockCliff: BN,
+    lpVestingPeriod: BN
+  ) {
+    let [config] = this.pdaGetter.getConfigStateAddress();
+    let [authorityManager] = this.pdaGetter.getAuthorityManagerAddress();
+    return await this.program.methods
+      .updateConfigLpPolicy(lpBurnRate, lpLockCliff, lpVestingPeriod)
+      .accounts({
+        admin: signer.publicKey,
+        authorityManager,
+        config,
+      })
+      .rpc(this.confirmOptions);
+  }
+  async updateSniperFee(
+    signer: Signer,
+    sniperFeeRate: BN,
+    sniperFeePeriod: BN,
+    sniperFeeSteps: BN
+  ) {
+    let [config] = this.pdaGetter.getConfigStateAddress();
+    let [authorityManager] = this.pdaGetter.getAuthorityManagerAddress();
+    return await this.program.methods
+      .updateConfigSniperFee(sniperFeeRate, sniperFeePeriod, sniperFeeSteps)
+      .accounts({
+        admin: signer.publicKey,
+        authorityManager,
+        config,
+      })
+      .rpc(this.confirmOptions);
+  }
+  async updateFeeRecipients(signer: Signer, recipients: FeeRecipient[]) {
+    let [authorityManager] = this.pdaGetter.getAuthorityManagerAddress();
+    let [feeDistribution] = this.pdaGetter.getFeeDistributionAddress();
+    return await this.program.methods
+      .updateFeeRecipients(recipients)
+      .accounts({
+        admin: signer.publicKey,
+        authorityManager,
+        feeDistribution,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc(this.confirmOptions);
+  }
+  async updateConfigDisabled(signer: Signer, disabled: boolean, index = 0) {
+    let [config] = this.pdaGetter.getConfigStateAddress(index);
+    let [authorityManager] = this.pdaGetter.getAuthorityManagerAddress();
+    return await this.program.methods
+      .updateConfigDisabled(disabled)
+      .accounts({
+        admin: signer.publicKey,
+        authorityManager,
+        config,
+      })
+      .rpc(this.confirmOptions);
+  }
   async dexIsReadyToLaunch(dexState: PublicKey) {
     return (await this.program.account.dexState.fetchNullable(dexState))
       .isReadyToLaunch;
@@ -329,6 +1046,12 @@ export class DexUtils {
     return (await this.program.account.dexState.fetchNullable(dexState))
       .isLaunched;
   }
+  async getFeeDistributionState() {
+    let [feeDistribution] = this.pdaGetter.getFeeDistributionAddress();
+    return await this.program.account.feeDistributionState.fetchNullable(
+      feeDistribution
+    );
+  }
   async getAuthorityManagerState(authorityManager: PublicKey) {
     return await this.program.account.authorityManager.fetchNullable(
       authorityManager
@@ -340,6 +1063,24 @@ export class DexUtils {
   async getConfigState(config: PublicKey) {
     return await this.program.account.configState.fetchNullable(config);
   }
+  async getWalletLimitState(walletLimit: PublicKey) {
+    return await this.program.account.walletLimitState.fetchNullable(
+      walletLimit
+    );
+  }
+  async getTraderStatsState(traderStats: PublicKey) {
+    return await this.program.account.traderStatsState.fetchNullable(
+      traderStats
+    );
+  }
+  async getLimitOrderState(order: PublicKey) {
+    return await this.program.account.limitOrderState.fetchNullable(order);
+  }
+  async getObservationState(observation: PublicKey) {
+    return await this.program.account.observationState.fetchNullable(
+      observation
+    );
+  }
 }
 
 export class DexPda {
@@ -362,9 +1103,14 @@ export class DexPda {
       this.programId
     );
   }
-  getConfigStateAddress() {
+  getConfigStateAddress(index = 0) {
+    // index 0 keeps the address of the single config before indexed configs
+    const indexBuffer = Buffer.alloc(index == 0 ? 0 : 2);
+    if (index != 0) {
+      indexBuffer.writeUInt16BE(index);
+    }
     return PublicKey.findProgramAddressSync(
-      [this.seeds.dexConfig],
+      [this.seeds.dexConfig, indexBuffer],
       this.programId
     );
   }
@@ -380,6 +1126,65 @@ export class DexPda {
       this.programId
     );
   }
+  getWalletLimitAddress(state: PublicKey, wallet: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [this.seeds.dexWalletLimit, state.toBuffer(), wallet.toBuffer()],
+      this.programId
+    );
+  }
+  getTraderStatsAddress(state: PublicKey, trader: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [this.seeds.dexTraderStats, state.toBuffer(), trader.toBuffer()],
+      this.programId
+    );
+  }
+  getLimitOrderAddress(state: PublicKey, owner: PublicKey, orderId: BN) {
+    return PublicKey.findProgramAddressSync(
+      [
+        this.seeds.dexLimitOrder,
+        state.toBuffer(),
+        owner.toBuffer(),
+        orderId.toArrayLike(Buffer, "be", 8),
+      ],
+      this.programId
+    );
+  }
+  getLimitOrderEscrowAddress(order: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [this.seeds.dexLimitOrderEscrow, order.toBuffer()],
+      this.programId
+    );
+  }
+  getNativeAccountAddress(payer: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [this.seeds.dexNativeAccount, payer.toBuffer()],
+      this.programId
+    );
+  }
+  getFeeDistributionAddress() {
+    return PublicKey.findProgramAddressSync(
+      [this.seeds.dexFeeDistribution],
+      this.programId
+    );
+  }
+  getObservationAddress(state: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [this.seeds.dexObservation, state.toBuffer()],
+      this.programId
+    );
+  }
+  getClmmVaultAddress(dex: PublicKey, mint: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [this.seeds.dexClmmVault, dex.toBuffer(), mint.toBuffer()],
+      this.programId
+    );
+  }
+  getClmmPositionMintAddress(dex: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [this.seeds.dexClmmPositionMint, dex.toBuffer()],
+      this.programId
+    );
+  }
 }
 
 export class DexSeeds {
@@ -388,6 +1193,15 @@ export class DexSeeds {
   dexConfig: Buffer;
   dexState: Buffer;
   dexVault: Buffer;
+  dexObservation: Buffer;
+  dexWalletLimit: Buffer;
+  dexTraderStats: Buffer;
+  dexFeeDistribution: Buffer;
+  dexNativeAccount: Buffer;
+  dexLimitOrder: Buffer;
+  dexLimitOrderEscrow: Buffer;
+  dexClmmVault: Buffer;
+  dexClmmPositionMint: Buffer;
 
   constructor() {
     this.dexAuthorityManager = this.toSeed("dex_authority_manager");
@@ -395,6 +1209,15 @@ export class DexSeeds {
     this.dexConfig = this.toSeed("dex_config");
     this.dexState = this.toSeed("dex_state");
     this.dexVault = this.toSeed("dex_vault");
+    this.dexObservation = this.toSeed("dex_observation");
+    this.dexWalletLimit = this.toSeed("dex_wallet_limit");
+    this.dexTraderStats = this.toSeed("dex_trader_stats");
+    this.dexFeeDistribution = this.toSeed("dex_fee_distribution");
+    this.dexNativeAccount = this.toSeed("dex_native_account");
+    this.dexLimitOrder = this.toSeed("dex_limit_order");
+    this.dexLimitOrderEscrow = this.toSeed("dex_limit_order_escrow");
+    this.dexClmmVault = this.toSeed("dex_clmm_vault");
+    this.dexClmmPositionMint = this.toSeed("dex_clmm_position_mint");
   }
 
   toSeed(seed: string) {
@@ -1,6 +1,8 @@
 export * from "./utils";
 export * from "./dex.utils";
 export * from "./raydium.utils";
+export * from "./whirlpool.utils";
+export * from "./transfer_hook.utils";
 export * from "./faucet.utils";
 export * from "./launcher.utils";
 export * from "./raydium.idl";
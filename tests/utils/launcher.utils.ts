@@ -8,6 +8,7 @@ import {
   ComputeBudgetProgram,
   TransactionSignature,
   Keypair,
+  AccountMeta,
 } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
@@ -28,6 +29,8 @@ export interface InitializeDexArgs {
   payerVault: TokenVault;
   mintAuthority: Mint;
   hasFaucet: boolean;
+  // extra accounts of the payer mint transfer hook
+  hookAccounts?: AccountMeta[];
 }
 
 export class LauncherUtils {
@@ -181,6 +184,8 @@ export class LauncherUtils {
     let [mint_0, mint_1] = to_sort;
 
     let [dexState] = args.dexUtils.pdaGetter.getDexStateAddress(mint_0, mint_1);
+    let [dexObservation] =
+      args.dexUtils.pdaGetter.getObservationAddress(dexState);
 
     let [dexVault] = args.dexUtils.pdaGetter.getDexVaultAddress(
       dexState,
@@ -212,6 +217,7 @@ export class LauncherUtils {
           dexConfig,
           dexProgram: args.dexUtils.program.programId,
           dexState,
+          dexObservation,
           dexVault,
           dexVaultAuthority,
           mint: args.payerVault.mint.address,
@@ -223,6 +229,7 @@ export class LauncherUtils {
           faucetVault,
           payerVault: args.payerVault.address,
         })
+        .remainingAccounts(args.hookAccounts ?? [])
         .preInstructions([
           ComputeBudgetProgram.setComputeUnitLimit({ units: 500000 }),
         ])
@@ -241,6 +248,7 @@ export class LauncherUtils {
           dexConfig,
           dexProgram: args.dexUtils.program.programId,
           dexState,
+          dexObservation,
           dexVault,
           dexVaultAuthority,
           mint: args.payerVault.mint.address,
@@ -250,6 +258,7 @@ export class LauncherUtils {
           teamVault,
           payerVault: args.payerVault.address,
         })
+        .remainingAccounts(args.hookAccounts ?? [])
         .preInstructions([
           ComputeBudgetProgram.setComputeUnitLimit({ units: 500000 }),
         ])
@@ -283,6 +292,7 @@ export class LauncherUtils {
       vaultZero: vault_zero,
       vaultOne: vault_one,
       dex: dexState,
+      observation: dexObservation,
     };
   }
   async createMint(
@@ -1,4 +1,9 @@
-import { LAMPORTS_PER_SOL, PublicKey, Signer } from "@solana/web3.js";
+import {
+  AccountMeta,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  Signer,
+} from "@solana/web3.js";
 import { BN } from "@coral-xyz/anchor";
 import { Mint, MintPair, TokenUtils, TokenVault } from "./token.utils";
 import {
@@ -7,6 +12,8 @@ import {
   DexUtils,
   SwapBaseInputArgs,
   SwapBaseOutputArgs,
+  SwapHopArgs,
+  SwapRouteArgs,
 } from "./dex.utils";
 import { SwapBaseResult, SwapCalculator } from "./curve";
 import {
@@ -40,6 +47,12 @@ export interface SetupOutputSwap {
   zeroToOne: boolean;
 }
 
+export interface SetupRouteSwap {
+  // buys the supply token of the first dex to sell it through the route
+  swapBaseInputArgs: SwapBaseInputArgs;
+  swapRouteArgs: SwapRouteArgs;
+}
+
 export class SetupSwapTest {
   tokenUtils: TokenUtils;
   faucetUtils: FaucetUtils;
@@ -60,11 +73,18 @@ export class SetupSwapTest {
 
   async setupDex(
     signer: Signer,
-    splToken = true
+    splToken = true,
+    reserveVault?: TokenVault,
+    hookAccounts?: AccountMeta[]
   ): Promise<[DexAccounts, Atas]> {
-    let tokenVault = splToken
-      ? await this.tokenUtils.initializeSplMint(signer, 210_000_000 * 10 ** 9)
-      : await this.tokenUtils.initialize2022Mint(signer, 210_000_000 * 10 ** 9);
+    let tokenVault =
+      reserveVault ??
+      (splToken
+        ? await this.tokenUtils.initializeSplMint(signer, 210_000_000 * 10 ** 9)
+        : await this.tokenUtils.initialize2022Mint(
+            signer,
+            210_000_000 * 10 ** 9
+          ));
 
     const [faucetAuthority] = this.faucetUtils.pdaGetter.getAuthorityAddress();
     const [cpiAuthority] = this.launcherUtils.pdaGetter.getAuthorityAddress();
@@ -102,6 +122,7 @@ export class SetupSwapTest {
       payerVault: tokenVault,
       mintAuthority: dex_mint,
       hasFaucet: true,
+      hookAccounts,
     };
     let dexAccounts = await this.launcherUtils.initializeDex(
       signer,
@@ -207,12 +228,18 @@ export class SetupSwapTest {
     ]);
 
     let inputProtocolFee = zeroToOne
-      ? dexState.swapFeesToken0
-      : dexState.swapFeesToken1;
+      ? dexState.swapFeesToken0.add(dexState.creatorFeesToken0)
+      : dexState.swapFeesToken1.add(dexState.creatorFeesToken1);
 
     let outputProtocolFee = zeroToOne
-      ? dexState.swapFeesToken1
-      : dexState.swapFeesToken0;
+      ? dexState.swapFeesToken1.add(dexState.creatorFeesToken1)
+      : dexState.swapFeesToken0.add(dexState.creatorFeesToken0);
+
+    // buys pay the reserve token and stop at the vault reserve bound
+    let isBuy = zeroToOne != dexState.vaultForReserveBound;
+    let remainingTokens = isBuy
+      ? BN.max(dexState.vaultReserveBound.sub(inputVaultBalance), new BN(0))
+      : undefined;
 
     let result = calculator.swapBaseInput({
       swapFeeRate: configState.swapFeeRate,
@@ -225,6 +252,7 @@ export class SetupSwapTest {
       amountIn,
       minimumAmountOut,
       epoch: BigInt(epoch),
+      remainingTokens,
     });
 
     return result;
@@ -239,8 +267,12 @@ export class SetupSwapTest {
       dexAccounts.vaultOne.address
     );
 
-    vaultZeroBalance = vaultZeroBalance.sub(dexState.swapFeesToken0);
-    vaultOneBalance = vaultOneBalance.sub(dexState.swapFeesToken1);
+    vaultZeroBalance = vaultZeroBalance
+      .sub(dexState.swapFeesToken0)
+      .sub(dexState.creatorFeesToken0);
+    vaultOneBalance = vaultOneBalance
+      .sub(dexState.swapFeesToken1)
+      .sub(dexState.creatorFeesToken1);
 
     if (vaultZeroBalance.gte(vaultOneBalance)) {
       return vaultZeroBalance.div(vaultOneBalance);
@@ -317,9 +349,16 @@ export class SetupSwapTest {
   async setupSwapBaseInput(
     signer: Signer,
     launch: boolean = false,
-    splToken: boolean = true
+    splToken: boolean = true,
+    reserveVault?: TokenVault,
+    hookAccounts?: AccountMeta[]
   ): Promise<SetupInputSwap> {
-    let [dexAccounts, atas] = await this.setupDex(signer, splToken);
+    let [dexAccounts, atas] = await this.setupDex(
+      signer,
+      splToken,
+      reserveVault,
+      hookAccounts
+    );
 
     let vaultForReserveBound = (
       await this.dexUtils.getDexState(dexAccounts.dex)
@@ -346,6 +385,7 @@ export class SetupSwapTest {
           amountIn,
           minimumAmountOut,
           dexAccounts: dexAccounts,
+          hookAccounts,
         }
       : {
           inputToken: dexAccounts.vaultOne.mint.address,
@@ -359,6 +399,7 @@ export class SetupSwapTest {
           amountIn,
           minimumAmountOut,
           dexAccounts: dexAccounts,
+          hookAccounts,
         };
 
     let swapInputExpected = await this.swapBaseInputCalculator(
@@ -413,12 +454,12 @@ export class SetupSwapTest {
     ]);
 
     let inputProtocolFee = zeroToOne
-      ? dexState.swapFeesToken0
-      : dexState.swapFeesToken1;
+      ? dexState.swapFeesToken0.add(dexState.creatorFeesToken0)
+      : dexState.swapFeesToken1.add(dexState.creatorFeesToken1);
 
     let outputProtocolFee = zeroToOne
-      ? dexState.swapFeesToken1
-      : dexState.swapFeesToken0;
+      ? dexState.swapFeesToken1.add(dexState.creatorFeesToken1)
+      : dexState.swapFeesToken0.add(dexState.creatorFeesToken0);
 
     let result = calculator.swapBaseOutput({
       swapFeeRate: dexConfig.swapFeeRate,
@@ -502,4 +543,64 @@ export class SetupSwapTest {
       zeroToOne,
     };
   }
+
+  swapHop(dexAccounts: DexAccounts, atas: Atas, zeroToOne: boolean) {
+    let swapHopArgs: SwapHopArgs = zeroToOne
+      ? {
+          inputToken: dexAccounts.vaultZero.mint.address,
+          inputTokenProgram: dexAccounts.vaultZero.mint.program,
+          outputToken: dexAccounts.vaultOne.mint.address,
+          outputTokenProgram: dexAccounts.vaultOne.mint.program,
+          inputAta: atas.vaultZero.address,
+          outputAta: atas.vaultOne.address,
+          inputVault: dexAccounts.vaultZero.address,
+          outputVault: dexAccounts.vaultOne.address,
+          dexAccounts,
+        }
+      : {
+          inputToken: dexAccounts.vaultOne.mint.address,
+          inputTokenProgram: dexAccounts.vaultOne.mint.program,
+          outputToken: dexAccounts.vaultZero.mint.address,
+          outputTokenProgram: dexAccounts.vaultZero.mint.program,
+          inputAta: atas.vaultOne.address,
+          outputAta: atas.vaultZero.address,
+          inputVault: dexAccounts.vaultOne.address,
+          outputVault: dexAccounts.vaultZero.address,
+          dexAccounts,
+        };
+    return swapHopArgs;
+  }
+
+  async setupSwapRoute(signer: Signer): Promise<SetupRouteSwap> {
+    let first = await this.setupSwapBaseInput(signer);
+    let reserveVault = first.zeroToOne
+      ? first.atas.vaultZero
+      : first.atas.vaultOne;
+
+    let [secondDexAccounts, secondAtas] = await this.setupDex(
+      signer,
+      true,
+      reserveVault
+    );
+    let secondVaultForReserveBound = (
+      await this.dexUtils.getDexState(secondDexAccounts.dex)
+    ).vaultForReserveBound;
+
+    // sell the first supply token for the shared reserve token, then buy the second one
+    let swapRouteArgs = {
+      first: this.swapHop(first.dexAccounts, first.atas, !first.zeroToOne),
+      second: this.swapHop(
+        secondDexAccounts,
+        secondAtas,
+        !secondVaultForReserveBound
+      ),
+      amountIn: new BN(100),
+      minimumAmountOut: new BN(1),
+    };
+
+    return {
+      swapBaseInputArgs: first.swapBaseInputArgs,
+      swapRouteArgs,
+    };
+  }
 }
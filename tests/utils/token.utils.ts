@@ -23,7 +23,10 @@ import {
   ExtensionType,
   getMintLen,
   createInitializeTransferFeeConfigInstruction,
+  createInitializeTransferHookInstruction,
   createInitializeMintInstruction,
+  createSyncNativeInstruction,
+  NATIVE_MINT,
 } from "@solana/spl-token";
 import { BN } from "@coral-xyz/anchor";
 
@@ -159,6 +162,58 @@ export class TokenUtils {
     };
   }
 
+  async create2022MintWithTransferHook(
+    signer: Signer,
+    decimals: number,
+    hookProgram: PublicKey
+  ): Promise<Mint> {
+    let mintKeypair = Keypair.generate();
+
+    const extensions = [ExtensionType.TransferHook];
+    const mintLen = getMintLen(extensions);
+
+    const mintLamports =
+      await this.connection.getMinimumBalanceForRentExemption(mintLen);
+
+    const mintTransaction = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: signer.publicKey,
+        newAccountPubkey: mintKeypair.publicKey,
+        space: mintLen,
+        lamports: mintLamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferHookInstruction(
+        mintKeypair.publicKey,
+        signer.publicKey,
+        hookProgram,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeMintInstruction(
+        mintKeypair.publicKey,
+        decimals,
+        signer.publicKey,
+        signer.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+
+    await sendAndConfirmTransaction(
+      this.connection,
+      mintTransaction,
+      [signer, mintKeypair],
+      this.confirmOptions
+    );
+
+    return {
+      authority: signer.publicKey,
+      freezeAuthority: signer.publicKey,
+      decimals,
+      address: mintKeypair.publicKey,
+      program: TOKEN_2022_PROGRAM_ID,
+    };
+  }
+
   async createAta(
     signer: Signer,
     owner: PublicKey,
@@ -237,6 +292,39 @@ export class TokenUtils {
     };
   }
 
+  async initializeNativeMint(
+    signer: Signer,
+    lamports: number
+  ): Promise<TokenVault> {
+    let ata = await this.createAta(signer, signer.publicKey, NATIVE_MINT);
+
+    let tx = new Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: signer.publicKey,
+        toPubkey: ata,
+        lamports,
+      }),
+      createSyncNativeInstruction(ata)
+    );
+    await sendAndConfirmTransaction(
+      this.connection,
+      tx,
+      [signer],
+      this.confirmOptions
+    );
+
+    return {
+      address: ata,
+      mint: {
+        authority: PublicKey.default,
+        freezeAuthority: PublicKey.default,
+        decimals: 9,
+        address: NATIVE_MINT,
+        program: TOKEN_PROGRAM_ID,
+      },
+    };
+  }
+
   async initializeSplMintPair(
     signer: Signer,
     amount0: number,
@@ -289,6 +377,34 @@ export class TokenUtils {
     };
   }
 
+  async initialize2022HookMint(
+    signer: Signer,
+    amount: number,
+    hookProgram: PublicKey
+  ): Promise<TokenVault> {
+    let mint = await this.create2022MintWithTransferHook(
+      signer,
+      9,
+      hookProgram
+    );
+
+    let ata = await this.createAta(
+      signer,
+      signer.publicKey,
+      mint.address,
+      false,
+      mint.program,
+      this.confirmOptions.commitment
+    );
+
+    await this.mintTo(signer, signer, mint.address, ata, amount, mint.program);
+
+    return {
+      mint,
+      address: ata,
+    };
+  }
+
   async initialize2022MintPair(
     signer: Signer,
     amount0: number,
@@ -0,0 +1,95 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { TransferHook } from "../../target/types/transfer_hook";
+import {
+  PublicKey,
+  Signer,
+  ConfirmOptions,
+  AccountMeta,
+  SystemProgram,
+} from "@solana/web3.js";
+
+export class TransferHookUtils {
+  program: Program<TransferHook>;
+  pdaGetter: TransferHookPda;
+  confirmOptions: ConfirmOptions;
+
+  constructor(program: Program<TransferHook>, confirmOptions: ConfirmOptions) {
+    this.program = program;
+    this.confirmOptions = confirmOptions;
+    this.pdaGetter = new TransferHookPda(program.programId);
+  }
+
+  async initializeExtraAccountMetaList(signer: Signer, mint: PublicKey) {
+    let [extraAccountMetaList] =
+      this.pdaGetter.getExtraAccountMetaListAddress(mint);
+    let [counter] = this.pdaGetter.getCounterAddress(mint);
+
+    await this.program.methods
+      .initializeExtraAccountMetaList()
+      .accounts({
+        payer: signer.publicKey,
+        extraAccountMetaList,
+        mint,
+        counter,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc(this.confirmOptions);
+  }
+
+  // extra accounts the token program needs to execute the hook of the mint
+  hookAccounts(mint: PublicKey): AccountMeta[] {
+    let [extraAccountMetaList] =
+      this.pdaGetter.getExtraAccountMetaListAddress(mint);
+    let [counter] = this.pdaGetter.getCounterAddress(mint);
+
+    return [
+      { pubkey: this.program.programId, isSigner: false, isWritable: false },
+      { pubkey: extraAccountMetaList, isSigner: false, isWritable: false },
+      { pubkey: counter, isSigner: false, isWritable: true },
+    ];
+  }
+
+  async getCounterState(mint: PublicKey) {
+    let [counter] = this.pdaGetter.getCounterAddress(mint);
+    return await this.program.account.counterState.fetchNullable(counter);
+  }
+}
+
+export class TransferHookPda {
+  programId: PublicKey;
+  seeds: TransferHookSeeds;
+
+  constructor(programId: PublicKey) {
+    this.programId = programId;
+    this.seeds = new TransferHookSeeds();
+  }
+
+  getExtraAccountMetaListAddress(mint: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [this.seeds.extraAccountMetas, mint.toBuffer()],
+      this.programId
+    );
+  }
+
+  getCounterAddress(mint: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [this.seeds.counter, mint.toBuffer()],
+      this.programId
+    );
+  }
+}
+
+export class TransferHookSeeds {
+  extraAccountMetas: Buffer;
+  counter: Buffer;
+
+  constructor() {
+    this.extraAccountMetas = this.toSeed("extra-account-metas");
+    this.counter = this.toSeed("counter");
+  }
+
+  toSeed(seed: string) {
+    return Buffer.from(anchor.utils.bytes.utf8.encode(seed));
+  }
+}
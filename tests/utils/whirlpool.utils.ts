@@ -0,0 +1,106 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, Connection } from "@solana/web3.js";
+
+// whirlpool program and config in mainnet, cloned by the test validator
+export const whirlpoolProgram = new PublicKey(
+  "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"
+);
+export const whirlpoolsConfigAddress = new PublicKey(
+  "2LecshUwdy9xi7meFgHtFJQNSKk4KdTrcpvaB56dP2NQ"
+);
+export const whirlpoolTickSpacing = 128;
+// largest tick index of the tick spacing below the whirlpool max tick
+export const fullRangeTickIndex = 443520;
+const tickArraySize = 88;
+
+export class WhirlpoolUtils {
+  connection: Connection;
+  pdaGetter: WhirlpoolPda;
+
+  constructor(connection: Connection) {
+    this.connection = connection;
+    this.pdaGetter = new WhirlpoolPda(whirlpoolProgram);
+  }
+
+  async getAccountOwner(address: PublicKey) {
+    return (await this.connection.getAccountInfo(address))?.owner ?? null;
+  }
+}
+
+export class WhirlpoolPda {
+  programId: PublicKey;
+  seeds: WhirlpoolSeeds;
+
+  constructor(programId: PublicKey) {
+    this.programId = programId;
+    this.seeds = new WhirlpoolSeeds();
+  }
+
+  getWhirlpoolAddress(mint0: PublicKey, mint1: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [
+        this.seeds.whirlpool,
+        whirlpoolsConfigAddress.toBuffer(),
+        mint0.toBuffer(),
+        mint1.toBuffer(),
+        tickSpacingBytes(),
+      ],
+      this.programId
+    );
+  }
+
+  getFeeTierAddress() {
+    return PublicKey.findProgramAddressSync(
+      [
+        this.seeds.feeTier,
+        whirlpoolsConfigAddress.toBuffer(),
+        tickSpacingBytes(),
+      ],
+      this.programId
+    );
+  }
+
+  getTickArrayAddress(whirlpool: PublicKey, tickIndex: number) {
+    let ticks = tickArraySize * whirlpoolTickSpacing;
+    let startTickIndex = Math.floor(tickIndex / ticks) * ticks;
+    return PublicKey.findProgramAddressSync(
+      [
+        this.seeds.tickArray,
+        whirlpool.toBuffer(),
+        Buffer.from(startTickIndex.toString()),
+      ],
+      this.programId
+    );
+  }
+
+  getPositionAddress(positionMint: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+      [this.seeds.position, positionMint.toBuffer()],
+      this.programId
+    );
+  }
+}
+
+export class WhirlpoolSeeds {
+  whirlpool: Buffer;
+  feeTier: Buffer;
+  tickArray: Buffer;
+  position: Buffer;
+
+  constructor() {
+    this.whirlpool = this.toSeed("whirlpool");
+    this.feeTier = this.toSeed("fee_tier");
+    this.tickArray = this.toSeed("tick_array");
+    this.position = this.toSeed("position");
+  }
+
+  toSeed(seed: string) {
+    return Buffer.from(anchor.utils.bytes.utf8.encode(seed));
+  }
+}
+
+function tickSpacingBytes() {
+  let bytes = Buffer.alloc(2);
+  bytes.writeUInt16LE(whirlpoolTickSpacing);
+  return bytes;
+}